@@ -1,8 +1,11 @@
 use anyhow::{bail, Context as _};
 use indicatif::ProgressDrawTarget;
 use maplit::btreemap;
-use snowchains_core::{judge::CommandExpression, testsuite::TestSuite};
-use std::{env, ffi::OsString, fs, path::PathBuf};
+use snowchains_core::{
+    judge::{CommandExpression, JudgeOptions, Sandbox},
+    testsuite::TestSuite,
+};
+use std::{collections::HashSet, env, ffi::OsString, fs, num::NonZeroUsize, path::PathBuf};
 use structopt::StructOpt;
 use termcolor::BufferedStandardStream;
 
@@ -11,6 +14,15 @@ struct Opt {
     #[structopt(long, value_name("NAME"))]
     testcases: Option<Vec<String>>,
 
+    #[structopt(long)]
+    sandbox: bool,
+
+    #[structopt(long)]
+    jobs: Option<NonZeroUsize>,
+
+    #[structopt(long)]
+    fail_fast: bool,
+
     file: PathBuf,
 
     #[structopt(parse(from_os_str), raw(true), required(true))]
@@ -20,6 +32,9 @@ struct Opt {
 fn main() -> anyhow::Result<()> {
     let Opt {
         testcases,
+        sandbox,
+        jobs,
+        fail_fast,
         file,
         args,
     } = Opt::from_args();
@@ -33,28 +48,53 @@ fn main() -> anyhow::Result<()> {
     let test_suite = serde_yaml::from_slice(&test_suite)
         .with_context(|| format!("Could not parse the test file at `{}`", file.display()))?;
 
-    let test_suite = match test_suite {
-        TestSuite::Batch(test_suite) => test_suite,
-        _ => bail!("Only `Batch` is supported"),
+    let cmd = CommandExpression {
+        program: args[0].clone(),
+        args: args[1..].to_owned(),
+        cwd,
+        env: btreemap!(),
+        sandbox: sandbox.then(Sandbox::default),
     };
 
-    let test_cases = test_suite.load_test_cases(
-        file.parent().expect("should have file name"),
-        testcases.map(|ss| ss.into_iter().collect()),
-        |_| todo!(),
-    )?;
-
-    let outcome = snowchains_core::judge::judge(
-        ProgressDrawTarget::stderr(),
-        tokio::signal::ctrl_c,
-        &CommandExpression {
-            program: args[0].clone(),
-            args: args[1..].to_owned(),
-            cwd,
-            env: btreemap!(),
-        },
-        &test_cases,
-    )?;
+    let testcases = testcases.map(|ss| ss.into_iter().collect::<HashSet<_>>());
+
+    let outcome = match test_suite {
+        TestSuite::Batch(test_suite) => {
+            let test_cases = test_suite.load_test_cases(
+                file.parent().expect("should have file name"),
+                testcases,
+                |_| todo!(),
+            )?;
+
+            snowchains_core::judge::judge(
+                ProgressDrawTarget::stderr(),
+                tokio::signal::ctrl_c,
+                &cmd,
+                &test_cases,
+                JudgeOptions {
+                    jobs,
+                    fail_fast,
+                    ..JudgeOptions::default()
+                },
+            )?
+        }
+        TestSuite::Interactive(test_suite) => {
+            let test_cases = test_suite.load_test_cases(testcases)?;
+
+            snowchains_core::judge::judge_interactive(
+                ProgressDrawTarget::stderr(),
+                tokio::signal::ctrl_c,
+                &cmd,
+                &test_cases,
+                JudgeOptions {
+                    jobs,
+                    fail_fast,
+                    ..JudgeOptions::default()
+                },
+            )?
+        }
+        TestSuite::Unsubmittable => bail!("`Unsubmittable` is not supported"),
+    };
 
     let stdout = BufferedStandardStream::stdout(if atty::is(atty::Stream::Stdout) {
         termcolor::ColorChoice::Auto
@@ -0,0 +1,454 @@
+//! Line-by-line and word-by-word comparison of expected and actual outputs, shown for `Wrong Answer`s.
+
+use crate::testsuite::{self, DeterministicExpectedOutput};
+use itertools::{EitherOrBoth, Itertools as _};
+use std::io;
+use termcolor::{Color, WriteColor};
+
+const CONTEXT_LINES: usize = 2;
+const CONTEXT_WORDS: usize = 4;
+
+/// Prints the lines of `expected_text` and `actual` side by side, starting from a few lines
+/// before the first mismatch.
+///
+/// If `display_limit` is `Some`, lines (and words in a long line) beyond it are omitted.
+pub(crate) fn print_diff<W: WriteColor>(
+    wtr: &mut W,
+    expected: &DeterministicExpectedOutput,
+    expected_text: &str,
+    actual: &str,
+    display_limit: Option<usize>,
+) -> io::Result<()> {
+    let lines = expected_text
+        .split_inclusive('\n')
+        .zip_longest(actual.split_inclusive('\n'))
+        .map(|zip| LinePair::new(expected, zip))
+        .collect::<Vec<_>>();
+
+    let first_mismatch = lines.iter().position(|line| !line.matches);
+
+    wtr.set_color(color_spec!(Fg(Color::Yellow)))?;
+    match first_mismatch.map(|i| (i, &lines[i])) {
+        Some((i, LinePair { words, .. })) if !words.is_empty() => {
+            let (j, _) = words.iter().find_position(|w| !w.matches).unwrap();
+            writeln!(wtr, "first mismatch: line {}, word {}", i + 1, j + 1)?;
+        }
+        Some((i, LinePair { zip, .. })) => match zip {
+            EitherOrBoth::Both(..) => writeln!(wtr, "first mismatch: line {} (whitespace)", i + 1)?,
+            EitherOrBoth::Left(_) => writeln!(wtr, "first mismatch: line {} is missing", i + 1)?,
+            EitherOrBoth::Right(_) => writeln!(wtr, "first mismatch: extra line {}", i + 1)?,
+        },
+        None => writeln!(wtr, "no mismatching lines")?,
+    }
+    wtr.reset()?;
+
+    let focus = first_mismatch.unwrap_or(0);
+    let begin = if display_limit.is_some() {
+        focus.saturating_sub(CONTEXT_LINES)
+    } else {
+        0
+    };
+    let mut end = begin;
+    let mut size = 0;
+    while end < lines.len() {
+        size += lines[end].size();
+        if end > focus && matches!(display_limit, Some(l) if l < size) {
+            break;
+        }
+        end += 1;
+    }
+
+    let width = lines.len().to_string().len();
+
+    if begin > 0 {
+        wtr.set_color(color_spec!(Fg(Color::Yellow)))?;
+        writeln!(wtr, "… {} line(s)", begin)?;
+        wtr.reset()?;
+    }
+
+    for (i, line) in lines.iter().enumerate().take(end).skip(begin) {
+        line.print(wtr, i + 1, width, expected.float_tolerance(), display_limit)?;
+    }
+
+    if end < lines.len() {
+        wtr.set_color(color_spec!(Fg(Color::Yellow)))?;
+        writeln!(wtr, "… {} more line(s)", lines.len() - end)?;
+        wtr.reset()?;
+    }
+    Ok(())
+}
+
+struct LinePair<'a> {
+    zip: EitherOrBoth<&'a str, &'a str>,
+    matches: bool,
+    words: Vec<WordPair<'a>>,
+}
+
+impl<'a> LinePair<'a> {
+    fn new(expected: &DeterministicExpectedOutput, zip: EitherOrBoth<&'a str, &'a str>) -> Self {
+        let (matches, words) = if let EitherOrBoth::Both(line1, line2) = zip {
+            let words = line1
+                .split_whitespace()
+                .zip_longest(line2.split_whitespace())
+                .map(|zip| WordPair {
+                    matches: matches!(zip, EitherOrBoth::Both(w1, w2) if expected.word_accepts(w1, w2)),
+                    zip,
+                })
+                .collect::<Vec<_>>();
+
+            let matches = match expected {
+                DeterministicExpectedOutput::Pass => true,
+                DeterministicExpectedOutput::Exact { .. } => line1 == line2,
+                DeterministicExpectedOutput::Lines { .. } => strip_eol(line1) == strip_eol(line2),
+                DeterministicExpectedOutput::SplitWhitespace { .. }
+                | DeterministicExpectedOutput::Float { .. } => words.iter().all(|w| w.matches),
+            };
+
+            let words = if words.iter().all(|w| w.matches) {
+                vec![]
+            } else {
+                words
+            };
+
+            (matches, words)
+        } else {
+            (false, vec![])
+        };
+
+        Self {
+            zip,
+            matches,
+            words,
+        }
+    }
+
+    fn size(&self) -> usize {
+        match &self.zip {
+            EitherOrBoth::Both(line1, _) if self.matches => line1.len(),
+            EitherOrBoth::Both(line1, line2) => line1.len() + line2.len(),
+            EitherOrBoth::Left(line) | EitherOrBoth::Right(line) => line.len(),
+        }
+    }
+
+    fn print<W: WriteColor>(
+        &self,
+        wtr: &mut W,
+        line_number: usize,
+        width: usize,
+        float_tolerance: Option<(f64, f64)>,
+        display_limit: Option<usize>,
+    ) -> io::Result<()> {
+        let focus = self.words.iter().position(|w| !w.matches).unwrap_or(0);
+
+        let window = {
+            let sizes = if self.words.is_empty() {
+                let (EitherOrBoth::Both(line, _)
+                | EitherOrBoth::Left(line)
+                | EitherOrBoth::Right(line)) = &self.zip;
+                line.split_whitespace().map(str::len).collect::<Vec<_>>()
+            } else {
+                self.words
+                    .iter()
+                    .map(|w| match &w.zip {
+                        EitherOrBoth::Both(w1, w2) => w1.len().max(w2.len()),
+                        EitherOrBoth::Left(w) | EitherOrBoth::Right(w) => w.len(),
+                    })
+                    .collect()
+            };
+            word_window(&sizes, focus, display_limit)
+        };
+
+        let print_line = |wtr: &mut W, sign: char, color: Option<Color>, line: &str| {
+            if let Some(color) = color {
+                wtr.set_color(color_spec!(Fg(color)))?;
+            }
+            write!(wtr, "{} {:>width$} | ", sign, line_number, width = width)?;
+            wtr.reset()?;
+            print_words(wtr, line, color, &self.words, focus, window)
+        };
+
+        match &self.zip {
+            EitherOrBoth::Both(line, _) if self.matches => {
+                print_line(wtr, ' ', None, line)?;
+            }
+            EitherOrBoth::Both(line1, line2) => {
+                print_line(wtr, '-', Some(Color::Green), line1)?;
+                print_line(wtr, '+', Some(Color::Red), line2)?;
+
+                if let Some((relative_error, absolute_error)) = float_tolerance {
+                    for (i, word) in self.words.iter().enumerate() {
+                        if let (false, EitherOrBoth::Both(w1, w2)) = (word.matches, &word.zip) {
+                            if let (Ok(v1), Ok(v2)) = (w1.parse::<f64>(), w2.parse::<f64>()) {
+                                let (abs, rel) = testsuite::float_errors(v1, v2);
+                                wtr.set_color(color_spec!(Fg(Color::Yellow)))?;
+                                writeln!(
+                                    wtr,
+                                    "{:width$}   word {}: absolute error {:.3e} (> {:.3e}), \
+                                     relative error {:.3e} (> {:.3e})",
+                                    "",
+                                    i + 1,
+                                    abs,
+                                    absolute_error,
+                                    rel,
+                                    relative_error,
+                                    width = width,
+                                )?;
+                                wtr.reset()?;
+                            }
+                        }
+                    }
+                }
+            }
+            EitherOrBoth::Left(line) => print_line(wtr, '-', Some(Color::Green), line)?,
+            EitherOrBoth::Right(line) => print_line(wtr, '+', Some(Color::Red), line)?,
+        }
+        Ok(())
+    }
+}
+
+struct WordPair<'a> {
+    zip: EitherOrBoth<&'a str, &'a str>,
+    matches: bool,
+}
+
+/// Returns the range of words to show. Both lines of a pair share it so that they stay aligned.
+fn word_window(sizes: &[usize], focus: usize, display_limit: Option<usize>) -> (usize, usize) {
+    match display_limit {
+        Some(display_limit) if display_limit < sizes.iter().map(|n| n + 1).sum() => {
+            let begin = focus.saturating_sub(CONTEXT_WORDS);
+            let mut end = begin;
+            let mut size = 0;
+            for n in &sizes[begin..] {
+                size += n + 1;
+                if end > focus && display_limit < size {
+                    break;
+                }
+                end += 1;
+            }
+            (begin, end)
+        }
+        _ => (0, usize::MAX),
+    }
+}
+
+fn print_words(
+    wtr: &mut impl WriteColor,
+    line: &str,
+    color: Option<Color>,
+    word_pairs: &[WordPair<'_>],
+    focus: usize,
+    (begin, end): (usize, usize),
+) -> io::Result<()> {
+    let segments = segments(line);
+    let num_words = segments.iter().filter(|(is_word, _)| *is_word).count();
+
+    if begin > 0 {
+        wtr.set_color(color_spec!(Fg(Color::Yellow)))?;
+        write!(wtr, "… ")?;
+        wtr.reset()?;
+    }
+
+    let mut word_index = 0;
+    for (is_word, segment) in segments {
+        if is_word {
+            if begin <= word_index && word_index < end {
+                match (color, word_pairs.get(word_index)) {
+                    (Some(color), Some(WordPair { matches: false, .. })) if word_index == focus => {
+                        wtr.set_color(color_spec!(Bold, Underline, Fg(color)))?;
+                    }
+                    (Some(color), Some(WordPair { matches: false, .. })) => {
+                        wtr.set_color(color_spec!(Bold, Fg(color)))?;
+                    }
+                    _ => {}
+                }
+                write!(wtr, "{}", segment)?;
+                wtr.reset()?;
+            }
+            word_index += 1;
+        } else if begin < word_index && word_index < end
+            || begin == 0 && word_index == 0
+            || end >= num_words && word_index == num_words
+        {
+            print_whitespace(wtr, segment)?;
+        }
+    }
+
+    if end < num_words {
+        wtr.set_color(color_spec!(Fg(Color::Yellow)))?;
+        write!(wtr, " …")?;
+        wtr.reset()?;
+    }
+
+    if !line.ends_with('\n') {
+        wtr.set_color(color_spec!(Fg(Color::Yellow)))?;
+        write!(wtr, "⏎")?;
+        wtr.reset()?;
+    }
+    writeln!(wtr)
+}
+
+fn print_whitespace(wtr: &mut impl WriteColor, s: &str) -> io::Result<()> {
+    for c in s.chars() {
+        match c {
+            ' ' => write!(wtr, " ")?,
+            '\n' => {}
+            c => {
+                wtr.set_color(color_spec!(Fg(Color::Yellow)))?;
+                match c {
+                    '\r' => write!(wtr, "\\r")?,
+                    '\t' => write!(wtr, "\\t")?,
+                    c => write!(wtr, "{}", c.escape_unicode())?,
+                }
+                wtr.reset()?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Splits `line` into whitespace and non-whitespace segments, in the same way as `str::split_whitespace`.
+fn segments(line: &str) -> Vec<(bool, &str)> {
+    let mut segments = vec![];
+    let mut rest = line;
+    while let Some(c) = rest.chars().next() {
+        let is_word = !c.is_whitespace();
+        let len = rest
+            .find(|c: char| c.is_whitespace() == is_word)
+            .unwrap_or(rest.len());
+        segments.push((is_word, &rest[..len]));
+        rest = &rest[len..];
+    }
+    segments
+}
+
+fn strip_eol(line: &str) -> &str {
+    let line = line.strip_suffix('\n').unwrap_or(line);
+    line.strip_suffix('\r').unwrap_or(line)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testsuite::DeterministicExpectedOutput;
+    use itertools::Itertools as _;
+    use pretty_assertions::assert_eq;
+    use termcolor::NoColor;
+
+    fn print_diff(
+        expected: &DeterministicExpectedOutput,
+        actual: &str,
+        display_limit: Option<usize>,
+    ) -> String {
+        let mut wtr = NoColor::new(vec![]);
+        super::print_diff(
+            &mut wtr,
+            expected,
+            expected.expected_stdout().unwrap(),
+            actual,
+            display_limit,
+        )
+        .unwrap();
+        String::from_utf8(wtr.into_inner()).unwrap()
+    }
+
+    fn lines(lines: &[&str]) -> String {
+        lines.iter().map(|l| format!("{}\n", l)).join("")
+    }
+
+    #[test]
+    fn lines_mismatch() {
+        let expected = DeterministicExpectedOutput::Lines {
+            text: "1\n2 3 4\n5\n".into(),
+        };
+
+        assert_eq!(
+            lines(&[
+                "first mismatch: line 2, word 2",
+                "  1 | 1",
+                "- 2 | 2 3 4",
+                "+ 2 | 2 0 4",
+                "  3 | 5",
+            ]),
+            print_diff(&expected, "1\n2 0 4\n5\n", None),
+        );
+
+        assert_eq!(
+            lines(&[
+                "first mismatch: line 3 is missing",
+                "  1 | 1",
+                "  2 | 2 3 4",
+                "- 3 | 5",
+            ]),
+            print_diff(&expected, "1\n2 3 4\n", None),
+        );
+    }
+
+    #[test]
+    fn exact_whitespace() {
+        let expected = DeterministicExpectedOutput::Exact {
+            text: "Yes\n".into(),
+        };
+
+        assert_eq!(
+            lines(&[
+                "first mismatch: line 1 (whitespace)",
+                "- 1 | Yes",
+                "+ 1 | Yes\\r",
+            ]),
+            print_diff(&expected, "Yes\r\n", None),
+        );
+    }
+
+    #[test]
+    fn float_errors() {
+        let expected = DeterministicExpectedOutput::Float {
+            text: "1.0 2.0\n".into(),
+            relative_error: Some("1e-6".parse().unwrap()),
+            absolute_error: Some("1e-6".parse().unwrap()),
+        };
+
+        assert_eq!(
+            lines(&[
+                "first mismatch: line 1, word 2",
+                "- 1 | 1.0 2.0",
+                "+ 1 | 1.0000001 2.5",
+                "    word 2: absolute error 5.000e-1 (> 1.000e-6), relative error 2.000e-1 (> 1.000e-6)",
+            ]),
+            print_diff(&expected, "1.0000001 2.5\n", None),
+        );
+    }
+
+    #[test]
+    fn display_limit() {
+        let expected = DeterministicExpectedOutput::Lines {
+            text: (1..=100).join("\n").into(),
+        };
+        let actual = (1..=100).map(|i| if i == 50 { 0 } else { i }).join("\n");
+
+        assert_eq!(
+            lines(&[
+                "first mismatch: line 50, word 1",
+                "… 47 line(s)",
+                "   48 | 48",
+                "   49 | 49",
+                "-  50 | 50",
+                "+  50 | 0",
+                "… 50 more line(s)",
+            ]),
+            print_diff(&expected, &actual, Some(10)),
+        );
+
+        let expected = DeterministicExpectedOutput::SplitWhitespace {
+            text: (1..=100).join(" ").into(),
+        };
+        let actual = (1..=100).map(|i| if i == 50 { 0 } else { i }).join(" ");
+
+        assert_eq!(
+            lines(&[
+                "first mismatch: line 1, word 50",
+                "- 1 | … 46 47 48 49 50 51 …⏎",
+                "+ 1 | … 46 47 48 49 0 51 …⏎",
+            ]),
+            print_diff(&expected, &actual, Some(20)),
+        );
+    }
+}
@@ -1,27 +1,40 @@
-use crate::testsuite::{BatchTestCase, CheckerShell, ExpectedOutput};
-use anyhow::{anyhow, bail};
+use crate::testsuite::{
+    BatchTestCase, CheckerShell, ExpectedOutput, InteractiveTestCase, Interactor,
+};
+use anyhow::{anyhow, bail, Context as _};
 use futures_util::{select, FutureExt as _};
 use indicatif::{MultiProgress, ProgressBar, ProgressDrawTarget, ProgressStyle};
+use serde::{Serialize, Serializer};
 use std::{
     cmp,
-    collections::BTreeMap,
+    collections::{BTreeMap, BTreeSet},
     env,
     ffi::{OsStr, OsString},
     future::Future,
     io, iter,
-    path::{Path, PathBuf},
+    num::NonZeroUsize,
+    path::{Component, Path, PathBuf},
     process::{ExitStatus, Output, Stdio},
-    sync::Arc,
+    sync::{
+        atomic::{self, AtomicBool},
+        Arc,
+    },
     time::{Duration, Instant},
 };
+use strum::{EnumString, EnumVariantNames};
 use termcolor::{Color, WriteColor};
-use tokio::io::AsyncWriteExt as _;
+use tokio::io::{AsyncRead, AsyncReadExt as _, AsyncWrite, AsyncWriteExt as _};
 use unicode_width::UnicodeWidthStr as _;
 
+#[cfg(target_os = "linux")]
+use std::ffi::CStr;
+
 #[non_exhaustive]
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct JudgeOutcome {
     pub verdicts: Vec<Verdict>,
+    /// Statistics of the elapsed times for each test case. Only present with [`JudgeOptions::repeat`].
+    pub timings: Vec<Option<Timings>>,
 }
 
 impl JudgeOutcome {
@@ -47,79 +60,122 @@ impl JudgeOutcome {
             writeln!(wtr, "{}", verdict.summary())?;
             wtr.reset()?;
 
-            let mut write_text =
-                |header: &str, text: &str, highlight_numbers: bool| -> io::Result<()> {
-                    wtr.set_color(color_spec!(Bold, Fg(Color::Magenta)))?;
-                    writeln!(wtr, "{}", header)?;
+            if let Some(timings) = self.timings.get(i).copied().flatten() {
+                wtr.set_color(color_spec!(Bold, Fg(Color::Magenta)))?;
+                writeln!(wtr, "timings:")?;
+                wtr.reset()?;
+                write!(
+                    wtr,
+                    "min {} ms, median {} ms, max {} ms ({} runs)",
+                    timings.min.as_millis(),
+                    timings.median.as_millis(),
+                    timings.max.as_millis(),
+                    timings.runs,
+                )?;
+                if timings.near_timelimit {
+                    wtr.set_color(color_spec!(Bold, Fg(Color::Yellow)))?;
+                    write!(wtr, " near the timelimit")?;
                     wtr.reset()?;
+                }
+                writeln!(wtr)?;
+            }
 
-                    if text.is_empty() {
-                        wtr.set_color(color_spec!(Bold, Fg(Color::Yellow)))?;
-                        writeln!(wtr, "EMPTY")?;
-                        return wtr.reset();
-                    }
+            let write_text = |wtr: &mut W,
+                              header: &str,
+                              text: &str,
+                              highlight_numbers: bool|
+             -> io::Result<()> {
+                wtr.set_color(color_spec!(Bold, Fg(Color::Magenta)))?;
+                writeln!(wtr, "{}", header)?;
+                wtr.reset()?;
+
+                if text.is_empty() {
+                    wtr.set_color(color_spec!(Bold, Fg(Color::Yellow)))?;
+                    writeln!(wtr, "EMPTY")?;
+                    return wtr.reset();
+                }
 
-                    if matches!(display_limit, Some(l) if l < text.len()) {
-                        wtr.set_color(color_spec!(Bold, Fg(Color::Yellow)))?;
-                        writeln!(wtr, "{} B", text.len())?;
-                        return wtr.reset();
-                    }
+                if matches!(display_limit, Some(l) if l < text.len()) {
+                    wtr.set_color(color_spec!(Bold, Fg(Color::Yellow)))?;
+                    writeln!(wtr, "{} B", text.len())?;
+                    return wtr.reset();
+                }
 
-                    for token in parse_to_tokens(text, highlight_numbers) {
-                        match token {
-                            Token::SpcLf(s) | Token::Plain(s) => wtr.write_all(s.as_ref())?,
-                            Token::Cr(n) => {
-                                wtr.set_color(color_spec!(Fg(Color::Yellow)))?;
-                                (0..n).try_for_each(|_| wtr.write_all(b"\\r"))?;
-                                wtr.reset()?;
-                            }
-                            Token::Tab(n) => {
-                                wtr.set_color(color_spec!(Fg(Color::Yellow)))?;
-                                (0..n).try_for_each(|_| wtr.write_all(b"\\t"))?;
-                                wtr.reset()?;
-                            }
-                            Token::OtherWhitespaceControl(s) => {
-                                wtr.set_color(color_spec!(Fg(Color::Yellow)))?;
-                                write!(wtr, "{}", s.escape_unicode())?;
-                                wtr.reset()?;
-                            }
-                            Token::HighlightedNumber(s) => {
-                                wtr.set_color(color_spec!(Fg(Color::Cyan)))?;
-                                wtr.write_all(s.as_ref())?;
-                                wtr.reset()?;
-                            }
+                for token in parse_to_tokens(text, highlight_numbers) {
+                    match token {
+                        Token::SpcLf(s) | Token::Plain(s) => wtr.write_all(s.as_ref())?,
+                        Token::Cr(n) => {
+                            wtr.set_color(color_spec!(Fg(Color::Yellow)))?;
+                            (0..n).try_for_each(|_| wtr.write_all(b"\\r"))?;
+                            wtr.reset()?;
+                        }
+                        Token::Tab(n) => {
+                            wtr.set_color(color_spec!(Fg(Color::Yellow)))?;
+                            (0..n).try_for_each(|_| wtr.write_all(b"\\t"))?;
+                            wtr.reset()?;
+                        }
+                        Token::OtherWhitespaceControl(s) => {
+                            wtr.set_color(color_spec!(Fg(Color::Yellow)))?;
+                            write!(wtr, "{}", s.escape_unicode())?;
+                            wtr.reset()?;
+                        }
+                        Token::HighlightedNumber(s) => {
+                            wtr.set_color(color_spec!(Fg(Color::Cyan)))?;
+                            wtr.write_all(s.as_ref())?;
+                            wtr.reset()?;
                         }
                     }
+                }
 
-                    if !text.ends_with('\n') {
-                        wtr.set_color(color_spec!(Fg(Color::Yellow)))?;
-                        writeln!(wtr, "⏎")?;
-                        wtr.reset()?;
-                    }
+                if !text.ends_with('\n') {
+                    wtr.set_color(color_spec!(Fg(Color::Yellow)))?;
+                    writeln!(wtr, "⏎")?;
+                    wtr.reset()?;
+                }
 
-                    Ok(())
-                };
+                Ok(())
+            };
 
-            write_text("stdin:", verdict.stdin(), false)?;
-            if let Some(expected) = verdict.expected().expected_stdout() {
-                write_text("expected:", expected, verdict.expected().is_float())?;
-            } else if let Some(example) = verdict.expected().example() {
-                write_text("example:", example, verdict.expected().is_float())?;
-            }
-            if let Some(stdout) = verdict.stdout() {
-                write_text("actual:", stdout, verdict.expected().is_float())?;
+            let is_float = verdict.expected().is_float();
+
+            write_text(&mut wtr, "stdin:", verdict.stdin(), false)?;
+            if let (Verdict::WrongAnswer { stdout, .. }, ExpectedOutput::Deterministic(expected)) =
+                (verdict, verdict.expected())
+            {
+                if let Some(expected_stdout) = expected.expected_stdout() {
+                    wtr.set_color(color_spec!(Bold, Fg(Color::Magenta)))?;
+                    writeln!(wtr, "diff:")?;
+                    wtr.reset()?;
+                    crate::diff::print_diff(
+                        &mut wtr,
+                        expected,
+                        expected_stdout,
+                        stdout,
+                        display_limit,
+                    )?;
+                }
+            } else {
+                if let Some(expected) = verdict.expected().expected_stdout() {
+                    write_text(&mut wtr, "expected:", expected, is_float)?;
+                } else if let Some(example) = verdict.expected().example() {
+                    write_text(&mut wtr, "example:", example, is_float)?;
+                }
+                if let Some(stdout) = verdict.stdout() {
+                    write_text(&mut wtr, "actual:", stdout, is_float)?;
+                }
             }
             if let Some(stderr) = verdict.stderr().filter(|s| !s.is_empty()) {
-                write_text("stderr:", stderr, verdict.expected().is_float())?;
+                write_text(&mut wtr, "stderr:", stderr, is_float)?;
             }
             if let Some(checker_stdout) = verdict.checker_stdout().filter(|s| !s.is_empty()) {
-                write_text("checker stdout: ", checker_stdout, false)?;
+                write_text(&mut wtr, "checker stdout: ", checker_stdout, false)?;
             }
             if let Some(checker_stderr) = verdict.checker_stderr().filter(|s| !s.is_empty()) {
-                write_text("checker stderr: ", checker_stderr, false)?;
+                write_text(&mut wtr, "checker stderr: ", checker_stderr, false)?;
             }
             if let Some(wrong_answer_note) = verdict.wrong_answer_note() {
-                write_text("note: ", &(wrong_answer_note.to_string() + "\n"), false)?;
+                let note = wrong_answer_note.to_string() + "\n";
+                write_text(&mut wtr, "note: ", &note, false)?;
             }
         }
 
@@ -199,11 +255,139 @@ impl JudgeOutcome {
         }
     }
 
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("should not fail")
+    }
+
+    /// Renders the outcome as a JUnit XML report with one `<testsuite>` named `name`.
+    ///
+    /// `Runtime Error`s are reported as `<error>`s, cancelled test cases as `<skipped>`s, and the other rejections as `<failure>`s.
+    pub fn to_junit_xml(&self, name: &str) -> String {
+        let count = |p: fn(&Verdict) -> bool| self.verdicts.iter().filter(|v| p(v)).count();
+        let errors = count(|v| matches!(v, Verdict::RuntimeError { .. }));
+        let skipped = count(|v| matches!(v, Verdict::Cancelled { .. }));
+        let failures = count(|v| {
+            !matches!(
+                v,
+                Verdict::Accepted { .. } | Verdict::RuntimeError { .. } | Verdict::Cancelled { .. },
+            )
+        });
+        let time = self
+            .verdicts
+            .iter()
+            .flat_map(Verdict::elapsed)
+            .sum::<Duration>()
+            .as_secs_f64();
+
+        let mut xml = r#"<?xml version="1.0" encoding="UTF-8"?>"#.to_owned();
+        xml += "\n";
+        xml += &format!(
+            r#"<testsuites tests="{}" failures="{}" errors="{}" time="{:.3}">"#,
+            self.verdicts.len(),
+            failures,
+            errors,
+            time,
+        );
+        xml += "\n";
+        xml += &format!(
+            r#"  <testsuite name="{}" tests="{}" failures="{}" errors="{}" skipped="{}" time="{:.3}">"#,
+            escape_xml(name),
+            self.verdicts.len(),
+            failures,
+            errors,
+            skipped,
+            time,
+        );
+        xml += "\n";
+
+        for (i, verdict) in self.verdicts.iter().enumerate() {
+            let test_case_name = verdict
+                .test_case_name()
+                .map(ToOwned::to_owned)
+                .unwrap_or_else(|| (i + 1).to_string());
+
+            xml += &format!(
+                r#"    <testcase name="{}" classname="{}" time="{:.3}">"#,
+                escape_xml(&test_case_name),
+                escape_xml(name),
+                verdict.elapsed().unwrap_or_default().as_secs_f64(),
+            );
+            xml += "\n";
+
+            if let Verdict::Cancelled { .. } = verdict {
+                xml += "      <skipped/>\n";
+            } else if !matches!(verdict, Verdict::Accepted { .. }) {
+                let tag = if let Verdict::RuntimeError { .. } = verdict {
+                    "error"
+                } else {
+                    "failure"
+                };
+
+                let mut body = "".to_owned();
+                if let Some(expected) = verdict
+                    .expected()
+                    .expected_stdout()
+                    .or_else(|| verdict.expected().example())
+                {
+                    body += "expected:\n";
+                    body += expected;
+                }
+                if let Some(checker_stderr) = verdict.checker_stderr().filter(|s| !s.is_empty()) {
+                    body += "checker stderr:\n";
+                    body += checker_stderr;
+                }
+                if let Some(note) = verdict.wrong_answer_note() {
+                    body += &format!("note: {}\n", note);
+                }
+
+                xml += &format!(
+                    r#"      <{} type="{}" message="{}">{}</{}>"#,
+                    tag,
+                    verdict.kind(),
+                    escape_xml(&verdict.summary()),
+                    escape_xml(&body),
+                    tag,
+                );
+                xml += "\n";
+            }
+
+            xml += &format!(
+                "      <system-out>{}</system-out>\n",
+                escape_xml(verdict.stdout().unwrap_or("")),
+            );
+            xml += &format!(
+                "      <system-err>{}</system-err>\n",
+                escape_xml(verdict.stderr().unwrap_or("")),
+            );
+            xml += "    </testcase>\n";
+        }
+
+        xml += "  </testsuite>\n";
+        xml += "</testsuites>\n";
+        return xml;
+
+        fn escape_xml(s: &str) -> String {
+            s.chars()
+                .filter(|&c| matches!(c, '\t' | '\n' | '\r') || !c.is_control())
+                .fold("".to_owned(), |mut acc, c| {
+                    match c {
+                        '&' => acc += "&amp;",
+                        '<' => acc += "&lt;",
+                        '>' => acc += "&gt;",
+                        '"' => acc += "&quot;",
+                        '\'' => acc += "&apos;",
+                        c => acc.push(c),
+                    }
+                    acc
+                })
+        }
+    }
+
     pub fn error_on_fail(&self) -> anyhow::Result<()> {
         let fails = self
             .verdicts
             .iter()
-            .filter(|v| !matches!(v, Verdict::Accepted { .. }))
+            .filter(|v| !matches!(v, Verdict::Accepted { .. } | Verdict::Cancelled { .. }))
             .count();
 
         if fails > 0 {
@@ -219,11 +403,15 @@ impl JudgeOutcome {
     }
 }
 
+/// Result of a test case.
+///
+/// `memory` is the peak resident set size of the process in bytes. It is `None` on platforms where it cannot be measured.
 #[derive(Debug, Clone)]
 pub enum Verdict {
     Accepted {
         test_case_name: Option<String>,
         elapsed: Duration,
+        memory: Option<u64>,
         stdin: Arc<str>,
         stdout: Arc<str>,
         stderr: Arc<str>,
@@ -232,6 +420,7 @@ pub enum Verdict {
     WrongAnswer {
         test_case_name: Option<String>,
         elapsed: Duration,
+        memory: Option<u64>,
         stdin: Arc<str>,
         stdout: Arc<str>,
         stderr: Arc<str>,
@@ -243,6 +432,7 @@ pub enum Verdict {
     RuntimeError {
         test_case_name: Option<String>,
         elapsed: Duration,
+        memory: Option<u64>,
         stdin: Arc<str>,
         stdout: Arc<str>,
         stderr: Arc<str>,
@@ -255,15 +445,128 @@ pub enum Verdict {
         stdin: Arc<str>,
         expected: ExpectedOutput,
     },
+    MemoryLimitExceeded {
+        test_case_name: Option<String>,
+        elapsed: Duration,
+        memory: u64,
+        memorylimit: u64,
+        stdin: Arc<str>,
+        stdout: Arc<str>,
+        stderr: Arc<str>,
+        expected: ExpectedOutput,
+    },
+    /// The test case was cancelled because another one failed with [`JudgeOptions::fail_fast`].
+    Cancelled {
+        test_case_name: Option<String>,
+        stdin: Arc<str>,
+        expected: ExpectedOutput,
+    },
+    /// The process was killed by the [`Sandbox`].
+    ResourceLimitExceeded {
+        test_case_name: Option<String>,
+        elapsed: Duration,
+        memory: Option<u64>,
+        resource: Resource,
+        stdin: Arc<str>,
+        stdout: Arc<str>,
+        stderr: Arc<str>,
+        expected: ExpectedOutput,
+    },
+}
+
+impl Serialize for Verdict {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        #[derive(Serialize)]
+        struct Repr<'a> {
+            name: Option<&'a str>,
+            verdict: &'static str,
+            elapsed_ms: Option<u128>,
+            memory_bytes: Option<u64>,
+            exit_code: Option<i32>,
+            stdin: &'a str,
+            stdout: Option<&'a str>,
+            stderr: Option<&'a str>,
+            expected: Option<&'a str>,
+            checker_stdout: Option<&'a str>,
+            checker_stderr: Option<&'a str>,
+            note: Option<String>,
+        }
+
+        Repr {
+            name: self.test_case_name(),
+            verdict: self.kind(),
+            elapsed_ms: self.elapsed().map(|d| d.as_millis()),
+            memory_bytes: self.memory(),
+            exit_code: match self {
+                Self::Accepted { .. } | Self::WrongAnswer { .. } => Some(0),
+                Self::RuntimeError { status, .. } => status.code(),
+                Self::TimelimitExceeded { .. }
+                | Self::MemoryLimitExceeded { .. }
+                | Self::ResourceLimitExceeded { .. }
+                | Self::Cancelled { .. } => None,
+            },
+            stdin: self.stdin(),
+            stdout: self.stdout(),
+            stderr: self.stderr(),
+            expected: self
+                .expected()
+                .expected_stdout()
+                .or_else(|| self.expected().example()),
+            checker_stdout: self.checker_stdout(),
+            checker_stderr: self.checker_stderr(),
+            note: self.wrong_answer_note().map(|n| n.to_string()),
+        }
+        .serialize(serializer)
+    }
 }
 
 impl Verdict {
+    fn kind(&self) -> &'static str {
+        match self {
+            Self::Accepted { .. } => "Accepted",
+            Self::WrongAnswer { .. } => "WrongAnswer",
+            Self::RuntimeError { .. } => "RuntimeError",
+            Self::TimelimitExceeded { .. } => "TimelimitExceeded",
+            Self::MemoryLimitExceeded { .. } => "MemoryLimitExceeded",
+            Self::ResourceLimitExceeded { .. } => "ResourceLimitExceeded",
+            Self::Cancelled { .. } => "Cancelled",
+        }
+    }
+
+    fn elapsed(&self) -> Option<Duration> {
+        match *self {
+            Self::Accepted { elapsed, .. }
+            | Self::WrongAnswer { elapsed, .. }
+            | Self::RuntimeError { elapsed, .. }
+            | Self::MemoryLimitExceeded { elapsed, .. }
+            | Self::ResourceLimitExceeded { elapsed, .. } => Some(elapsed),
+            Self::TimelimitExceeded { .. } | Self::Cancelled { .. } => None,
+        }
+    }
+
+    fn memory(&self) -> Option<u64> {
+        match *self {
+            Self::Accepted { memory, .. }
+            | Self::WrongAnswer { memory, .. }
+            | Self::RuntimeError { memory, .. }
+            | Self::ResourceLimitExceeded { memory, .. } => memory,
+            Self::MemoryLimitExceeded { memory, .. } => Some(memory),
+            Self::TimelimitExceeded { .. } | Self::Cancelled { .. } => None,
+        }
+    }
+
     fn test_case_name(&self) -> Option<&str> {
         match self {
             Verdict::Accepted { test_case_name, .. }
             | Verdict::WrongAnswer { test_case_name, .. }
             | Verdict::RuntimeError { test_case_name, .. }
-            | Verdict::TimelimitExceeded { test_case_name, .. } => test_case_name.as_deref(),
+            | Verdict::TimelimitExceeded { test_case_name, .. }
+            | Verdict::MemoryLimitExceeded { test_case_name, .. }
+            | Verdict::ResourceLimitExceeded { test_case_name, .. }
+            | Verdict::Cancelled { test_case_name, .. } => test_case_name.as_deref(),
         }
     }
 
@@ -272,7 +575,10 @@ impl Verdict {
             Verdict::Accepted { stdin, .. }
             | Verdict::WrongAnswer { stdin, .. }
             | Verdict::RuntimeError { stdin, .. }
-            | Verdict::TimelimitExceeded { stdin, .. } => stdin,
+            | Verdict::TimelimitExceeded { stdin, .. }
+            | Verdict::MemoryLimitExceeded { stdin, .. }
+            | Verdict::ResourceLimitExceeded { stdin, .. }
+            | Verdict::Cancelled { stdin, .. } => stdin,
         }
     }
 
@@ -280,8 +586,10 @@ impl Verdict {
         match self {
             Verdict::Accepted { stdout, .. }
             | Verdict::WrongAnswer { stdout, .. }
-            | Verdict::RuntimeError { stdout, .. } => Some(stdout),
-            Verdict::TimelimitExceeded { .. } => None,
+            | Verdict::RuntimeError { stdout, .. }
+            | Verdict::MemoryLimitExceeded { stdout, .. }
+            | Verdict::ResourceLimitExceeded { stdout, .. } => Some(stdout),
+            Verdict::TimelimitExceeded { .. } | Verdict::Cancelled { .. } => None,
         }
     }
 
@@ -289,8 +597,10 @@ impl Verdict {
         match self {
             Verdict::Accepted { stderr, .. }
             | Verdict::WrongAnswer { stderr, .. }
-            | Verdict::RuntimeError { stderr, .. } => Some(stderr),
-            Verdict::TimelimitExceeded { .. } => None,
+            | Verdict::RuntimeError { stderr, .. }
+            | Verdict::MemoryLimitExceeded { stderr, .. }
+            | Verdict::ResourceLimitExceeded { stderr, .. } => Some(stderr),
+            Verdict::TimelimitExceeded { .. } | Verdict::Cancelled { .. } => None,
         }
     }
 
@@ -299,7 +609,10 @@ impl Verdict {
             Verdict::Accepted { expected, .. }
             | Verdict::WrongAnswer { expected, .. }
             | Verdict::RuntimeError { expected, .. }
-            | Verdict::TimelimitExceeded { expected, .. } => expected,
+            | Verdict::TimelimitExceeded { expected, .. }
+            | Verdict::MemoryLimitExceeded { expected, .. }
+            | Verdict::ResourceLimitExceeded { expected, .. }
+            | Verdict::Cancelled { expected, .. } => expected,
         }
     }
 
@@ -325,33 +638,86 @@ impl Verdict {
     }
 
     fn summary(&self) -> String {
-        match self {
-            Self::Accepted { elapsed, .. } => format!("Accepted ({} ms)", elapsed.as_millis()),
+        return match self {
+            Self::Accepted {
+                elapsed, memory, ..
+            } => format!(
+                "Accepted ({} ms{})",
+                elapsed.as_millis(),
+                format_memory(*memory),
+            ),
             Self::TimelimitExceeded { timelimit, .. } => {
                 format!("Timelimit Exceeded ({} ms)", timelimit.as_millis())
             }
-            Self::WrongAnswer { elapsed, .. } => {
-                format!("Wrong Answer ({} ms)", elapsed.as_millis())
-            }
+            Self::MemoryLimitExceeded {
+                elapsed,
+                memory,
+                memorylimit,
+                ..
+            } => format!(
+                "Memory Limit Exceeded ({} ms{} > {} KiB)",
+                elapsed.as_millis(),
+                format_memory(Some(*memory)),
+                memorylimit / 1024,
+            ),
+            Self::ResourceLimitExceeded {
+                elapsed,
+                memory,
+                resource,
+                ..
+            } => format!(
+                "Resource Limit Exceeded ({} ms{}, {})",
+                elapsed.as_millis(),
+                format_memory(*memory),
+                resource,
+            ),
+            Self::WrongAnswer {
+                elapsed, memory, ..
+            } => format!(
+                "Wrong Answer ({} ms{})",
+                elapsed.as_millis(),
+                format_memory(*memory),
+            ),
             Self::RuntimeError {
-                elapsed, status, ..
-            } => format!("Runtime Error ({} ms, {})", elapsed.as_millis(), status),
+                elapsed,
+                memory,
+                status,
+                ..
+            } => format!(
+                "Runtime Error ({} ms{}, {})",
+                elapsed.as_millis(),
+                format_memory(*memory),
+                status,
+            ),
+            Self::Cancelled { .. } => "Cancelled".to_owned(),
+        };
+
+        fn format_memory(memory: Option<u64>) -> String {
+            memory
+                .map(|memory| format!(", {} KiB", memory / 1024))
+                .unwrap_or_default()
         }
     }
 
     fn summary_color(&self) -> Color {
         match self {
             Self::Accepted { .. } => Color::Green,
-            Self::TimelimitExceeded { .. } => Color::Red,
+            Self::TimelimitExceeded { .. }
+            | Self::MemoryLimitExceeded { .. }
+            | Self::ResourceLimitExceeded { .. } => Color::Red,
             Self::WrongAnswer { .. } | Self::RuntimeError { .. } => Color::Yellow,
+            Self::Cancelled { .. } => Color::White,
         }
     }
 
     fn summary_style(&self) -> &'static str {
         match self {
             Self::Accepted { .. } => ".bold.green",
-            Self::TimelimitExceeded { .. } => ".bold.red",
+            Self::TimelimitExceeded { .. }
+            | Self::MemoryLimitExceeded { .. }
+            | Self::ResourceLimitExceeded { .. } => ".bold.red",
             Self::WrongAnswer { .. } | Self::RuntimeError { .. } => ".bold.yellow",
+            Self::Cancelled { .. } => ".bold",
         }
     }
 }
@@ -362,269 +728,1425 @@ pub enum WrongAnswerNote {
         fmt = "whitespace-separated words matched. try setting `match` to `SplitWhitespace`"
     )]
     WordsMatched,
+    #[display(fmt = "presentation error (wrong output format)")]
+    PresentationError,
+    #[display(fmt = "partially accepted ({} points)", _0)]
+    Points(f64),
 }
 
-#[derive(Debug, Clone)]
-pub struct CommandExpression {
-    pub program: OsString,
-    pub args: Vec<OsString>,
-    pub cwd: PathBuf,
-    pub env: BTreeMap<OsString, OsString>,
+/// A resource whose limit is enforced by [`Sandbox`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, derive_more::Display)]
+pub enum Resource {
+    #[display(fmt = "CPU time")]
+    CpuTime,
+    #[display(fmt = "file size")]
+    FileSize,
 }
 
-impl CommandExpression {
-    async fn build(
-        &self,
-        stdin: Option<&Path>,
-        stdout: &Path,
-        stderr: &Path,
-    ) -> io::Result<tokio::process::Command> {
-        let mut cmd = tokio::process::Command::new(&self.program);
-        let stdin = if let Some(stdin) = stdin {
-            tokio::fs::File::open(stdin).await?.into_std().await.into()
+/// Restrictions applied to the process of a [`CommandExpression`].
+///
+/// This only takes effect on Linux. In addition to the limits below, `RLIMIT_CPU` is set to the `timelimit` of each test case (rounded up to seconds) and `PR_SET_NO_NEW_PRIVS` is set.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Sandbox {
+    /// `RLIMIT_AS` in bytes.
+    pub address_space: Option<u64>,
+    /// `RLIMIT_FSIZE` in bytes. For batch problems, this also limits the size of the stdout.
+    pub file_size: Option<u64>,
+    /// `RLIMIT_NPROC`.
+    ///
+    /// Note that this counts all of the processes of the user unless the kernel counts them per user namespace.
+    pub processes: Option<u64>,
+    /// Enters new user and network namespaces so that the process cannot access the network.
+    ///
+    /// Spawning the process fails if unprivileged user namespaces are disabled. See [`Sandbox::degrade`].
+    pub unshare_network: bool,
+    /// Uses [Landlock](https://docs.kernel.org/userspace-api/landlock.html) so that the process cannot create, modify or
+    /// delete files except beneath `/dev` and `$TMPDIR`, which is set to an empty directory.
+    ///
+    /// Spawning the process fails if Landlock is not available. See [`Sandbox::degrade`].
+    pub protect_files: bool,
+    /// Uses a seccomp filter so that the system calls for administrating the system or inspecting other processes
+    /// (e.g. `mount`, `reboot`, `ptrace`) fail with `EPERM`. System calls of other architectures kill the process.
+    ///
+    /// Spawning the process fails if seccomp is not available. See [`Sandbox::degrade`].
+    pub filter_syscalls: bool,
+}
+
+impl Sandbox {
+    /// Returns the resource whose limit killed the process, if any.
+    ///
+    /// Some runtimes (e.g. CPython) ignore `SIGXFSZ` and fail with `EFBIG` instead, so a failure with `stdout_len` reaching `file_size` is also regarded as exceeding it.
+    fn exceeded(self, status: ExitStatus, stdout_len: Option<usize>) -> Option<Resource> {
+        #[cfg(unix)]
+        {
+            use std::os::unix::process::ExitStatusExt as _;
+
+            match status.signal() {
+                Some(libc::SIGXCPU) => return Some(Resource::CpuTime),
+                Some(libc::SIGXFSZ) => return Some(Resource::FileSize),
+                _ => {}
+            }
+        }
+
+        let reached_file_size = matches!(
+            (self.file_size, stdout_len),
+            (Some(file_size), Some(stdout_len)) if stdout_len as u64 >= file_size
+        );
+        if !status.success() && reached_file_size {
+            Some(Resource::FileSize)
         } else {
-            Stdio::piped()
-        };
-        let stdout = tokio::fs::File::create(stdout).await?.into_std().await;
-        let stderr = tokio::fs::File::create(stderr).await?.into_std().await;
-        cmd.args(&self.args)
-            .current_dir(&self.cwd)
-            .envs(&self.env)
-            .stdin(stdin)
-            .stdout(stdout)
-            .stderr(stderr);
-        Ok(cmd)
+            None
+        }
     }
-}
 
-pub fn judge<C: 'static + Future<Output = tokio::io::Result<()>> + Send>(
-    draw_target: ProgressDrawTarget,
-    ctrl_c: fn() -> C,
-    cmd: &CommandExpression,
-    test_cases: &[BatchTestCase],
-) -> anyhow::Result<JudgeOutcome> {
-    let cmd = Arc::new(cmd.clone());
-    let num_test_cases = test_cases.len();
+    /// Disables the features that are not available on this system, returning the reasons.
+    ///
+    /// Each feature is tried in a forked process.
+    pub fn degrade(mut self) -> (Self, Vec<String>) {
+        let mut reasons = vec![];
+
+        #[cfg(target_os = "linux")]
+        {
+            if self.unshare_network {
+                if let Err(err) = probe(unshare_network) {
+                    self.unshare_network = false;
+                    reasons.push(format!(
+                        "Could not enter new user and network namespaces ({}). The network is not isolated",
+                        err,
+                    ));
+                }
+            }
 
-    let quoted_name_width = test_cases
-        .iter()
-        .flat_map(|BatchTestCase { name, .. }| name.as_ref())
-        .map(|s| format!("{:?}", s).width())
-        .max()
-        .unwrap_or(0);
+            if self.protect_files {
+                let dev = CStr::from_bytes_with_nul(b"/dev\0").expect("should be valid");
+                if let Err(err) = probe(|| landlock::restrict_writes(&[dev])) {
+                    self.protect_files = false;
+                    reasons.push(format!(
+                        "Could not use Landlock ({}). The files are not protected",
+                        err,
+                    ));
+                }
+            }
 
-    let bash_exe = {
-        static GIT_BASH: &str = r"C:\Program Files\Git\bin\bash.exe";
+            if self.filter_syscalls {
+                if let Err(err) = probe(seccomp::filter) {
+                    self.filter_syscalls = false;
+                    reasons.push(format!(
+                        "Could not use seccomp ({}). The system calls are not filtered",
+                        err,
+                    ));
+                }
+            }
 
-        let bash_exe = if cfg!(windows) && Path::new(GIT_BASH).exists() {
-            GIT_BASH
-        } else {
-            "bash"
-        };
-        which::which_in(bash_exe, env::var_os("PATH"), &cmd.cwd)
-            .map_err(|_| anyhow!("`{}` not found", bash_exe))?
-    };
+            fn probe(f: impl FnOnce() -> io::Result<()>) -> io::Result<()> {
+                match unsafe { libc::fork() } {
+                    -1 => Err(io::Error::last_os_error()),
+                    0 => {
+                        let code = match f() {
+                            Ok(()) => 0,
+                            Err(err) => err.raw_os_error().unwrap_or(libc::EINVAL),
+                        };
+                        unsafe { libc::_exit(code) }
+                    }
+                    pid => {
+                        let mut status = 0;
+                        while unsafe { libc::waitpid(pid, &mut status, 0) } == -1 {
+                            let err = io::Error::last_os_error();
+                            if err.kind() != io::ErrorKind::Interrupted {
+                                return Err(err);
+                            }
+                        }
+                        match libc::WEXITSTATUS(status) {
+                            0 => Ok(()),
+                            errno => Err(io::Error::from_raw_os_error(errno)),
+                        }
+                    }
+                }
+            }
+        }
 
-    let tempdir = tempfile::Builder::new()
-        .prefix("snowchains-core-juding-")
-        .tempdir()?;
-    let tempdir_path = tempdir.path().to_owned();
+        (self, reasons)
+    }
 
-    let mp = MultiProgress::with_draw_target(draw_target);
+    #[cfg(target_os = "linux")]
+    fn enter(self, cpu_time: Option<u64>, tmpdir: Option<&CStr>) -> io::Result<()> {
+        if self.unshare_network {
+            unshare_network()?;
+        }
 
-    let mut targets = vec![];
+        let limits = [
+            (libc::RLIMIT_CPU, cpu_time.map(|t| (t, t + 1))),
+            (libc::RLIMIT_AS, self.address_space.map(|n| (n, n))),
+            (libc::RLIMIT_FSIZE, self.file_size.map(|n| (n, n))),
+            (libc::RLIMIT_NPROC, self.processes.map(|n| (n, n))),
+        ];
+
+        for (resource, limit) in limits {
+            if let Some((soft, hard)) = limit {
+                let rlimit = libc::rlimit {
+                    rlim_cur: soft as _,
+                    rlim_max: hard as _,
+                };
+                if unsafe { libc::setrlimit(resource, &rlimit) } == -1 {
+                    return Err(io::Error::last_os_error());
+                }
+            }
+        }
 
-    for (i, test_case) in test_cases.iter().enumerate() {
-        let pb = mp.add(ProgressBar::new_spinner());
+        if unsafe { libc::prctl(libc::PR_SET_NO_NEW_PRIVS, 1, 0, 0, 0) } == -1 {
+            return Err(io::Error::last_os_error());
+        }
 
-        pb.set_style(progress_style("{prefix}{spinner} {msg:bold}"));
+        if let Some(tmpdir) = tmpdir.filter(|_| self.protect_files) {
+            let dev = CStr::from_bytes_with_nul(b"/dev\0").expect("should be valid");
+            landlock::restrict_writes(&[dev, tmpdir])?;
+        }
 
-        pb.set_prefix(&format!(
-            "{}/{} ({} ",
-            align_right(&(i + 1).to_string(), num_test_cases.to_string().len()),
-            num_test_cases,
-            align_left(
-                &format!("{:?})", test_case.name.as_deref().unwrap_or("")),
-                quoted_name_width + 1,
-            ),
-        ));
+        if self.filter_syscalls {
+            seccomp::filter()?;
+        }
+        Ok(())
+    }
+}
 
-        pb.set_message("Judging...");
-        pb.enable_steady_tick(50);
+#[cfg(target_os = "linux")]
+fn unshare_network() -> io::Result<()> {
+    if unsafe { libc::unshare(libc::CLONE_NEWUSER | libc::CLONE_NEWNET) } == -1 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
 
-        targets.push((test_case.clone(), pb));
+/// Raw system calls for [Landlock](https://docs.kernel.org/userspace-api/landlock.html), which `libc` does not
+/// provide yet.
+#[cfg(target_os = "linux")]
+mod landlock {
+    use std::{ffi::CStr, io, mem, ptr};
+
+    const SYS_LANDLOCK_CREATE_RULESET: libc::c_long = 444;
+    const SYS_LANDLOCK_ADD_RULE: libc::c_long = 445;
+    const SYS_LANDLOCK_RESTRICT_SELF: libc::c_long = 446;
+
+    const LANDLOCK_CREATE_RULESET_VERSION: u32 = 1;
+    const LANDLOCK_RULE_PATH_BENEATH: libc::c_int = 1;
+
+    const LANDLOCK_ACCESS_FS_WRITE_FILE: u64 = 1 << 1;
+    /// From `LANDLOCK_ACCESS_FS_REMOVE_DIR` to `LANDLOCK_ACCESS_FS_MAKE_SYM`.
+    const LANDLOCK_ACCESS_FS_REMOVE_AND_MAKE: u64 = 0b1_1111_1111 << 4;
+    /// Since ABI 2.
+    const LANDLOCK_ACCESS_FS_REFER: u64 = 1 << 13;
+    /// Since ABI 3.
+    const LANDLOCK_ACCESS_FS_TRUNCATE: u64 = 1 << 14;
+
+    #[repr(C)]
+    struct RulesetAttr {
+        handled_access_fs: u64,
     }
 
-    let rt = tokio::runtime::Builder::new_multi_thread()
-        .enable_io()
-        .enable_time()
-        .build()?;
+    #[repr(C, packed)]
+    struct PathBeneathAttr {
+        allowed_access: u64,
+        parent_fd: i32,
+    }
 
-    let outcome = rt.spawn(async move {
-        let num_targets = targets.len();
+    /// Forbids the current thread and its future children to create, modify or delete files except beneath `dirs`.
+    ///
+    /// This sets `PR_SET_NO_NEW_PRIVS` as Landlock requires, and only makes system calls so that it can be called in
+    /// `pre_exec`.
+    pub(super) fn restrict_writes(dirs: &[&CStr]) -> io::Result<()> {
+        if unsafe { libc::prctl(libc::PR_SET_NO_NEW_PRIVS, 1, 0, 0, 0) } == -1 {
+            return Err(io::Error::last_os_error());
+        }
 
-        let (ctrl_c_tx, ctrl_c_rx) = tokio::sync::broadcast::channel(cmp::max(1, num_targets));
+        let abi = unsafe {
+            libc::syscall(
+                SYS_LANDLOCK_CREATE_RULESET,
+                ptr::null::<RulesetAttr>(),
+                0usize,
+                LANDLOCK_CREATE_RULESET_VERSION,
+            )
+        };
+        if abi == -1 {
+            return Err(io::Error::last_os_error());
+        }
 
-        let mut ctrl_c_rxs = iter::once(ctrl_c_rx)
-            .chain(iter::repeat_with(|| ctrl_c_tx.subscribe()))
-            .take(num_targets)
-            .collect::<Vec<_>>();
+        let mut access = LANDLOCK_ACCESS_FS_WRITE_FILE | LANDLOCK_ACCESS_FS_REMOVE_AND_MAKE;
+        if abi >= 2 {
+            access |= LANDLOCK_ACCESS_FS_REFER;
+        }
+        if abi >= 3 {
+            access |= LANDLOCK_ACCESS_FS_TRUNCATE;
+        }
 
-        tokio::task::spawn(async move {
-            let err_msg = match ctrl_c().await {
-                Ok(()) => "Recieved Ctrl-c".to_owned(),
-                Err(err) => err.to_string(),
+        let attr = RulesetAttr {
+            handled_access_fs: access,
+        };
+        let ruleset_fd = unsafe {
+            libc::syscall(
+                SYS_LANDLOCK_CREATE_RULESET,
+                &attr as *const RulesetAttr,
+                mem::size_of::<RulesetAttr>(),
+                0u32,
+            )
+        };
+        if ruleset_fd == -1 {
+            return Err(io::Error::last_os_error());
+        }
+
+        for dir in dirs {
+            let parent_fd = unsafe { libc::open(dir.as_ptr(), libc::O_PATH | libc::O_CLOEXEC) };
+            if parent_fd == -1 {
+                return Err(io::Error::last_os_error());
+            }
+            let rule = PathBeneathAttr {
+                allowed_access: access,
+                parent_fd,
             };
-            ctrl_c_tx.send(err_msg).unwrap();
-        });
+            let ret = unsafe {
+                libc::syscall(
+                    SYS_LANDLOCK_ADD_RULE,
+                    ruleset_fd,
+                    LANDLOCK_RULE_PATH_BENEATH,
+                    &rule as *const PathBeneathAttr,
+                    0u32,
+                )
+            };
+            unsafe { libc::close(parent_fd) };
+            if ret == -1 {
+                return Err(io::Error::last_os_error());
+            }
+        }
 
-        let (job_start_tx, mut job_start_rx) = tokio::sync::mpsc::channel(num_cpus::get());
-        for _ in 0..num_cpus::get() {
-            job_start_tx.send(()).await?;
+        let ret = unsafe { libc::syscall(SYS_LANDLOCK_RESTRICT_SELF, ruleset_fd, 0u32) };
+        unsafe { libc::close(ruleset_fd as _) };
+        if ret == -1 {
+            return Err(io::Error::last_os_error());
         }
+        Ok(())
+    }
+}
 
-        let mut results = vec![];
+/// A seccomp filter written in raw BPF, since `libc` does not provide the structs for it yet.
+#[cfg(target_os = "linux")]
+mod seccomp {
+    use std::io;
+
+    const SECCOMP_MODE_FILTER: libc::c_ulong = 2;
+
+    const SECCOMP_RET_KILL_PROCESS: u32 = 0x8000_0000;
+    const SECCOMP_RET_ERRNO: u32 = 0x0005_0000;
+    const SECCOMP_RET_ALLOW: u32 = 0x7fff_0000;
+
+    /// `BPF_LD | BPF_W | BPF_ABS`
+    const BPF_LD_W_ABS: u16 = 0x20;
+    /// `BPF_JMP | BPF_JEQ | BPF_K`
+    const BPF_JMP_JEQ_K: u16 = 0x15;
+    /// `BPF_JMP | BPF_JGE | BPF_K`
+    const BPF_JMP_JGE_K: u16 = 0x35;
+    /// `BPF_RET | BPF_K`
+    const BPF_RET_K: u16 = 0x06;
+
+    /// Offsets in `struct seccomp_data`.
+    const OFFSET_NR: u32 = 0;
+    const OFFSET_ARCH: u32 = 4;
+
+    #[cfg(target_arch = "x86_64")]
+    const AUDIT_ARCH: u32 = 0xc000_003e;
+    #[cfg(target_arch = "aarch64")]
+    const AUDIT_ARCH: u32 = 0xc000_00b7;
+
+    /// The x32 ABI shares `AUDIT_ARCH_X86_64` and sets this bit on the numbers. No numbers of aarch64 have it.
+    const X32_SYSCALL_BIT: u32 = 0x4000_0000;
+
+    const DENIED: &[libc::c_long] = &[
+        libc::SYS_bpf,
+        libc::SYS_chroot,
+        libc::SYS_delete_module,
+        libc::SYS_finit_module,
+        libc::SYS_init_module,
+        libc::SYS_kexec_load,
+        libc::SYS_mount,
+        libc::SYS_perf_event_open,
+        libc::SYS_pivot_root,
+        libc::SYS_process_vm_writev,
+        libc::SYS_ptrace,
+        libc::SYS_reboot,
+        libc::SYS_setns,
+        libc::SYS_swapoff,
+        libc::SYS_swapon,
+        libc::SYS_umount2,
+    ];
+
+    const LEN: usize = 6 + 2 * DENIED.len();
+
+    #[repr(C)]
+    #[derive(Clone, Copy)]
+    struct SockFilter {
+        code: u16,
+        jt: u8,
+        jf: u8,
+        k: u32,
+    }
 
-        for (i, (test_case, pb)) in targets.into_iter().enumerate() {
-            let cmd = cmd.clone();
-            let stdin_path = tempdir_path.join(format!("{}-stdin", i));
-            let actual_stdout_path = tempdir_path.join(format!("{}-actual-stdout", i));
-            let expected_stdout_path = tempdir_path.join(format!("{}-expected-stdout", i));
-            let stderr_path = tempdir_path.join(format!("{}-stderr", i));
-            let bash_exe = bash_exe.clone();
+    #[repr(C)]
+    struct SockFprog {
+        len: libc::c_ushort,
+        filter: *const SockFilter,
+    }
 
-            job_start_rx.recv().await;
+    /// Installs the filter to the current thread and its future children.
+    ///
+    /// This sets `PR_SET_NO_NEW_PRIVS` as seccomp requires, and does not allocate so that it can be called in
+    /// `pre_exec`.
+    #[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+    pub(super) fn filter() -> io::Result<()> {
+        fn stmt(code: u16, k: u32) -> SockFilter {
+            SockFilter {
+                code,
+                jt: 0,
+                jf: 0,
+                k,
+            }
+        }
 
-            let job_start_tx = job_start_tx.clone();
-            let mut ctrl_c_rx = ctrl_c_rxs.pop().expect("should have enough length");
-            let pb_clone = pb.clone();
+        fn jump(code: u16, k: u32, jt: u8, jf: u8) -> SockFilter {
+            SockFilter { code, jt, jf, k }
+        }
 
-            results.push(tokio::task::spawn(async move {
-                let result = tokio::task::spawn(async move {
-                    tokio::fs::write(&stdin_path, test_case.input.as_ref()).await?;
-
-                    let test_case_name = test_case.name.clone();
-                    let timelimit = test_case.timelimit;
-                    let stdin = test_case.input.clone();
-                    let expected = test_case.output.clone();
-
-                    let cwd = &cmd.cwd;
-                    let cmd = cmd
-                        .build(
-                            (stdin.len() >= 10 * 1024).then(|| &*stdin_path),
-                            &actual_stdout_path,
-                            &stderr_path,
-                        )
-                        .await?;
+        if unsafe { libc::prctl(libc::PR_SET_NO_NEW_PRIVS, 1, 0, 0, 0) } == -1 {
+            return Err(io::Error::last_os_error());
+        }
 
-                    let started = Instant::now();
+        let errno = SECCOMP_RET_ERRNO | libc::EPERM as u32;
+
+        let mut program = [stmt(BPF_RET_K, SECCOMP_RET_ALLOW); LEN];
+        program[0] = stmt(BPF_LD_W_ABS, OFFSET_ARCH);
+        program[1] = jump(BPF_JMP_JEQ_K, AUDIT_ARCH, 1, 0);
+        program[2] = stmt(BPF_RET_K, SECCOMP_RET_KILL_PROCESS);
+        program[3] = stmt(BPF_LD_W_ABS, OFFSET_NR);
+        // Jumps to the last `SECCOMP_RET_ERRNO`.
+        program[4] = jump(
+            BPF_JMP_JGE_K,
+            X32_SYSCALL_BIT,
+            2 * DENIED.len() as u8 - 1,
+            0,
+        );
+        for (i, &nr) in DENIED.iter().enumerate() {
+            program[5 + 2 * i] = jump(BPF_JMP_JEQ_K, nr as u32, 0, 1);
+            program[6 + 2 * i] = stmt(BPF_RET_K, errno);
+        }
+        // `program[LEN - 1]` allows the rest.
 
-                    let mut child = { cmd }.spawn()?;
+        let prog = SockFprog {
+            len: LEN as _,
+            filter: program.as_ptr(),
+        };
+        let ret = unsafe {
+            libc::prctl(
+                libc::PR_SET_SECCOMP,
+                SECCOMP_MODE_FILTER,
+                &prog as *const SockFprog,
+            )
+        };
+        if ret == -1 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(())
+    }
 
-                    if let Some(mut child_stdin) = child.stdin.take() {
-                        child_stdin.write_all((*stdin).as_ref()).await?;
-                    }
+    #[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+    pub(super) fn filter() -> io::Result<()> {
+        Err(io::Error::new(
+            io::ErrorKind::Other,
+            "seccomp filters are only supported on x86_64 and aarch64",
+        ))
+    }
+}
 
-                    macro_rules! with_ctrl_c {
-                        ($future:expr) => {
-                            select! {
-                                __output = $future => __output,
-                                err_msg = ctrl_c_rx.recv().fuse() => {
-                                    let _ = child.kill();
-                                    bail!("{}", err_msg?);
-                                },
-                            }
-                        };
-                    }
+/// Options for [`judge`] and [`judge_interactive`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JudgeOptions {
+    /// Number of test cases run concurrently. Defaults to the number of logical CPUs.
+    pub jobs: Option<NonZeroUsize>,
+    /// Cancels the remaining test cases once one of them fails.
+    pub fail_fast: bool,
+    /// Runs each test case this many times. Defaults to once.
+    pub repeat: Option<NonZeroUsize>,
+    /// Statistic of the elapsed times that the verdicts are based on when running test cases repeatedly.
+    pub statistic: Statistic,
+    /// Flags test cases whose `statistic` exceeds this fraction of the timelimit.
+    pub warning_ratio: Option<f64>,
+}
 
-                    let status = if let Some(timelimit) = timelimit {
-                        let timeout = timelimit + Duration::from_millis(100);
+#[derive(EnumString, EnumVariantNames, strum::Display, Debug, Clone, Copy, PartialEq, Eq)]
+#[strum(serialize_all = "lowercase")]
+pub enum Statistic {
+    Min,
+    Median,
+    Max,
+}
 
-                        if let Ok(status) =
-                            with_ctrl_c!(tokio::time::timeout(timeout, child.wait()).fuse())
-                        {
-                            status?
-                        } else {
-                            let _ = child.kill().await;
-                            let verdict = Verdict::TimelimitExceeded {
-                                test_case_name,
-                                timelimit,
+// `#[default]` on a variant needs Rust 1.62.
+#[allow(clippy::derivable_impls)]
+impl Default for Statistic {
+    fn default() -> Self {
+        Self::Median
+    }
+}
+
+/// Statistics of the elapsed times of repeated runs.
+#[derive(Debug, Clone, Copy)]
+pub struct Timings {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+    /// `true` if the statistic exceeds [`JudgeOptions::warning_ratio`] of the timelimit.
+    pub near_timelimit: bool,
+}
+
+impl Timings {
+    fn new(
+        elapsed_times: &[Duration],
+        statistic: Statistic,
+        timelimit: Option<Duration>,
+        warning_ratio: Option<f64>,
+    ) -> Self {
+        let mut sorted = elapsed_times.to_owned();
+        sorted.sort();
+
+        let n = sorted.len();
+        let median = if n % 2 == 1 {
+            sorted[n / 2]
+        } else {
+            (sorted[n / 2 - 1] + sorted[n / 2]) / 2
+        };
+
+        let mut timings = Self {
+            runs: n,
+            min: sorted[0],
+            median,
+            max: sorted[n - 1],
+            near_timelimit: false,
+        };
+        timings.near_timelimit = matches!(
+            (timelimit, warning_ratio),
+            (Some(timelimit), Some(ratio)) if timings.get(statistic) > timelimit.mul_f64(ratio)
+        );
+        timings
+    }
+
+    pub fn get(&self, statistic: Statistic) -> Duration {
+        match statistic {
+            Statistic::Min => self.min,
+            Statistic::Median => self.median,
+            Statistic::Max => self.max,
+        }
+    }
+}
+
+impl Serialize for Timings {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        #[derive(Serialize)]
+        struct Repr {
+            runs: usize,
+            min_ms: u128,
+            median_ms: u128,
+            max_ms: u128,
+            near_timelimit: bool,
+        }
+
+        Repr {
+            runs: self.runs,
+            min_ms: self.min.as_millis(),
+            median_ms: self.median.as_millis(),
+            max_ms: self.max.as_millis(),
+            near_timelimit: self.near_timelimit,
+        }
+        .serialize(serializer)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CommandExpression {
+    pub program: OsString,
+    pub args: Vec<OsString>,
+    pub cwd: PathBuf,
+    pub env: BTreeMap<OsString, OsString>,
+    pub sandbox: Option<Sandbox>,
+}
+
+impl CommandExpression {
+    /// Constructs a command that runs `cmd` with `bash -c`.
+    pub fn bash(
+        cmd: impl Into<OsString>,
+        cwd: PathBuf,
+        env: BTreeMap<OsString, OsString>,
+    ) -> anyhow::Result<Self> {
+        Ok(Self {
+            program: find_bash(&cwd)?.into(),
+            args: vec!["-c".into(), cmd.into()],
+            cwd,
+            env,
+            sandbox: None,
+        })
+    }
+
+    async fn build(
+        &self,
+        stdin: Option<&Path>,
+        stdout: &Path,
+        stderr: &Path,
+        tmpdir: &Path,
+        timelimit: Option<Duration>,
+    ) -> io::Result<tokio::process::Command> {
+        let mut cmd = tokio::process::Command::new(&self.program);
+        let stdin = if let Some(stdin) = stdin {
+            tokio::fs::File::open(stdin).await?.into_std().await.into()
+        } else {
+            Stdio::piped()
+        };
+        let stdout = tokio::fs::File::create(stdout).await?.into_std().await;
+        let stderr = tokio::fs::File::create(stderr).await?.into_std().await;
+        cmd.args(&self.args)
+            .current_dir(&self.cwd)
+            .envs(&self.env)
+            .stdin(stdin)
+            .stdout(stdout)
+            .stderr(stderr);
+        self.apply_sandbox(&mut cmd, tmpdir, timelimit).await?;
+        Ok(cmd)
+    }
+
+    async fn build_piped(
+        &self,
+        stderr: &Path,
+        tmpdir: &Path,
+        timelimit: Option<Duration>,
+    ) -> io::Result<tokio::process::Command> {
+        let mut cmd = tokio::process::Command::new(&self.program);
+        let stderr = tokio::fs::File::create(stderr).await?.into_std().await;
+        cmd.args(&self.args)
+            .current_dir(&self.cwd)
+            .envs(&self.env)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(stderr);
+        self.apply_sandbox(&mut cmd, tmpdir, timelimit).await?;
+        Ok(cmd)
+    }
+
+    /// With [`Sandbox::protect_files`], `tmpdir` is created and set to `$TMPDIR`.
+    async fn apply_sandbox(
+        &self,
+        cmd: &mut tokio::process::Command,
+        tmpdir: &Path,
+        timelimit: Option<Duration>,
+    ) -> io::Result<()> {
+        #[cfg(target_os = "linux")]
+        if let Some(sandbox) = self.sandbox {
+            use std::{ffi::CString, os::unix::ffi::OsStrExt as _};
+
+            let tmpdir = if sandbox.protect_files {
+                tokio::fs::create_dir_all(tmpdir).await?;
+                cmd.env("TMPDIR", tmpdir);
+                Some(CString::new(tmpdir.as_os_str().as_bytes())?)
+            } else {
+                None
+            };
+
+            let cpu_time =
+                timelimit.map(|t| cmp::max(t.as_secs() + u64::from(t.subsec_nanos() > 0), 1));
+            unsafe {
+                cmd.pre_exec(move || sandbox.enter(cpu_time, tmpdir.as_deref()));
+            }
+        }
+
+        #[cfg(not(target_os = "linux"))]
+        {
+            let _ = (cmd, tmpdir, timelimit);
+        }
+        Ok(())
+    }
+}
+
+pub fn judge<C: 'static + Future<Output = tokio::io::Result<()>> + Send>(
+    draw_target: ProgressDrawTarget,
+    ctrl_c: fn() -> C,
+    cmd: &CommandExpression,
+    test_cases: &[BatchTestCase],
+    options: JudgeOptions,
+) -> anyhow::Result<JudgeOutcome> {
+    let cmd = Arc::new(cmd.clone());
+    let bash_exe = find_bash(&cmd.cwd)?;
+
+    compile_testlib_checkers(&cmd.cwd, &bash_exe, test_cases)?;
+
+    let targets = test_cases
+        .iter()
+        .map(|test_case| (test_case.name.clone(), test_case.clone()))
+        .collect();
+
+    run_jobs(
+        draw_target,
+        ctrl_c,
+        options,
+        targets,
+        move |mut job, test_case| {
+            let cmd = cmd.clone();
+            let bash_exe = bash_exe.clone();
+
+            async move {
+                if job.cancelled {
+                    return Ok((
+                        Verdict::Cancelled {
+                            test_case_name: test_case.name,
+                            stdin: test_case.input,
+                            expected: test_case.output,
+                        },
+                        None,
+                    ));
+                }
+
+                let repeat = options.repeat.map_or(1, NonZeroUsize::get);
+                let mut runs = vec![];
+
+                for i in 0..repeat {
+                    if repeat > 1 {
+                        job.pb
+                            .set_message(&format!("Judging... ({}/{})", i + 1, repeat));
+                    }
+
+                    let (verdict, elapsed) =
+                        judge_batch_once(&mut job, &cmd, &bash_exe, &test_case).await?;
+                    let done = !matches!(
+                        verdict,
+                        Verdict::Accepted { .. } | Verdict::TimelimitExceeded { .. },
+                    );
+                    runs.push((verdict, elapsed));
+                    if done {
+                        break;
+                    }
+                }
+
+                Ok(summarize_runs(runs, test_case.timelimit, options))
+            }
+        },
+    )
+}
+
+/// Combines the verdicts of the runs of a test case, which end at the first one that is neither `Accepted` nor
+/// `TimelimitExceeded`.
+///
+/// With more than one run, the verdict is based on [`JudgeOptions::statistic`] of the elapsed times.
+fn summarize_runs(
+    runs: Vec<(Verdict, Duration)>,
+    timelimit: Option<Duration>,
+    options: JudgeOptions,
+) -> (Verdict, Option<Timings>) {
+    let elapsed_times = runs.iter().map(|&(_, elapsed)| elapsed).collect::<Vec<_>>();
+    let (mut accepted, mut timelimit_exceeded) = (None, None);
+
+    for (verdict, _) in runs {
+        match verdict {
+            Verdict::Accepted { .. } => accepted = Some(verdict),
+            Verdict::TimelimitExceeded { .. } => timelimit_exceeded = Some(verdict),
+            verdict => return (verdict, None),
+        }
+    }
+
+    if elapsed_times.len() == 1 {
+        let verdict = accepted.or(timelimit_exceeded).expect("should have run");
+        return (verdict, None);
+    }
+
+    let timings = Timings::new(
+        &elapsed_times,
+        options.statistic,
+        timelimit,
+        options.warning_ratio,
+    );
+    let statistic = timings.get(options.statistic);
+
+    // If the statistic exceeds the timelimit, at least one run has exceeded it.
+    let verdict = match accepted {
+        Some(Verdict::Accepted {
+            test_case_name,
+            memory,
+            stdin,
+            stdout,
+            stderr,
+            expected,
+            ..
+        }) if !matches!(timelimit, Some(t) if statistic > t) => Verdict::Accepted {
+            test_case_name,
+            elapsed: statistic,
+            memory,
+            stdin,
+            stdout,
+            stderr,
+            expected,
+        },
+        _ => timelimit_exceeded.expect("should exceed the timelimit at least once"),
+    };
+    (verdict, Some(timings))
+}
+
+/// Runs `cmd` once for `test_case`, returning the verdict and the elapsed time.
+///
+/// If the process is killed for exceeding the timelimit, the elapsed time is the time until it is killed.
+async fn judge_batch_once(
+    job: &mut Job,
+    cmd: &CommandExpression,
+    bash_exe: &Path,
+    test_case: &BatchTestCase,
+) -> anyhow::Result<(Verdict, Duration)> {
+    let stdin_path = job.temp_path("stdin");
+    let actual_stdout_path = job.temp_path("actual-stdout");
+    let expected_stdout_path = job.temp_path("expected-stdout");
+    let stderr_path = job.temp_path("stderr");
+    let tmpdir = job.temp_path("tmp");
+
+    let ctrl_c_rx = &mut job.ctrl_c_rx;
+
+    tokio::fs::write(&stdin_path, test_case.input.as_ref()).await?;
+
+    let test_case_name = test_case.name.clone();
+    let timelimit = test_case.timelimit;
+    let memorylimit = test_case.memorylimit;
+    let stdin = test_case.input.clone();
+    let expected = test_case.output.clone();
+
+    let cwd = &cmd.cwd;
+    let sandbox = cmd.sandbox;
+    let cmd = cmd
+        .build(
+            (stdin.len() >= 10 * 1024).then(|| &*stdin_path),
+            &actual_stdout_path,
+            &stderr_path,
+            &tmpdir,
+            timelimit,
+        )
+        .await?;
+
+    let started = Instant::now();
+
+    let mut child = { cmd }.spawn()?;
+
+    if let Some(mut child_stdin) = child.stdin.take() {
+        child_stdin.write_all((*stdin).as_ref()).await?;
+    }
+
+    macro_rules! with_ctrl_c {
+        ($future:expr) => {
+            select! {
+                __output = $future => __output,
+                interruption = ctrl_c_rx.recv().fuse() => {
+                    let _ = child.start_kill();
+                    match interruption? {
+                        Interruption::CtrlC(err_msg) => bail!("{}", err_msg),
+                        Interruption::FailFast => {
+                            let verdict = Verdict::Cancelled {
+                                test_case_name,
                                 stdin,
                                 expected,
                             };
-                            tokio::task::block_in_place(|| {
-                                pb_clone.set_style(progress_style(&format!(
-                                    "{{prefix}}{{msg:{}}}",
-                                    verdict.summary_style(),
-                                )));
-                                pb_clone.finish_with_message(&verdict.summary());
-                            });
-                            return Ok(verdict);
+                            return Ok((verdict, Instant::now() - started));
                         }
-                    } else {
-                        with_ctrl_c!(child.wait().fuse())?
-                    };
-
-                    let elapsed = Instant::now() - started;
-
-                    let stdout = utf8(tokio::fs::read(&actual_stdout_path).await?)?;
-                    let stderr = utf8(tokio::fs::read(&stderr_path).await?)?;
-
-                    if matches!(timelimit, Some(t) if t < elapsed) {
-                        Ok(Verdict::TimelimitExceeded {
-                            test_case_name,
-                            timelimit: timelimit.unwrap(),
-                            stdin,
-                            expected,
-                        })
-                    } else if !status.success() {
-                        Ok(Verdict::RuntimeError {
-                            test_case_name,
-                            elapsed,
-                            stdin,
-                            stdout,
-                            stderr,
-                            expected,
-                            status,
-                        })
-                    } else if let Err((checker_stdout, checker_stderr, note)) = check(
-                        &test_case.output,
-                        &stdout,
-                        cwd,
-                        &stdin_path,
-                        &actual_stdout_path,
-                        &expected_stdout_path,
-                        &bash_exe,
-                    )
-                    .await?
-                    {
-                        Ok(Verdict::WrongAnswer {
-                            test_case_name,
-                            elapsed,
-                            stdin,
-                            stdout,
-                            stderr,
-                            checker_stdout,
-                            checker_stderr,
-                            expected,
-                            note,
-                        })
-                    } else {
-                        Ok(Verdict::Accepted {
-                            test_case_name,
-                            elapsed,
-                            stdin,
-                            stdout,
-                            stderr,
-                            expected,
-                        })
                     }
-                })
+                },
+            }
+        };
+    }
+
+    let (status, memory) = if let Some(timelimit) = timelimit {
+        let timeout = timelimit + Duration::from_millis(100);
+
+        if let Ok(status) = with_ctrl_c!(tokio::time::timeout(timeout, wait(&mut child)).fuse()) {
+            status?
+        } else {
+            let _ = child.kill().await;
+            let verdict = Verdict::TimelimitExceeded {
+                test_case_name,
+                timelimit,
+                stdin,
+                expected,
+            };
+            return Ok((verdict, Instant::now() - started));
+        }
+    } else {
+        with_ctrl_c!(wait(&mut child).fuse())?
+    };
+
+    let elapsed = Instant::now() - started;
+
+    let stdout = utf8(tokio::fs::read(&actual_stdout_path).await?)?;
+    let stderr = utf8(tokio::fs::read(&stderr_path).await?)?;
+
+    let verdict = if matches!(timelimit, Some(t) if t < elapsed) {
+        Verdict::TimelimitExceeded {
+            test_case_name,
+            timelimit: timelimit.unwrap(),
+            stdin,
+            expected,
+        }
+    } else if let Some((memory, memorylimit)) = memory
+        .zip(memorylimit)
+        .filter(|(memory, memorylimit)| memory > memorylimit)
+    {
+        Verdict::MemoryLimitExceeded {
+            test_case_name,
+            elapsed,
+            memory,
+            memorylimit,
+            stdin,
+            stdout,
+            stderr,
+            expected,
+        }
+    } else if let Some(resource) = sandbox.and_then(|s| s.exceeded(status, Some(stdout.len()))) {
+        Verdict::ResourceLimitExceeded {
+            test_case_name,
+            elapsed,
+            memory,
+            resource,
+            stdin,
+            stdout,
+            stderr,
+            expected,
+        }
+    } else if !status.success() {
+        Verdict::RuntimeError {
+            test_case_name,
+            elapsed,
+            memory,
+            stdin,
+            stdout,
+            stderr,
+            expected,
+            status,
+        }
+    } else if let Err((checker_stdout, checker_stderr, note)) = check(
+        &test_case.output,
+        &stdout,
+        cwd,
+        &stdin_path,
+        &actual_stdout_path,
+        &expected_stdout_path,
+        bash_exe,
+    )
+    .await?
+    {
+        Verdict::WrongAnswer {
+            test_case_name,
+            elapsed,
+            memory,
+            stdin,
+            stdout,
+            stderr,
+            checker_stdout,
+            checker_stderr,
+            expected,
+            note,
+        }
+    } else {
+        Verdict::Accepted {
+            test_case_name,
+            elapsed,
+            memory,
+            stdin,
+            stdout,
+            stderr,
+            expected,
+        }
+    };
+    Ok((verdict, elapsed))
+}
+
+/// Runs `cmd` against interactors, connecting the stdout of each process to the stdin of the other.
+///
+/// The verdict is decided by the exit status of the interactor.
+pub fn judge_interactive<C: 'static + Future<Output = tokio::io::Result<()>> + Send>(
+    draw_target: ProgressDrawTarget,
+    ctrl_c: fn() -> C,
+    cmd: &CommandExpression,
+    test_cases: &[InteractiveTestCase],
+    options: JudgeOptions,
+) -> anyhow::Result<JudgeOutcome> {
+    let cmd = Arc::new(cmd.clone());
+    let bash_exe = find_bash(&cmd.cwd)?;
+
+    let targets = test_cases
+        .iter()
+        .map(|test_case| (test_case.name.clone(), test_case.clone()))
+        .collect();
+
+    run_jobs(
+        draw_target,
+        ctrl_c,
+        options,
+        targets,
+        move |mut job, test_case| {
+            let cmd = cmd.clone();
+            let bash_exe = bash_exe.clone();
+
+            async move {
+                if job.cancelled {
+                    return Ok((
+                        Verdict::Cancelled {
+                            test_case_name: test_case.name,
+                            stdin: Arc::from(""),
+                            expected: ExpectedOutput::interactor(
+                                test_case.interactor.cmd,
+                                test_case.interactor.shell,
+                            ),
+                        },
+                        None,
+                    ));
+                }
+
+                let repeat = options.repeat.map_or(1, NonZeroUsize::get);
+                let mut runs = vec![];
+
+                for i in 0..repeat {
+                    if repeat > 1 {
+                        job.pb
+                            .set_message(&format!("Judging... ({}/{})", i + 1, repeat));
+                    }
+
+                    let started = Instant::now();
+                    let verdict =
+                        judge_interactive_once(&mut job, &cmd, &bash_exe, &test_case).await?;
+                    let elapsed = verdict.elapsed().unwrap_or_else(|| started.elapsed());
+                    let done = !matches!(
+                        verdict,
+                        Verdict::Accepted { .. } | Verdict::TimelimitExceeded { .. },
+                    );
+                    runs.push((verdict, elapsed));
+                    if done {
+                        break;
+                    }
+                }
+
+                Ok(summarize_runs(runs, test_case.timelimit, options))
+            }
+        },
+    )
+}
+
+async fn judge_interactive_once(
+    job: &mut Job,
+    cmd: &CommandExpression,
+    bash_exe: &Path,
+    test_case: &InteractiveTestCase,
+) -> anyhow::Result<Verdict> {
+    let input_path = job.temp_path("input");
+    let stderr_path = job.temp_path("stderr");
+    let interactor_stderr_path = job.temp_path("interactor-stderr");
+    let tmpdir = job.temp_path("tmp");
+
+    let ctrl_c_rx = &mut job.ctrl_c_rx;
+
+    let InteractiveTestCase {
+        name: test_case_name,
+        timelimit,
+        input,
+        interactor: Interactor {
+            cmd: interactor_cmd,
+            shell,
+        },
+    } = test_case.clone();
+
+    let mut interactor = {
+        let (program, args) = match shell {
+            CheckerShell::Bash => (&bash_exe, [OsStr::new("-c"), OsStr::new(&interactor_cmd)]),
+        };
+
+        let mut interactor = tokio::process::Command::new(program);
+        interactor.args(args).envs(&cmd.env);
+
+        if let Some(input) = &input {
+            tokio::fs::write(&input_path, input.as_ref()).await?;
+            interactor.env("INPUT", &input_path);
+        }
+
+        let stderr = tokio::fs::File::create(&interactor_stderr_path)
+            .await?
+            .into_std()
+            .await;
+
+        interactor
+            .current_dir(&cmd.cwd)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(stderr)
+            .kill_on_drop(true)
+            .spawn()?
+    };
+
+    let started = Instant::now();
+
+    let mut solution = {
+        let mut solution = cmd.build_piped(&stderr_path, &tmpdir, timelimit).await?;
+        solution.kill_on_drop(true).spawn()?
+    };
+
+    let solution_stdout = tokio::spawn(relay(
+        solution.stdout.take().expect("should be piped"),
+        interactor.stdin.take().expect("should be piped"),
+    ));
+    let solution_stdin = tokio::spawn(relay(
+        interactor.stdout.take().expect("should be piped"),
+        solution.stdin.take().expect("should be piped"),
+    ));
+
+    let statuses = {
+        let wait = async {
+            let status = wait(&mut solution).await;
+            let elapsed = Instant::now() - started;
+            (status, elapsed, interactor.wait().await)
+        };
+
+        let timeout = timelimit.map_or(INTERACTIVE_FALLBACK_TIMELIMIT, |t| {
+            t + Duration::from_millis(100)
+        });
+        let wait = async { tokio::time::timeout(timeout, wait).await.ok() };
+
+        select! {
+            statuses = wait.fuse() => Ok(statuses),
+            interruption = ctrl_c_rx.recv().fuse() => Err(interruption),
+        }
+    };
+
+    let statuses = match statuses {
+        Ok(statuses) => statuses,
+        Err(interruption) => {
+            let _ = solution.start_kill();
+            let _ = interactor.start_kill();
+            match interruption? {
+                Interruption::CtrlC(err_msg) => bail!("{}", err_msg),
+                Interruption::FailFast => {
+                    let stdin = utf8(solution_stdin.await??)?;
+                    let expected = ExpectedOutput::interactor(interactor_cmd, shell);
+                    return Ok(Verdict::Cancelled {
+                        test_case_name,
+                        stdin,
+                        expected,
+                    });
+                }
+            }
+        }
+    };
+
+    let ((status, memory), elapsed, interactor_status) = match statuses {
+        Some((status, elapsed, interactor_status)) => (status?, elapsed, interactor_status?),
+        None => {
+            let _ = solution.kill().await;
+            let _ = interactor.kill().await;
+            let stdin = utf8(solution_stdin.await??)?;
+            let expected = ExpectedOutput::interactor(interactor_cmd, shell);
+
+            return Ok(Verdict::TimelimitExceeded {
+                test_case_name,
+                timelimit: timelimit.unwrap_or(INTERACTIVE_FALLBACK_TIMELIMIT),
+                stdin,
+                expected,
+            });
+        }
+    };
+
+    let stdin = utf8(solution_stdin.await??)?;
+    let stdout = utf8(solution_stdout.await??)?;
+    let stderr = utf8(tokio::fs::read(&stderr_path).await?)?;
+    let interactor_stderr = utf8(tokio::fs::read(&interactor_stderr_path).await?)?;
+    let expected = ExpectedOutput::interactor(interactor_cmd, shell);
+
+    if matches!(timelimit, Some(t) if t < elapsed) {
+        Ok(Verdict::TimelimitExceeded {
+            test_case_name,
+            timelimit: timelimit.unwrap(),
+            stdin,
+            expected,
+        })
+    } else if let Some(resource) = cmd.sandbox.and_then(|s| s.exceeded(status, None)) {
+        Ok(Verdict::ResourceLimitExceeded {
+            test_case_name,
+            elapsed,
+            memory,
+            resource,
+            stdin,
+            stdout,
+            stderr,
+            expected,
+        })
+    } else if !status.success() {
+        Ok(Verdict::RuntimeError {
+            test_case_name,
+            elapsed,
+            memory,
+            stdin,
+            stdout,
+            stderr,
+            expected,
+            status,
+        })
+    } else if !interactor_status.success() {
+        Ok(Verdict::WrongAnswer {
+            test_case_name,
+            elapsed,
+            memory,
+            stdin,
+            stdout,
+            stderr,
+            checker_stdout: Arc::from(""),
+            checker_stderr: interactor_stderr,
+            expected,
+            note: None,
+        })
+    } else {
+        Ok(Verdict::Accepted {
+            test_case_name,
+            elapsed,
+            memory,
+            stdin,
+            stdout,
+            stderr,
+            expected,
+        })
+    }
+}
+
+/// Wall-clock limit for interactive test cases without `timelimit`, so that a wedged interactor does not
+/// hang the judge.
+const INTERACTIVE_FALLBACK_TIMELIMIT: Duration = Duration::from_secs(60);
+
+/// Waits for `child` to exit, returning the exit status and the peak resident set size in bytes.
+///
+/// On Linux, `waitid(2)` with `WNOWAIT` waits for the child without reaping it and retrieves its `rusage`. The
+/// child is then reaped by `Child::wait`, so that Tokio keeps ownership of it.
+async fn wait(child: &mut tokio::process::Child) -> io::Result<(ExitStatus, Option<u64>)> {
+    #[cfg(target_os = "linux")]
+    {
+        let pid = child
+            .id()
+            .expect("should not have been polled to completion");
+        let memory = tokio::task::spawn_blocking(move || peek_maxrss(pid))
+            .await
+            .expect("should not panic")?;
+        return Ok((child.wait().await?, Some(memory)));
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        return Ok((child.wait().await?, None));
+    }
+
+    #[cfg(target_os = "linux")]
+    fn peek_maxrss(pid: u32) -> io::Result<u64> {
+        let mut info = unsafe { std::mem::zeroed::<libc::siginfo_t>() };
+        let mut rusage = unsafe { std::mem::zeroed::<libc::rusage>() };
+
+        // The raw system call takes a fifth `rusage` argument that the libc wrapper does not expose.
+        while unsafe {
+            libc::syscall(
+                libc::SYS_waitid,
+                libc::P_PID,
+                pid,
+                &mut info as *mut libc::siginfo_t,
+                libc::WEXITED | libc::WNOWAIT,
+                &mut rusage as *mut libc::rusage,
+            )
+        } == -1
+        {
+            let err = io::Error::last_os_error();
+            if err.kind() != io::ErrorKind::Interrupted {
+                return Err(err);
+            }
+        }
+
+        // `ru_maxrss` is in kilobytes.
+        Ok(rusage.ru_maxrss as u64 * 1024)
+    }
+}
+
+/// Copies `from` to `to` until EOF, returning everything that has been read.
+///
+/// Once `to` is closed by the other side, the rest is still read and recorded but discarded.
+async fn relay(
+    mut from: impl AsyncRead + Unpin,
+    to: impl AsyncWrite + Unpin,
+) -> io::Result<Vec<u8>> {
+    let mut to = Some(to);
+    let mut transcript = vec![];
+    let mut buf = [0; 4096];
+
+    loop {
+        let n = from.read(&mut buf).await?;
+        if n == 0 {
+            return Ok(transcript);
+        }
+        transcript.extend_from_slice(&buf[..n]);
+
+        if let Some(w) = &mut to {
+            if w.write_all(&buf[..n]).await.is_err() || w.flush().await.is_err() {
+                to = None;
+            }
+        }
+    }
+}
+
+struct Job {
+    index: usize,
+    tempdir_path: PathBuf,
+    ctrl_c_rx: tokio::sync::broadcast::Receiver<Interruption>,
+    pb: ProgressBar,
+    /// `true` if another job has already failed with `JudgeOptions::fail_fast`.
+    cancelled: bool,
+}
+
+#[derive(Clone, Debug)]
+enum Interruption {
+    CtrlC(String),
+    FailFast,
+}
+
+impl Job {
+    fn temp_path(&self, name: &str) -> PathBuf {
+        self.tempdir_path.join(format!("{}-{}", self.index, name))
+    }
+}
+
+fn run_jobs<C, T, F, O>(
+    draw_target: ProgressDrawTarget,
+    ctrl_c: fn() -> C,
+    options: JudgeOptions,
+    targets: Vec<(Option<String>, T)>,
+    job: F,
+) -> anyhow::Result<JudgeOutcome>
+where
+    C: 'static + Future<Output = tokio::io::Result<()>> + Send,
+    T: 'static + Send,
+    F: 'static + Fn(Job, T) -> O + Send + Sync,
+    O: 'static + Future<Output = anyhow::Result<(Verdict, Option<Timings>)>> + Send,
+{
+    let job = Arc::new(job);
+    let num_test_cases = targets.len();
+
+    let quoted_name_width = targets
+        .iter()
+        .flat_map(|(name, _)| name.as_ref())
+        .map(|s| format!("{:?}", s).width())
+        .max()
+        .unwrap_or(0);
+
+    let tempdir = tempfile::Builder::new()
+        .prefix("snowchains-core-juding-")
+        .tempdir()?;
+    let tempdir_path = tempdir.path().to_owned();
+
+    let mp = MultiProgress::with_draw_target(draw_target);
+
+    let targets = targets
+        .into_iter()
+        .enumerate()
+        .map(|(i, (name, target))| {
+            let pb = mp.add(ProgressBar::new_spinner());
+
+            pb.set_style(progress_style("{prefix}{spinner} {msg:bold}"));
+
+            pb.set_prefix(&format!(
+                "{}/{} ({} ",
+                align_right(&(i + 1).to_string(), num_test_cases.to_string().len()),
+                num_test_cases,
+                align_left(
+                    &format!("{:?})", name.as_deref().unwrap_or("")),
+                    quoted_name_width + 1,
+                ),
+            ));
+
+            pb.set_message("Judging...");
+            pb.enable_steady_tick(50);
+
+            (target, pb)
+        })
+        .collect::<Vec<_>>();
+
+    let rt = tokio::runtime::Builder::new_multi_thread()
+        .enable_io()
+        .enable_time()
+        .build()?;
+
+    let outcome = rt.spawn(async move {
+        let num_targets = targets.len();
+
+        let (ctrl_c_tx, ctrl_c_rx) = tokio::sync::broadcast::channel(cmp::max(2, num_targets));
+        let fail_fast_tx = ctrl_c_tx.clone();
+        let failed = Arc::new(AtomicBool::new(false));
+
+        let mut ctrl_c_rxs = iter::once(ctrl_c_rx)
+            .chain(iter::repeat_with(|| ctrl_c_tx.subscribe()))
+            .take(num_targets)
+            .collect::<Vec<_>>();
+
+        tokio::task::spawn(async move {
+            let err_msg = match ctrl_c().await {
+                Ok(()) => "Recieved Ctrl-c".to_owned(),
+                Err(err) => err.to_string(),
+            };
+            let _ = ctrl_c_tx.send(Interruption::CtrlC(err_msg));
+        });
+
+        let jobs = options.jobs.map_or_else(num_cpus::get, NonZeroUsize::get);
+
+        let (job_start_tx, mut job_start_rx) = tokio::sync::mpsc::channel(jobs);
+        for _ in 0..jobs {
+            job_start_tx.send(()).await?;
+        }
+
+        let mut results = vec![];
+
+        for (i, (target, pb)) in targets.into_iter().enumerate() {
+            let job = job.clone();
+            let tempdir_path = tempdir_path.clone();
+
+            job_start_rx.recv().await;
+
+            let job_start_tx = job_start_tx.clone();
+            let ctrl_c_rx = ctrl_c_rxs.pop().expect("should have enough length");
+            let pb_clone = pb.clone();
+            let fail_fast_tx = fail_fast_tx.clone();
+            let failed = failed.clone();
+
+            results.push(tokio::task::spawn(async move {
+                let result = tokio::task::spawn(job(
+                    Job {
+                        index: i,
+                        tempdir_path,
+                        ctrl_c_rx,
+                        pb: pb_clone,
+                        cancelled: failed.load(atomic::Ordering::SeqCst),
+                    },
+                    target,
+                ))
                 .await
                 .unwrap();
 
+                if let Ok((verdict, _)) = &result {
+                    let failure = !matches!(
+                        verdict,
+                        Verdict::Accepted { .. } | Verdict::Cancelled { .. }
+                    );
+                    if options.fail_fast && failure && !failed.swap(true, atomic::Ordering::SeqCst)
+                    {
+                        let _ = fail_fast_tx.send(Interruption::FailFast);
+                    }
+                }
+
                 match &result {
-                    Ok(verdict) => {
+                    Ok((verdict, _)) => {
                         tokio::task::block_in_place(|| {
                             pb.set_style(progress_style(&format!(
                                 "{{prefix}}{{msg:{}}}",
@@ -642,19 +2164,21 @@ pub fn judge<C: 'static + Future<Output = tokio::io::Result<()>> + Send>(
                 }
 
                 job_start_tx.send(()).await?;
-                let verdict = result?;
-                Ok::<_, anyhow::Error>((i, verdict))
+                let (verdict, timings) = result?;
+                Ok::<_, anyhow::Error>((i, verdict, timings))
             }));
         }
 
         let mut verdicts = vec![None; num_targets];
+        let mut timings = vec![None; num_targets];
         for result in results {
-            let (i, element) = result.await??;
-            verdicts[i] = Some(element);
+            let (i, verdict, element_timings) = result.await??;
+            verdicts[i] = Some(verdict);
+            timings[i] = element_timings;
         }
         let verdicts = verdicts.into_iter().map(Option::unwrap).collect();
 
-        Ok::<_, anyhow::Error>(JudgeOutcome { verdicts })
+        Ok::<_, anyhow::Error>(JudgeOutcome { verdicts, timings })
     });
 
     mp.join()?;
@@ -663,10 +2187,6 @@ pub fn judge<C: 'static + Future<Output = tokio::io::Result<()>> + Send>(
     tempdir.close()?;
     return Ok(outcome);
 
-    fn progress_style(template: impl AsRef<str>) -> ProgressStyle {
-        ProgressStyle::default_spinner().template(template.as_ref())
-    }
-
     fn align_left(s: &str, n: usize) -> String {
         let spaces = n.saturating_sub(s.width());
         s.chars().chain(itertools::repeat_n(' ', spaces)).collect()
@@ -678,6 +2198,97 @@ pub fn judge<C: 'static + Future<Output = tokio::io::Result<()>> + Send>(
     }
 }
 
+fn progress_style(template: impl AsRef<str>) -> ProgressStyle {
+    ProgressStyle::default_spinner().template(template.as_ref())
+}
+
+fn find_bash(cwd: &Path) -> anyhow::Result<PathBuf> {
+    static GIT_BASH: &str = r"C:\Program Files\Git\bin\bash.exe";
+
+    let bash_exe = if cfg!(windows) && Path::new(GIT_BASH).exists() {
+        GIT_BASH
+    } else {
+        "bash"
+    };
+    which::which_in(bash_exe, env::var_os("PATH"), cwd)
+        .map_err(|_| anyhow!("`{}` not found", bash_exe))
+}
+
+fn compile_testlib_checkers(
+    cwd: &Path,
+    bash_exe: &Path,
+    test_cases: &[BatchTestCase],
+) -> anyhow::Result<()> {
+    let checkers = test_cases
+        .iter()
+        .flat_map(|test_case| match &test_case.output {
+            ExpectedOutput::Testlib {
+                checker,
+                compile: Some(compile),
+                ..
+            } => Some((checker, compile)),
+            _ => None,
+        })
+        .collect::<BTreeSet<_>>();
+
+    for (checker, compile) in checkers {
+        let source = testlib_checker_path(cwd, checker, false);
+        let output = testlib_checker_path(cwd, checker, true);
+
+        let modified = |path: &Path| {
+            std::fs::metadata(path)
+                .and_then(|m| m.modified())
+                .with_context(|| format!("Could not get the metadata of `{}`", path.display()))
+        };
+
+        if output.exists() && modified(&output)? > modified(&source)? {
+            continue;
+        }
+
+        if let Some(parent) = output.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Could not create `{}`", parent.display()))?;
+        }
+
+        let Output { status, stderr, .. } = std::process::Command::new(bash_exe)
+            .args([OsStr::new("-c"), OsStr::new(compile)])
+            .env("SOURCE", &source)
+            .env("OUTPUT", &output)
+            .current_dir(cwd)
+            .stdin(Stdio::null())
+            .output()?;
+
+        if !status.success() {
+            bail!(
+                "Could not compile `{}` ({}):\n{}",
+                checker,
+                status,
+                String::from_utf8_lossy(&stderr),
+            );
+        }
+    }
+    Ok(())
+}
+
+/// The compiled checker is put in `.snowchains/testlib/` so that it never overwrites the source, even if `checker`
+/// has no extension.
+fn testlib_checker_path(cwd: &Path, checker: &str, compiled: bool) -> PathBuf {
+    let checker = Path::new(checker);
+    let checker = checker.strip_prefix(".").unwrap_or(checker);
+    if compiled {
+        let relative = checker
+            .components()
+            .filter(|c| matches!(c, Component::Normal(_)))
+            .collect::<PathBuf>();
+        cwd.join(".snowchains")
+            .join("testlib")
+            .join(relative)
+            .with_extension(if cfg!(windows) { "exe" } else { "" })
+    } else {
+        cwd.join(checker)
+    }
+}
+
 async fn check(
     expected: &ExpectedOutput,
     actual: &str,
@@ -731,6 +2342,53 @@ async fn check(
                 Err((stdout, stderr, None))
             })
         }
+        ExpectedOutput::Testlib {
+            text,
+            checker,
+            compile,
+        } => {
+            tokio::fs::write(expected_stdout_path, text.as_deref().unwrap_or("")).await?;
+
+            let Output {
+                status,
+                stdout,
+                stderr,
+            } = tokio::process::Command::new(testlib_checker_path(cwd, checker, compile.is_some()))
+                .args([stdin_path, actual_stdout_path, expected_stdout_path])
+                .current_dir(cwd)
+                .stdin(Stdio::null())
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped())
+                .kill_on_drop(true)
+                .output()
+                .await
+                .with_context(|| format!("Could not execute `{}`", checker))?;
+
+            let (stdout, stderr) = (utf8(stdout)?, utf8(stderr)?);
+
+            // https://github.com/MikeMirzayanov/testlib/blob/master/testlib.h
+            Ok(match status.code() {
+                Some(0) => Ok(()),
+                Some(2) | Some(4) | Some(8) => {
+                    Err((stdout, stderr, Some(WrongAnswerNote::PresentationError)))
+                }
+                Some(3) => bail!("`{}` failed: {}", checker, stderr.trim_end()),
+                Some(7) => {
+                    let points = stderr
+                        .strip_prefix("points ")
+                        .and_then(|s| s.split_whitespace().next())
+                        .and_then(|s| s.parse().ok())
+                        .map(WrongAnswerNote::Points);
+                    Err((stdout, stderr, points))
+                }
+                // `_pc(n)`
+                Some(code) if code >= 16 => {
+                    let points = WrongAnswerNote::Points(f64::from(code - 16));
+                    Err((stdout, stderr, Some(points)))
+                }
+                _ => Err((stdout, stderr, None)),
+            })
+        }
     }
 }
 
@@ -739,3 +2397,566 @@ fn utf8(bytes: Vec<u8>) -> anyhow::Result<Arc<str>> {
         .map(Into::into)
         .map_err(|_| anyhow!("the output was not a valid UTF-8 string"))
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        judge::{
+            CommandExpression, JudgeOptions, JudgeOutcome, Sandbox, Statistic, Timings, Verdict,
+            WrongAnswerNote,
+        },
+        testsuite::{
+            BatchTestCase, CheckerShell, DeterministicExpectedOutput, ExpectedOutput,
+            InteractiveTestCase, Interactor,
+        },
+    };
+    use futures_util::future;
+    use indicatif::ProgressDrawTarget;
+    use maplit::btreemap;
+    use pretty_assertions::assert_eq;
+    use std::{fs, io, num::NonZeroUsize, process::ExitStatus, time::Duration};
+
+    #[test]
+    fn timings() {
+        let ms = Duration::from_millis;
+        let timings = |elapsed_times: &[_], statistic, timelimit, warning_ratio| {
+            let Timings {
+                runs,
+                min,
+                median,
+                max,
+                near_timelimit,
+            } = Timings::new(elapsed_times, statistic, timelimit, warning_ratio);
+            (runs, min, median, max, near_timelimit)
+        };
+
+        let even = [ms(30), ms(10), ms(40), ms(20)];
+        let odd = [ms(30), ms(10), ms(20)];
+
+        assert_eq!(
+            (4, ms(10), ms(25), ms(40), true),
+            timings(&even, Statistic::Median, Some(ms(100)), Some(0.2)),
+        );
+        assert_eq!(
+            (3, ms(10), ms(20), ms(30), false),
+            timings(&odd, Statistic::Median, Some(ms(100)), Some(0.2)),
+        );
+        assert!(!timings(&odd, Statistic::Min, Some(ms(100)), Some(0.2)).4);
+        assert!(timings(&odd, Statistic::Max, Some(ms(100)), Some(0.2)).4);
+        assert!(!timings(&odd, Statistic::Max, None, Some(0.2)).4);
+        assert!(!timings(&odd, Statistic::Max, Some(ms(100)), None).4);
+    }
+
+    #[test]
+    fn summarize_runs() {
+        let ms = Duration::from_millis;
+        let expected = || ExpectedOutput::Deterministic(DeterministicExpectedOutput::Pass);
+        let accepted = |elapsed| {
+            let verdict = Verdict::Accepted {
+                test_case_name: None,
+                elapsed,
+                memory: None,
+                stdin: "".into(),
+                stdout: "".into(),
+                stderr: "".into(),
+                expected: expected(),
+            };
+            (verdict, elapsed)
+        };
+        let timelimit_exceeded = |elapsed| {
+            let verdict = Verdict::TimelimitExceeded {
+                test_case_name: None,
+                timelimit: ms(100),
+                stdin: "".into(),
+                expected: expected(),
+            };
+            (verdict, elapsed)
+        };
+        let options = |statistic| JudgeOptions {
+            statistic,
+            warning_ratio: Some(0.5),
+            ..JudgeOptions::default()
+        };
+        let runs = || {
+            vec![
+                accepted(ms(10)),
+                timelimit_exceeded(ms(150)),
+                accepted(ms(60)),
+            ]
+        };
+
+        let (verdict, timings) =
+            super::summarize_runs(runs(), Some(ms(100)), options(Statistic::Median));
+        assert!(matches!(verdict, Verdict::Accepted { elapsed, .. } if elapsed == ms(60)));
+        assert!(matches!(
+            timings,
+            Some(Timings {
+                runs: 3,
+                near_timelimit: true,
+                ..
+            })
+        ));
+
+        let (verdict, timings) =
+            super::summarize_runs(runs(), Some(ms(100)), options(Statistic::Min));
+        assert!(matches!(verdict, Verdict::Accepted { elapsed, .. } if elapsed == ms(10)));
+        assert!(matches!(
+            timings,
+            Some(Timings {
+                near_timelimit: false,
+                ..
+            })
+        ));
+
+        let (verdict, _) = super::summarize_runs(runs(), Some(ms(100)), options(Statistic::Max));
+        assert!(matches!(verdict, Verdict::TimelimitExceeded { .. }));
+
+        let (verdict, timings) = super::summarize_runs(
+            vec![accepted(ms(10))],
+            Some(ms(100)),
+            options(Statistic::Median),
+        );
+        assert!(matches!(verdict, Verdict::Accepted { .. }));
+        assert!(timings.is_none());
+    }
+
+    #[test]
+    fn to_json() {
+        let outcome = serde_json::from_str::<serde_json::Value>(&outcome().to_json()).unwrap();
+
+        assert_eq!(
+            serde_json::json!({
+                "verdicts": [
+                    {
+                        "name": "ac",
+                        "verdict": "Accepted",
+                        "elapsed_ms": 10,
+                        "memory_bytes": 1048576,
+                        "exit_code": 0,
+                        "stdin": "1 2\n",
+                        "stdout": "3\n",
+                        "stderr": "",
+                        "expected": "3\n",
+                        "checker_stdout": null,
+                        "checker_stderr": null,
+                        "note": null,
+                    },
+                    {
+                        "name": "wa",
+                        "verdict": "WrongAnswer",
+                        "elapsed_ms": 20,
+                        "memory_bytes": null,
+                        "exit_code": 0,
+                        "stdin": "1 2\n",
+                        "stdout": "<\"&'>\u{1}\n",
+                        "stderr": "",
+                        "expected": "3\n",
+                        "checker_stdout": "",
+                        "checker_stderr": "",
+                        "note": null,
+                    },
+                    {
+                        "name": "tle",
+                        "verdict": "TimelimitExceeded",
+                        "elapsed_ms": null,
+                        "memory_bytes": null,
+                        "exit_code": null,
+                        "stdin": "1 2\n",
+                        "stdout": null,
+                        "stderr": null,
+                        "expected": "3\n",
+                        "checker_stdout": null,
+                        "checker_stderr": null,
+                        "note": null,
+                    },
+                    {
+                        "name": null,
+                        "verdict": "RuntimeError",
+                        "elapsed_ms": 30,
+                        "memory_bytes": null,
+                        "exit_code": 101,
+                        "stdin": "1 2\n",
+                        "stdout": "",
+                        "stderr": "panicked\n",
+                        "expected": "3\n",
+                        "checker_stdout": null,
+                        "checker_stderr": null,
+                        "note": null,
+                    },
+                ],
+                "timings": [null, null, null, null],
+            }),
+            outcome,
+        );
+    }
+
+    // The message of `RuntimeError` depends on the `Display` of `ExitStatus`.
+    #[cfg(unix)]
+    #[test]
+    fn to_junit_xml() {
+        assert_eq!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites tests="4" failures="2" errors="1" time="0.060">
+  <testsuite name="a&lt;b&gt;" tests="4" failures="2" errors="1" skipped="0" time="0.060">
+    <testcase name="ac" classname="a&lt;b&gt;" time="0.010">
+      <system-out>3
+</system-out>
+      <system-err></system-err>
+    </testcase>
+    <testcase name="wa" classname="a&lt;b&gt;" time="0.020">
+      <failure type="WrongAnswer" message="Wrong Answer (20 ms)">expected:
+3
+</failure>
+      <system-out>&lt;&quot;&amp;&apos;&gt;
+</system-out>
+      <system-err></system-err>
+    </testcase>
+    <testcase name="tle" classname="a&lt;b&gt;" time="0.000">
+      <failure type="TimelimitExceeded" message="Timelimit Exceeded (2000 ms)">expected:
+3
+</failure>
+      <system-out></system-out>
+      <system-err></system-err>
+    </testcase>
+    <testcase name="4" classname="a&lt;b&gt;" time="0.030">
+      <error type="RuntimeError" message="Runtime Error (30 ms, exit status: 101)">expected:
+3
+</error>
+      <system-out></system-out>
+      <system-err>panicked
+</system-err>
+    </testcase>
+  </testsuite>
+</testsuites>
+"#,
+            outcome().to_junit_xml("a<b>"),
+        );
+    }
+
+    fn outcome() -> JudgeOutcome {
+        let expected = || {
+            ExpectedOutput::Deterministic(DeterministicExpectedOutput::Exact { text: "3\n".into() })
+        };
+
+        JudgeOutcome {
+            verdicts: vec![
+                Verdict::Accepted {
+                    test_case_name: Some("ac".to_owned()),
+                    elapsed: Duration::from_millis(10),
+                    memory: Some(1024 * 1024),
+                    stdin: "1 2\n".into(),
+                    stdout: "3\n".into(),
+                    stderr: "".into(),
+                    expected: expected(),
+                },
+                Verdict::WrongAnswer {
+                    test_case_name: Some("wa".to_owned()),
+                    elapsed: Duration::from_millis(20),
+                    memory: None,
+                    stdin: "1 2\n".into(),
+                    stdout: "<\"&'>\u{1}\n".into(),
+                    stderr: "".into(),
+                    checker_stdout: "".into(),
+                    checker_stderr: "".into(),
+                    expected: expected(),
+                    note: None,
+                },
+                Verdict::TimelimitExceeded {
+                    test_case_name: Some("tle".to_owned()),
+                    timelimit: Duration::from_secs(2),
+                    stdin: "1 2\n".into(),
+                    expected: expected(),
+                },
+                Verdict::RuntimeError {
+                    test_case_name: None,
+                    elapsed: Duration::from_millis(30),
+                    memory: None,
+                    stdin: "1 2\n".into(),
+                    stdout: "".into(),
+                    stderr: "panicked\n".into(),
+                    expected: expected(),
+                    status: exit_status(101),
+                },
+            ],
+            timings: vec![None; 4],
+        }
+    }
+
+    fn exit_status(code: i32) -> ExitStatus {
+        #[cfg(unix)]
+        {
+            use std::os::unix::process::ExitStatusExt as _;
+            ExitStatus::from_raw(code << 8)
+        }
+
+        #[cfg(windows)]
+        {
+            use std::os::windows::process::ExitStatusExt as _;
+            ExitStatus::from_raw(code as _)
+        }
+    }
+
+    #[test]
+    fn interactor_inherits_env() -> anyhow::Result<()> {
+        let tempdir = tempfile::Builder::new()
+            .prefix("snowchains-core-judge-test-")
+            .tempdir()?;
+
+        let cmd = CommandExpression::bash(
+            r#"read x && echo "$x""#,
+            tempdir.path().to_owned(),
+            btreemap!("GREETING".into() => "hello".into()),
+        )?;
+
+        let test_case = InteractiveTestCase {
+            name: Some("env".to_owned()),
+            timelimit: Some(Duration::from_secs(10)),
+            input: None,
+            interactor: Interactor {
+                cmd: r#"echo hi && read y && [ "$y" = hi ] && [ "$GREETING" = hello ]"#.to_owned(),
+                shell: CheckerShell::Bash,
+            },
+        };
+
+        let outcome = super::judge_interactive(
+            ProgressDrawTarget::hidden(),
+            future::pending::<io::Result<()>>,
+            &cmd,
+            &[test_case],
+            JudgeOptions::default(),
+        )?;
+
+        assert!(matches!(outcome.verdicts[..], [Verdict::Accepted { .. }]));
+        Ok(())
+    }
+
+    #[test]
+    fn interactive_repeat() -> anyhow::Result<()> {
+        let tempdir = tempfile::Builder::new()
+            .prefix("snowchains-core-judge-test-")
+            .tempdir()?;
+
+        let cmd = CommandExpression::bash(
+            r#"read x && echo "$x""#,
+            tempdir.path().to_owned(),
+            btreemap!(),
+        )?;
+
+        let test_case = InteractiveTestCase {
+            name: Some("repeat".to_owned()),
+            timelimit: Some(Duration::from_secs(10)),
+            input: None,
+            interactor: Interactor {
+                cmd: r#"echo hi && read y && [ "$y" = hi ]"#.to_owned(),
+                shell: CheckerShell::Bash,
+            },
+        };
+
+        let outcome = super::judge_interactive(
+            ProgressDrawTarget::hidden(),
+            future::pending::<io::Result<()>>,
+            &cmd,
+            &[test_case],
+            JudgeOptions {
+                repeat: NonZeroUsize::new(3),
+                ..JudgeOptions::default()
+            },
+        )?;
+
+        assert!(matches!(outcome.verdicts[..], [Verdict::Accepted { .. }]));
+        assert!(matches!(
+            outcome.timings[..],
+            [Some(Timings { runs: 3, .. })]
+        ));
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn testlib_exit_codes() -> anyhow::Result<()> {
+        let tempdir = tempfile::Builder::new()
+            .prefix("snowchains-core-judge-test-")
+            .tempdir()?;
+
+        // Exits with the code printed by the solution, like a testlib checker that is given a verdict.
+        let source = r#"#!/usr/bin/env bash
+code=$(cat "$2")
+if [ "$code" = 7 ]; then echo 'points 0.5' >&2; fi
+exit "$code"
+"#;
+        fs::write(tempdir.path().join("checker"), source)?;
+
+        let cmd = CommandExpression::bash("cat", tempdir.path().to_owned(), btreemap!())?;
+
+        let judge = |codes: &[&str]| {
+            let test_cases = codes
+                .iter()
+                .map(|&code| BatchTestCase {
+                    name: Some(code.to_owned()),
+                    timelimit: Some(Duration::from_secs(10)),
+                    memorylimit: None,
+                    input: code.into(),
+                    output: ExpectedOutput::Testlib {
+                        text: None,
+                        checker: "./checker".to_owned(),
+                        compile: Some(r#"cp "$SOURCE" "$OUTPUT" && chmod +x "$OUTPUT""#.to_owned()),
+                    },
+                })
+                .collect::<Vec<_>>();
+
+            super::judge(
+                ProgressDrawTarget::hidden(),
+                future::pending::<io::Result<()>>,
+                &cmd,
+                &test_cases,
+                JudgeOptions::default(),
+            )
+        };
+
+        let outcome = judge(&["0", "1", "2", "4", "8", "7", "19"])?;
+
+        assert!(matches!(
+            outcome.verdicts[..],
+            [
+                Verdict::Accepted { .. },
+                Verdict::WrongAnswer { note: None, .. },
+                Verdict::WrongAnswer { note: Some(WrongAnswerNote::PresentationError), .. },
+                Verdict::WrongAnswer { note: Some(WrongAnswerNote::PresentationError), .. },
+                Verdict::WrongAnswer { note: Some(WrongAnswerNote::PresentationError), .. },
+                Verdict::WrongAnswer { note: Some(WrongAnswerNote::Points(p1)), .. },
+                Verdict::WrongAnswer { note: Some(WrongAnswerNote::Points(p2)), .. },
+            ] if p1 == 0.5 && p2 == 3.0
+        ));
+
+        assert!(judge(&["3"]).is_err());
+
+        assert_eq!(source, fs::read_to_string(tempdir.path().join("checker"))?);
+        assert!(tempdir
+            .path()
+            .join(".snowchains")
+            .join("testlib")
+            .join("checker")
+            .exists());
+        Ok(())
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    #[ignore = "requires Landlock, which most CI runners do not have"]
+    fn sandbox_protects_files() -> anyhow::Result<()> {
+        let (sandbox, reasons) = Sandbox {
+            protect_files: true,
+            ..Sandbox::default()
+        }
+        .degrade();
+        assert!(reasons.is_empty(), "{:?}", reasons);
+
+        let tempdir = tempfile::Builder::new()
+            .prefix("snowchains-core-judge-test-")
+            .tempdir()?;
+        fs::write(tempdir.path().join("victim"), "")?;
+
+        let mut cmd = CommandExpression::bash(
+            r#"echo hi > "$TMPDIR/a" && cat "$TMPDIR/a" && ! rm victim 2> /dev/null && ! (echo > new) 2> /dev/null"#,
+            tempdir.path().to_owned(),
+            btreemap!(),
+        )?;
+        cmd.sandbox = Some(sandbox);
+
+        let test_case = BatchTestCase {
+            name: Some("protect-files".to_owned()),
+            timelimit: Some(Duration::from_secs(10)),
+            memorylimit: None,
+            input: "".into(),
+            output: ExpectedOutput::Deterministic(DeterministicExpectedOutput::Exact {
+                text: "hi\n".into(),
+            }),
+        };
+
+        let outcome = super::judge(
+            ProgressDrawTarget::hidden(),
+            future::pending::<io::Result<()>>,
+            &cmd,
+            &[test_case],
+            JudgeOptions::default(),
+        )?;
+
+        assert!(matches!(outcome.verdicts[..], [Verdict::Accepted { .. }]));
+        assert!(tempdir.path().join("victim").exists());
+        assert!(!tempdir.path().join("new").exists());
+        Ok(())
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn seccomp_filter() -> io::Result<()> {
+        use std::{os::unix::process::CommandExt as _, process::Command, ptr};
+
+        let mut cmd = Command::new("sh");
+        cmd.args(&["-c", "echo hi"]);
+        unsafe {
+            cmd.pre_exec(|| {
+                super::seccomp::filter()?;
+
+                // Fails with `ESRCH` if not filtered.
+                if libc::ptrace(
+                    libc::PTRACE_ATTACH,
+                    0,
+                    ptr::null_mut::<libc::c_void>(),
+                    ptr::null_mut::<libc::c_void>(),
+                ) == -1
+                    && io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+                {
+                    Ok(())
+                } else {
+                    Err(io::Error::last_os_error())
+                }
+            });
+        }
+
+        let output = cmd.output()?;
+        assert!(output.status.success());
+        assert_eq!(b"hi\n", &*output.stdout);
+        Ok(())
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn memory_limit_exceeded() -> anyhow::Result<()> {
+        let tempdir = tempfile::Builder::new()
+            .prefix("snowchains-core-judge-test-")
+            .tempdir()?;
+
+        let cmd = CommandExpression::bash(
+            "x=$(head -c 67108864 /dev/zero | tr '\\0' a)",
+            tempdir.path().to_owned(),
+            btreemap!(),
+        )?;
+
+        let test_case = BatchTestCase {
+            name: Some("mle".to_owned()),
+            timelimit: Some(Duration::from_secs(10)),
+            memorylimit: Some(16 * 1024 * 1024),
+            input: "".into(),
+            output: ExpectedOutput::Checker {
+                text: None,
+                cmd: "true".to_owned(),
+                shell: CheckerShell::Bash,
+            },
+        };
+
+        let outcome = super::judge(
+            ProgressDrawTarget::hidden(),
+            future::pending::<io::Result<()>>,
+            &cmd,
+            &[test_case],
+            JudgeOptions::default(),
+        )?;
+
+        assert!(matches!(
+            outcome.verdicts[..],
+            [Verdict::MemoryLimitExceeded { memory, memorylimit, .. }] if memory > memorylimit
+        ));
+        Ok(())
+    }
+}
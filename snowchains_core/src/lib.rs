@@ -38,6 +38,7 @@ macro_rules! _color_spec_inner {
     };
 }
 
+mod diff;
 pub mod judge;
 pub mod testsuite;
 pub mod web;
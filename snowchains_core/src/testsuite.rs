@@ -33,6 +33,9 @@ impl TestSuite {
 
                 yaml += &key_value("type", "Batch").ok()?;
                 yaml += &key_value("timelimit", Serde::from(suite.timelimit)).ok()?;
+                if let Some(memorylimit) = suite.memorylimit {
+                    yaml += &key_value("memorylimit", serde_fn::format_memory(memorylimit)).ok()?;
+                }
                 yaml += &key_value("match", &suite.r#match).ok()?;
 
                 yaml += if suite.cases.is_empty() {
@@ -58,6 +61,11 @@ impl TestSuite {
                         part += &key_value("timelimit", Serde::from(timelimit)).ok()?;
                     }
 
+                    if let Some(memorylimit) = case.memorylimit {
+                        part +=
+                            &key_value("memorylimit", serde_fn::format_memory(memorylimit)).ok()?;
+                    }
+
                     if let Some(r#match) = &case.r#match {
                         part += &key_value("match", r#match).ok()?;
                     }
@@ -153,6 +161,13 @@ impl TestSuite {
 pub struct BatchTestSuite {
     #[serde(default, with = "humantime_serde")]
     pub timelimit: Option<Duration>,
+    /// Memory limit in bytes.
+    #[serde(
+        default,
+        with = "serde_fn::option_memory",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub memorylimit: Option<u64>,
     pub r#match: Match,
     #[serde(default)]
     pub cases: Vec<PartialBatchTestCase>,
@@ -183,7 +198,7 @@ impl BatchTestSuite {
                     _ => true,
                 },
             )
-            .map(|case| BatchTestCase::new(case, self.timelimit, &self.r#match))
+            .map(|case| self.test_case(case))
             .collect();
 
         if let Some(names) = names {
@@ -197,6 +212,11 @@ impl BatchTestSuite {
 
         Ok(cases)
     }
+
+    /// Completes `case` with `timelimit`, `memorylimit` and `match` of this suite.
+    pub fn test_case(&self, case: PartialBatchTestCase) -> BatchTestCase {
+        BatchTestCase::new(case, self.timelimit, self.memorylimit, &self.r#match)
+    }
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
@@ -208,6 +228,13 @@ pub struct PartialBatchTestCase {
     pub out: Option<Arc<str>>,
     #[serde(default, with = "humantime_serde")]
     pub timelimit: Option<Duration>,
+    /// Memory limit in bytes.
+    #[serde(
+        default,
+        with = "serde_fn::option_memory",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub memorylimit: Option<u64>,
     pub r#match: Option<Match>,
 }
 
@@ -234,7 +261,8 @@ pub enum Additional {
 }
 
 impl Additional {
-    fn load_test_cases(
+    /// Loads the test cases. `prepare_system_test_cases` is called for `SystemTestCases`.
+    pub fn load_test_cases(
         &self,
         parent_dir: &Path,
         mut prepare_system_test_cases: impl FnMut(
@@ -311,6 +339,7 @@ impl Additional {
                             r#in,
                             out,
                             timelimit: *timelimit,
+                            memorylimit: None,
                             r#match: r#match.clone(),
                         })
                     })
@@ -334,6 +363,15 @@ pub enum Match {
         cmd: String,
         shell: CheckerShell,
     },
+    /// A [testlib](https://github.com/MikeMirzayanov/testlib) checker, run as `checker <input> <output> <answer>`.
+    Testlib {
+        /// Path to the checker, relative to the working directory.
+        checker: String,
+        /// Bash command to compile `checker` with `$SOURCE` and `$OUTPUT`. If this is set, the
+        /// compiled executable (`.snowchains/testlib/<checker without the extension>`) is run instead.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        compile: Option<String>,
+    },
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, PartialOrd, Eq, Ord)]
@@ -345,21 +383,104 @@ pub enum CheckerShell {
 pub struct InteractiveTestSuite {
     #[serde(default, with = "humantime_serde")]
     pub timelimit: Option<Duration>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub interactor: Option<Interactor>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cases: Vec<PartialInteractiveTestCase>,
+}
+
+impl InteractiveTestSuite {
+    pub fn load_test_cases<S: Borrow<str> + Eq + Hash>(
+        &self,
+        mut names: Option<HashSet<S>>,
+    ) -> anyhow::Result<Vec<InteractiveTestCase>> {
+        let interactor = self
+            .interactor
+            .clone()
+            .with_context(|| "`interactor` is not set for this interactive problem")?;
+
+        let cases = if self.cases.is_empty() {
+            vec![InteractiveTestCase {
+                name: None,
+                timelimit: self.timelimit,
+                input: None,
+                interactor,
+            }]
+        } else {
+            self.cases
+                .iter()
+                .filter(|PartialInteractiveTestCase { name, .. }| {
+                    match (names.as_mut(), name.as_ref()) {
+                        (Some(names), Some(name)) => names.remove(name),
+                        _ => true,
+                    }
+                })
+                .map(|case| InteractiveTestCase {
+                    name: case.name.clone(),
+                    timelimit: case.timelimit.or(self.timelimit),
+                    input: Some(case.r#in.clone()),
+                    interactor: interactor.clone(),
+                })
+                .collect()
+        };
+
+        if let Some(names) = names {
+            if !names.is_empty() {
+                bail!(
+                    "No such test cases: {:?}",
+                    names.iter().map(Borrow::borrow).collect::<BTreeSet<_>>(),
+                );
+            }
+        }
+
+        Ok(cases)
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct Interactor {
+    pub cmd: String,
+    pub shell: CheckerShell,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct PartialInteractiveTestCase {
+    pub name: Option<String>,
+    #[serde(with = "serde_fn::arc_str")]
+    pub r#in: Arc<str>,
+    #[serde(default, with = "humantime_serde")]
+    pub timelimit: Option<Duration>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct InteractiveTestCase {
+    pub name: Option<String>,
+    pub timelimit: Option<Duration>,
+    pub input: Option<Arc<str>>,
+    pub interactor: Interactor,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct BatchTestCase {
     pub name: Option<String>,
     pub timelimit: Option<Duration>,
+    /// Memory limit in bytes.
+    pub memorylimit: Option<u64>,
     pub input: Arc<str>,
     pub output: ExpectedOutput,
 }
 
 impl BatchTestCase {
-    fn new(case: PartialBatchTestCase, timelimit: Option<Duration>, matching: &Match) -> Self {
+    fn new(
+        case: PartialBatchTestCase,
+        timelimit: Option<Duration>,
+        memorylimit: Option<u64>,
+        matching: &Match,
+    ) -> Self {
         BatchTestCase {
             name: case.name,
             timelimit: case.timelimit.or(timelimit),
+            memorylimit: case.memorylimit.or(memorylimit),
             input: case.r#in,
             output: ExpectedOutput::new(case.out, case.r#match.unwrap_or_else(|| matching.clone())),
         }
@@ -374,12 +495,22 @@ pub enum ExpectedOutput {
         cmd: String,
         shell: CheckerShell,
     },
+    Testlib {
+        text: Option<Arc<str>>,
+        checker: String,
+        compile: Option<String>,
+    },
 }
 
 impl ExpectedOutput {
     fn new(text: Option<Arc<str>>, matching: Match) -> Self {
         match (text, matching) {
             (text, Match::Checker { cmd, shell }) => Self::Checker { text, cmd, shell },
+            (text, Match::Testlib { checker, compile }) => Self::Testlib {
+                text,
+                checker,
+                compile,
+            },
             (Some(text), Match::Exact) => {
                 Self::Deterministic(DeterministicExpectedOutput::Exact { text })
             }
@@ -404,6 +535,14 @@ impl ExpectedOutput {
         }
     }
 
+    pub(crate) fn interactor(cmd: String, shell: CheckerShell) -> Self {
+        Self::Checker {
+            text: None,
+            cmd,
+            shell,
+        }
+    }
+
     pub(crate) fn is_float(&self) -> bool {
         matches!(
             self,
@@ -414,13 +553,13 @@ impl ExpectedOutput {
     pub(crate) fn expected_stdout(&self) -> Option<&str> {
         match self {
             Self::Deterministic(expected) => expected.expected_stdout(),
-            Self::Checker { .. } => None,
+            Self::Checker { .. } | Self::Testlib { .. } => None,
         }
     }
 
     pub(crate) fn example(&self) -> Option<&str> {
         match self {
-            Self::Checker { text, .. } => text.as_deref(),
+            Self::Checker { text, .. } | Self::Testlib { text, .. } => text.as_deref(),
             _ => None,
         }
     }
@@ -452,28 +591,14 @@ impl DeterministicExpectedOutput {
             Self::Exact { text } => &**text == actual,
             Self::SplitWhitespace { text } => text.split_whitespace().eq(actual.split_whitespace()),
             Self::Lines { text } => text.lines().eq(actual.lines()),
-            Self::Float {
-                text,
-                relative_error,
-                absolute_error,
-            } => {
+            Self::Float { text, .. } => {
                 let (text, actual) = (text.lines(), actual.lines());
-                let relative_error = relative_error.map(PositiveFinite::get).unwrap_or(0.0);
-                let absolute_error = absolute_error.map(PositiveFinite::get).unwrap_or(0.0);
 
                 text.zip_longest(actual).all(|zip| {
                     if let EitherOrBoth::Both(line1, line2) = zip {
                         let (words1, words2) = (line1.split_whitespace(), line2.split_whitespace());
                         words1.zip_longest(words2).all(|zip| match zip {
-                            EitherOrBoth::Both(s1, s2) => {
-                                match (s1.parse::<f64>(), s2.parse::<f64>()) {
-                                    (Ok(v1), Ok(v2)) => {
-                                        (v1 - v2).abs() <= absolute_error
-                                            || ((v1 - v2) / v2).abs() <= relative_error
-                                    }
-                                    _ => s1 == s2,
-                                }
-                            }
+                            EitherOrBoth::Both(s1, s2) => self.word_accepts(s1, s2),
                             EitherOrBoth::Left(_) | EitherOrBoth::Right(_) => false,
                         })
                     } else {
@@ -484,6 +609,36 @@ impl DeterministicExpectedOutput {
         }
     }
 
+    /// Compares two whitespace-separated words. Numbers are compared with the tolerance for `Float`.
+    pub(crate) fn word_accepts(&self, expected: &str, actual: &str) -> bool {
+        match (
+            self.float_tolerance(),
+            expected.parse::<f64>(),
+            actual.parse::<f64>(),
+        ) {
+            (Some((relative_error, absolute_error)), Ok(v1), Ok(v2)) => {
+                let (abs, rel) = float_errors(v1, v2);
+                abs <= absolute_error || rel <= relative_error
+            }
+            _ => expected == actual,
+        }
+    }
+
+    /// Returns `(relative_error, absolute_error)` for `Float`.
+    pub(crate) fn float_tolerance(&self) -> Option<(f64, f64)> {
+        match self {
+            Self::Float {
+                relative_error,
+                absolute_error,
+                ..
+            } => Some((
+                relative_error.map(PositiveFinite::get).unwrap_or(0.0),
+                absolute_error.map(PositiveFinite::get).unwrap_or(0.0),
+            )),
+            _ => None,
+        }
+    }
+
     pub(crate) fn expected_stdout(&self) -> Option<&str> {
         match self {
             Self::Pass => None,
@@ -495,6 +650,14 @@ impl DeterministicExpectedOutput {
     }
 }
 
+/// Returns `(absolute_error, relative_error)` of `actual` against `expected`.
+pub(crate) fn float_errors(expected: f64, actual: f64) -> (f64, f64) {
+    (
+        (expected - actual).abs(),
+        ((expected - actual) / actual).abs(),
+    )
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Serialize)]
 #[serde(transparent)]
 pub struct PositiveFinite<F>(F);
@@ -532,6 +695,62 @@ impl<'de> Deserialize<'de> for PositiveFinite<f64> {
 }
 
 mod serde_fn {
+    use anyhow::Context as _;
+
+    const UNITS: &[(&str, u64)] = &[
+        ("GiB", 1024 * 1024 * 1024),
+        ("MiB", 1024 * 1024),
+        ("KiB", 1024),
+        ("GB", 1000 * 1000 * 1000),
+        ("MB", 1000 * 1000),
+        ("KB", 1000),
+        ("B", 1),
+    ];
+
+    pub(super) fn format_memory(bytes: u64) -> String {
+        if bytes & (1024 * 1024 - 1) == 0 {
+            format!("{}MiB", bytes >> 20)
+        } else if bytes & (1024 - 1) == 0 {
+            format!("{}KiB", bytes >> 10)
+        } else {
+            format!("{}B", bytes)
+        }
+    }
+
+    pub(super) fn parse_memory(s: &str) -> anyhow::Result<u64> {
+        let s = s.trim();
+        let (unit, size) = UNITS
+            .iter()
+            .find(|(unit, _)| s.ends_with(unit))
+            .with_context(|| format!("expected one of {:?}: {:?}", UNITS, s))?;
+        let n = s[..s.len() - unit.len()].trim().parse::<u64>()?;
+        n.checked_mul(*size).with_context(|| "too large")
+    }
+
+    pub(super) mod option_memory {
+        use serde::{de::Error as _, Deserialize, Deserializer, Serializer};
+
+        pub(crate) fn serialize<S>(this: &Option<u64>, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            if let Some(bytes) = *this {
+                serializer.serialize_some(&super::format_memory(bytes))
+            } else {
+                serializer.serialize_none()
+            }
+        }
+
+        pub(crate) fn deserialize<'de, D>(deserializer: D) -> Result<Option<u64>, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            Option::<String>::deserialize(deserializer)?
+                .map(|s| super::parse_memory(&s).map_err(D::Error::custom))
+                .transpose()
+        }
+    }
+
     pub(super) mod arc_str {
         use serde::{Deserialize, Deserializer, Serializer};
         use std::sync::Arc;
@@ -581,12 +800,14 @@ mod serde_fn {
 #[cfg(test)]
 mod tests {
     use crate::testsuite::{
-        Additional, BatchTestSuite, DeterministicExpectedOutput, Match, PartialBatchTestCase,
-        PositiveFinite, TestSuite,
+        Additional, BatchTestCase, BatchTestSuite, CheckerShell, DeterministicExpectedOutput,
+        ExpectedOutput, InteractiveTestCase, InteractiveTestSuite, Interactor, Match,
+        PartialBatchTestCase, PositiveFinite, TestSuite,
     };
     use difference::assert_diff;
+    use maplit::hashset;
     use pretty_assertions::assert_eq;
-    use std::time::Duration;
+    use std::{collections::HashSet, path::Path, time::Duration};
 
     #[test]
     fn atcoder_abc162_a() {
@@ -594,6 +815,7 @@ mod tests {
             r#"---
 type: Batch
 timelimit: 2s
+memorylimit: 1024MiB
 match: Lines
 
 cases:
@@ -617,6 +839,7 @@ extend: []
 "#,
             &TestSuite::Batch(BatchTestSuite {
                 timelimit: Some(Duration::from_secs(2)),
+                memorylimit: Some(1024 * 1024 * 1024),
                 r#match: Match::Lines,
                 cases: vec![
                     PartialBatchTestCase {
@@ -624,6 +847,7 @@ extend: []
                         r#in: "117\n".into(),
                         out: Some("Yes\n".into()),
                         timelimit: None,
+                        memorylimit: None,
                         r#match: None,
                     },
                     PartialBatchTestCase {
@@ -631,6 +855,7 @@ extend: []
                         r#in: "123\n".into(),
                         out: Some("No\n".into()),
                         timelimit: None,
+                        memorylimit: None,
                         r#match: None,
                     },
                     PartialBatchTestCase {
@@ -638,6 +863,7 @@ extend: []
                         r#in: "777\n".into(),
                         out: Some("Yes\n".into()),
                         timelimit: None,
+                        memorylimit: None,
                         r#match: None,
                     },
                 ],
@@ -661,6 +887,7 @@ extend:
 "#,
             &TestSuite::Batch(BatchTestSuite {
                 timelimit: Some(Duration::from_secs(2)),
+                memorylimit: None,
                 r#match: Match::Lines,
                 cases: vec![],
                 extend: vec![Additional::Text {
@@ -701,6 +928,7 @@ extend: []
 "#,
             &TestSuite::Batch(BatchTestSuite {
                 timelimit: Some(Duration::from_secs(2)),
+                memorylimit: None,
                 r#match: Match::Float {
                     relative_error: Some(PositiveFinite(0.01)),
                     absolute_error: Some(PositiveFinite(0.01)),
@@ -711,6 +939,7 @@ extend: []
                         r#in: "1\n".into(),
                         out: Some("6.28318530717958623200\n".into()),
                         timelimit: None,
+                        memorylimit: None,
                         r#match: None,
                     },
                     PartialBatchTestCase {
@@ -718,6 +947,7 @@ extend: []
                         r#in: "73\n".into(),
                         out: Some("458.67252742410977361942\n".into()),
                         timelimit: None,
+                        memorylimit: None,
                         r#match: None,
                     },
                 ],
@@ -755,6 +985,7 @@ extend: []
 "#,
             &TestSuite::Batch(BatchTestSuite {
                 timelimit: Some(Duration::from_secs(2)),
+                memorylimit: None,
                 r#match: Match::Lines,
                 cases: vec![
                     PartialBatchTestCase {
@@ -762,6 +993,7 @@ extend: []
                         r#in: "3\ncbaa\ndaacc\nacacac\n".into(),
                         out: Some("aac\n".into()),
                         timelimit: None,
+                        memorylimit: None,
                         r#match: None,
                     },
                     PartialBatchTestCase {
@@ -769,6 +1001,7 @@ extend: []
                         r#in: "3\na\naa\nb\n".into(),
                         out: Some("\n".into()),
                         timelimit: None,
+                        memorylimit: None,
                         r#match: None,
                     },
                 ],
@@ -777,6 +1010,105 @@ extend: []
         );
     }
 
+    #[test]
+    fn interactive() {
+        let yaml = r#"---
+type: Interactive
+timelimit: 2s
+interactor:
+  cmd: "python ./interactor.py < \"$INPUT\""
+  shell: Bash
+cases:
+  - name: Sample 1
+    in: "3\n"
+  - name: Sample 2
+    in: "100\n"
+    timelimit: 3s
+"#;
+
+        let suite = match serde_yaml::from_str::<TestSuite>(yaml).unwrap() {
+            TestSuite::Interactive(suite) => suite,
+            suite => panic!("expected `Interactive`, got {:?}", suite),
+        };
+
+        let interactor = Interactor {
+            cmd: r#"python ./interactor.py < "$INPUT""#.to_owned(),
+            shell: CheckerShell::Bash,
+        };
+
+        assert_eq!(
+            vec![InteractiveTestCase {
+                name: Some("Sample 2".to_owned()),
+                timelimit: Some(Duration::from_secs(3)),
+                input: Some("100\n".into()),
+                interactor: interactor.clone(),
+            }],
+            suite.load_test_cases(Some(hashset!("Sample 2"))).unwrap(),
+        );
+
+        let suite = InteractiveTestSuite {
+            timelimit: Some(Duration::from_secs(2)),
+            interactor: Some(interactor.clone()),
+            cases: vec![],
+        };
+
+        assert_eq!(
+            vec![InteractiveTestCase {
+                name: None,
+                timelimit: Some(Duration::from_secs(2)),
+                input: None,
+                interactor,
+            }],
+            suite.load_test_cases(None::<HashSet<&str>>).unwrap(),
+        );
+
+        let suite = InteractiveTestSuite {
+            timelimit: Some(Duration::from_secs(2)),
+            interactor: None,
+            cases: vec![],
+        };
+
+        assert!(suite.load_test_cases(None::<HashSet<&str>>).is_err());
+    }
+
+    #[test]
+    fn testlib() {
+        let yaml = r#"---
+type: Batch
+timelimit: 2s
+match:
+  Testlib:
+    checker: ./checker.cpp
+    compile: g++ -O2 -o "$OUTPUT" "$SOURCE"
+cases:
+  - name: Sample 1
+    in: "1\n"
+    out: "2\n"
+"#;
+
+        let suite = match serde_yaml::from_str::<TestSuite>(yaml).unwrap() {
+            TestSuite::Batch(suite) => suite,
+            suite => panic!("expected `Batch`, got {:?}", suite),
+        };
+
+        assert_eq!(
+            vec![BatchTestCase {
+                name: Some("Sample 1".to_owned()),
+                timelimit: Some(Duration::from_secs(2)),
+                memorylimit: None,
+                input: "1\n".into(),
+                output: ExpectedOutput::Testlib {
+                    text: Some("2\n".into()),
+                    checker: "./checker.cpp".to_owned(),
+                    compile: Some(r#"g++ -O2 -o "$OUTPUT" "$SOURCE""#.to_owned()),
+                },
+            }],
+            suite
+                .load_test_cases(Path::new(""), None::<HashSet<&str>>, |_| unreachable!())
+                .unwrap(),
+        );
+    }
+
     fn test_serialize_deserialize(yaml: &str, expected: &TestSuite) {
         let actual = serde_yaml::from_str::<TestSuite>(yaml).unwrap();
         assert_eq!(*expected, actual);
@@ -0,0 +1,945 @@
+use crate::{
+    testsuite::{BatchTestSuite, Match, PartialBatchTestCase, TestSuite},
+    web::{
+        aoj::api::SessionMutExt as _, submissions, AnsiColored, CookieStorage, Exec, Login,
+        LoginOutcome, Platform, RetrieveFullTestCases, RetrieveLanguages, RetrieveLanguagesOutcome,
+        RetrieveTestCases, RetrieveTestCasesOutcome, RetrieveTestCasesOutcomeProblem,
+        RetrieveTestCasesOutcomeProblemTextFiles, Session, SessionMut, Shell, SubmissionSummary,
+        SubmissionSummaryProblem, SubmissionVerdict, SubmissionVerdictKind,
+        SubmissionVerdictProgress, Submit, SubmitOutcome, WatchSubmissions,
+    },
+};
+use anyhow::{bail, Context as _};
+use chrono::{TimeZone as _, Utc};
+use indexmap::indexmap;
+use indicatif::MultiProgress;
+use itertools::Itertools as _;
+use once_cell::sync::Lazy;
+use std::{
+    collections::{BTreeSet, HashMap},
+    convert::Infallible,
+    marker::PhantomData,
+    time::Duration,
+};
+use tokio::runtime::Runtime;
+use url::Url;
+
+static BASE_URL: Lazy<Url> = lazy_url!("https://onlinejudge.u-aizu.ac.jp");
+
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum Aoj<'closures> {
+    Infallible(Infallible, PhantomData<fn() -> &'closures ()>),
+}
+
+impl<'closures> Platform for Aoj<'closures> {
+    type CookieStorage = CookieStorage;
+    type LoginCredentials = AojLoginCredentials<'closures>;
+    type ParticipateTarget = Infallible;
+    type ParticipateCredentials = Infallible;
+    type RetrieveLanguagesTarget = ();
+    type RetrieveLanguagesCredentials = ();
+    type RetrieveTestCasesTargets = AojRetrieveTestCasesTargets;
+    type RetrieveTestCasesCredentials = ();
+    type RetrieveFullTestCasesCredentials = ();
+    type RetrieveSubmissionSummariesTarget = Infallible;
+    type RetrieveSubmissionSummariesCredentials = Infallible;
+    type WatchSubmissionsTarget = AojWatchSubmissionsTarget;
+    type WatchSubmissionsCredentials = AojWatchSubmissionsCredentials<'closures>;
+    type SubmitTarget = AojSubmitTarget;
+    type SubmitCredentials = AojSubmitCredentials<'closures>;
+}
+
+impl Aoj<'_> {
+    pub fn exec<A>(args: A) -> anyhow::Result<<Self as Exec<A>>::Output>
+    where
+        Self: Exec<A>,
+    {
+        <Self as Exec<_>>::exec(args)
+    }
+}
+
+impl<S: Shell> Exec<Login<Self, S>> for Aoj<'_> {
+    type Output = LoginOutcome;
+
+    fn exec(args: Login<Self, S>) -> anyhow::Result<LoginOutcome> {
+        let Login {
+            credentials: AojLoginCredentials {
+                username_and_password,
+            },
+            cookie_storage,
+            timeout,
+            shell,
+        } = args;
+
+        let sess = Session::new(timeout, Some(cookie_storage), shell)?;
+        let (outcome, _) = login(sess, username_and_password)?;
+        Ok(outcome)
+    }
+}
+
+impl<S: Shell> Exec<RetrieveLanguages<Self, S>> for Aoj<'_> {
+    type Output = RetrieveLanguagesOutcome;
+
+    fn exec(args: RetrieveLanguages<Self, S>) -> anyhow::Result<RetrieveLanguagesOutcome> {
+        let RetrieveLanguages {
+            target: (),
+            credentials: (),
+            cookie_storage: _,
+            timeout,
+            shell,
+        } = args;
+
+        // `POST /submissions` takes the names. They are used as the IDs.
+        let names_by_id = Session::new(timeout, None, shell)?
+            .get_languages()?
+            .into_iter()
+            .map(|api::Language { language }| (language.clone(), language))
+            .collect();
+
+        Ok(RetrieveLanguagesOutcome { names_by_id })
+    }
+}
+
+impl<S: Shell> Exec<RetrieveTestCases<Self, S>> for Aoj<'_> {
+    type Output = RetrieveTestCasesOutcome;
+
+    fn exec(args: RetrieveTestCases<Self, S>) -> anyhow::Result<RetrieveTestCasesOutcome> {
+        let RetrieveTestCases {
+            targets,
+            credentials: (),
+            full,
+            cookie_storage: _,
+            timeout,
+            shell,
+        } = args;
+
+        let problem_ids = match targets {
+            AojRetrieveTestCasesTargets::ProblemIds(problem_ids) => problem_ids
+                .iter()
+                .map(|problem_id| parse_problem_id(problem_id))
+                .collect::<anyhow::Result<Vec<_>>>()?,
+            AojRetrieveTestCasesTargets::Urls(urls) => urls
+                .iter()
+                .map(parse_problem_url)
+                .collect::<anyhow::Result<Vec<_>>>()?,
+        };
+
+        let mut sess = Session::new(timeout, None, shell)?;
+
+        let mut outcome = RetrieveTestCasesOutcome { problems: vec![] };
+
+        for problem_id in problem_ids {
+            let api::Problem {
+                id,
+                name,
+                problem_time_limit,
+                problem_memory_limit,
+            } = sess.get_problem(&problem_id)?;
+
+            let cases = sess
+                .get_sample_test_cases(&id)?
+                .into_iter()
+                .map(|api::TestCase { serial, r#in, out }| PartialBatchTestCase {
+                    name: Some(format!("sample{}", serial)),
+                    r#in: r#in.into(),
+                    out: Some(out.into()),
+                    timelimit: None,
+                    memorylimit: None,
+                    r#match: None,
+                })
+                .collect();
+
+            let test_suite = TestSuite::Batch(BatchTestSuite {
+                timelimit: Some(Duration::from_secs(problem_time_limit)),
+                memorylimit: Some(problem_memory_limit * 1024),
+                r#match: Match::Lines,
+                cases,
+                extend: vec![],
+            });
+
+            outcome.problems.push(RetrieveTestCasesOutcomeProblem {
+                contest: None,
+                index: id.clone(),
+                url: url!("/problems/{}", id),
+                screen_name: Some(id),
+                display_name: name,
+                test_suite,
+                text_files: indexmap!(),
+            });
+        }
+
+        if let Some(RetrieveFullTestCases { credentials: () }) = full {
+            for outcome_problem in &mut outcome.problems {
+                let problem_id = outcome_problem
+                    .screen_name
+                    .as_ref()
+                    .expect("should be present");
+
+                let serials = sess
+                    .get_test_case_headers(problem_id)?
+                    .into_iter()
+                    .map(|api::TestCaseHeader { serial }| serial)
+                    .collect::<Vec<_>>();
+
+                // Zero-padded so that the files are sorted in order.
+                let width = serials.iter().max().unwrap_or(&0).to_string().len();
+                let names = serials
+                    .iter()
+                    .map(|serial| format!("{:0width$}", serial, width = width))
+                    .collect::<Vec<_>>();
+
+                let download = |which: api::Which| -> anyhow::Result<_> {
+                    super::download_with_progress(
+                        sess.shell.progress_draw_target(),
+                        serials
+                            .iter()
+                            .zip(&names)
+                            .map(|(&serial, name)| {
+                                let req = sess.get_test_case_file(problem_id, serial, which)?;
+                                Ok((format!("{}/{}", which, name), req))
+                            })
+                            .collect::<Result<_, url::ParseError>>()?,
+                    )
+                };
+
+                let in_contents = download(api::Which::In)?;
+                let out_contents = download(api::Which::Out)?;
+
+                for ((name, r#in), out) in
+                    names.into_iter().zip_eq(in_contents).zip_eq(out_contents)
+                {
+                    outcome_problem.text_files.insert(
+                        name,
+                        RetrieveTestCasesOutcomeProblemTextFiles {
+                            r#in,
+                            out: Some(out),
+                        },
+                    );
+                }
+            }
+        }
+
+        Ok(outcome)
+    }
+}
+
+impl<S: Shell> Exec<WatchSubmissions<Self, S>> for Aoj<'_> {
+    type Output = ();
+
+    fn exec(args: WatchSubmissions<Self, S>) -> anyhow::Result<()> {
+        let WatchSubmissions {
+            target: AojWatchSubmissionsTarget { problem },
+            credentials:
+                AojWatchSubmissionsCredentials {
+                    username_and_password,
+                },
+            cookie_storage,
+            timeout,
+            mut shell,
+        } = args;
+
+        let problem_id = parse_problem_id(&problem)?;
+
+        let mut sess = Session::new(timeout, Some(cookie_storage), &mut shell)?;
+
+        let (_, user_id) = login(&mut sess, username_and_password)?;
+
+        let summaries = sess
+            .get_submission_records(&user_id, &problem_id, SUBMISSIONS_TO_SHOW)?
+            .into_iter()
+            .map(api::SubmissionRecord::into_submission_summary)
+            .collect::<Vec<_>>();
+
+        if summaries.iter().any(|summary| summary.verdict.is_judging()) {
+            watch_submissions(sess, &user_id, &problem_id, &summaries)?;
+        } else {
+            let content = AnsiColored::new(|w| submissions::print_submissions(w, &summaries))?;
+            shell.print_ansi(content.get())?;
+        }
+        Ok(())
+    }
+}
+
+impl<S: Shell> Exec<Submit<Self, S>> for Aoj<'_> {
+    type Output = SubmitOutcome;
+
+    fn exec(args: Submit<Self, S>) -> anyhow::Result<SubmitOutcome> {
+        let Submit {
+            target,
+            credentials: AojSubmitCredentials {
+                username_and_password,
+            },
+            language_id,
+            code,
+            watch_submission,
+            cookie_storage,
+            timeout,
+            shell,
+        } = args;
+
+        let problem_id = match target {
+            AojSubmitTarget::ProblemId(problem_id) => parse_problem_id(&problem_id)?,
+            AojSubmitTarget::Url(url) => parse_problem_url(&url)?,
+        };
+
+        let mut sess = Session::new(timeout, Some(cookie_storage), shell)?;
+
+        let (_, user_id) = login(&mut sess, username_and_password)?;
+
+        let token = sess.post_submission(&problem_id, &language_id, &code)?;
+
+        // The response only contains a token. Finds the judge ID from the records of the user.
+        let record = (|| {
+            for _ in 0..SUBMISSION_RECORD_RETRIES {
+                let record = sess
+                    .get_submission_records(&user_id, &problem_id, SUBMISSIONS_TO_SHOW)?
+                    .into_iter()
+                    .find(|record| record.token.as_deref() == Some(&token));
+
+                if record.is_some() {
+                    return Ok(record);
+                }
+                std::thread::sleep(POLLING_INTERVAL);
+            }
+            Ok::<_, anyhow::Error>(None)
+        })()?
+        .with_context(|| format!("Could not find the submission (token: {:?})", token))?;
+
+        let summary = record.into_submission_summary();
+
+        if watch_submission {
+            watch_submissions(
+                &mut sess,
+                &user_id,
+                &problem_id,
+                std::slice::from_ref(&summary),
+            )?;
+        }
+
+        Ok(SubmitOutcome {
+            problem_screen_name: Some(problem_id.clone()),
+            submission_url: summary.url,
+            submissions_url: submissions_url(&user_id, &problem_id),
+        })
+    }
+}
+
+pub struct AojLoginCredentials<'closures> {
+    pub username_and_password: &'closures mut dyn FnMut() -> anyhow::Result<(String, String)>,
+}
+
+#[derive(Debug)]
+pub enum AojRetrieveTestCasesTargets {
+    ProblemIds(BTreeSet<String>),
+    Urls(BTreeSet<Url>),
+}
+
+#[derive(Debug)]
+pub struct AojWatchSubmissionsTarget {
+    pub problem: String,
+}
+
+pub struct AojWatchSubmissionsCredentials<'closures> {
+    pub username_and_password: &'closures mut dyn FnMut() -> anyhow::Result<(String, String)>,
+}
+
+#[derive(Debug)]
+pub enum AojSubmitTarget {
+    ProblemId(String),
+    Url(Url),
+}
+
+pub struct AojSubmitCredentials<'closures> {
+    pub username_and_password: &'closures mut dyn FnMut() -> anyhow::Result<(String, String)>,
+}
+
+fn parse_problem_id(s: &str) -> anyhow::Result<String> {
+    if !static_regex!(r"\A[a-zA-Z0-9_]+\z").is_match(s) {
+        bail!(
+            "A problem ID for AOJ must be `[a-zA-Z0-9_]+` (e.g. \"ITP1_1_A\", \"0001\"): {:?}",
+            s,
+        );
+    }
+    Ok(s.to_owned())
+}
+
+/// Accepts `/problems/{}`, `/courses/**/{}` and `description.jsp?id={}` of the old site.
+pub(super) fn parse_problem_url(url: &Url) -> anyhow::Result<String> {
+    match url.domain() {
+        Some("onlinejudge.u-aizu.ac.jp") => {
+            let caps = static_regex!(r"\A/(problems|courses/.+)/([a-zA-Z0-9_]+)/?\z")
+                .captures(url.path())
+                .with_context(|| format!("`{}` is not a URL for problem in AOJ", url))?;
+            Ok(caps[2].to_owned())
+        }
+        Some("judge.u-aizu.ac.jp") => url
+            .query_pairs()
+            .find(|(k, _)| k == "id")
+            .filter(|_| url.path() == "/onlinejudge/description.jsp")
+            .with_context(|| format!("`{}` is not a URL for problem in AOJ", url))
+            .and_then(|(_, id)| parse_problem_id(&id)),
+        _ => bail!(
+            "wrong domain. expected `onlinejudge.u-aizu.ac.jp` or `judge.u-aizu.ac.jp`: {}",
+            url,
+        ),
+    }
+}
+
+fn submissions_url(user_id: &str, problem_id: &str) -> Url {
+    url!("/status/users/{}/submissions/1/{}", user_id, problem_id)
+}
+
+fn login(
+    mut sess: impl SessionMut,
+    mut username_and_password: impl FnMut() -> anyhow::Result<(String, String)>,
+) -> anyhow::Result<(LoginOutcome, String)> {
+    if let Some(api::User { id }) = sess.get_self()? {
+        return Ok((LoginOutcome::AlreadyLoggedIn, id));
+    }
+
+    loop {
+        let (id, password) = username_and_password()?;
+
+        if let Some(api::User { id }) = sess.post_session(&id, &password)? {
+            break Ok((LoginOutcome::Success, id));
+        }
+
+        sess.shell().warn("Failed to login. Try again")?;
+    }
+}
+
+/// Same as the number of the rows in a page of `/status/users/{}/submissions/1/{}`.
+const SUBMISSIONS_TO_SHOW: usize = 20;
+
+const SUBMISSION_RECORD_RETRIES: usize = 10;
+
+const POLLING_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Polls the submission records until all of the `summaries` are judged.
+fn watch_submissions(
+    mut sess: impl SessionMut,
+    user_id: &str,
+    problem_id: &str,
+    summaries: &[SubmissionSummary],
+) -> anyhow::Result<()> {
+    let rt = Runtime::new()?;
+
+    let mp = MultiProgress::with_draw_target(sess.shell().progress_draw_target());
+
+    let mut pbs = summaries
+        .iter()
+        .zip(submissions::add_progress_bars(&mp, summaries))
+        .filter(|(summary, _)| summary.verdict.is_judging())
+        .map(|(summary, pb)| (summary.id.clone(), pb))
+        .collect::<HashMap<_, _>>();
+
+    let client = sess.async_client().clone();
+    let (user_id, problem_id) = (user_id.to_owned(), problem_id.to_owned());
+
+    let handle = rt.spawn(async move {
+        while !pbs.is_empty() {
+            tokio::time::sleep(POLLING_INTERVAL).await;
+
+            let records = match api::get_submission_records_async(
+                &client,
+                &user_id,
+                &problem_id,
+                SUBMISSIONS_TO_SHOW,
+            )
+            .await
+            {
+                Ok(records) => records,
+                Err(err) => {
+                    tokio::task::block_in_place(|| {
+                        for pb in pbs.values() {
+                            pb.finish_at_current_pos();
+                        }
+                    });
+                    return Err(err);
+                }
+            };
+
+            tokio::task::block_in_place(|| {
+                for record in records {
+                    let summary = record.into_submission_summary();
+
+                    if summary.verdict.is_judging() {
+                        if let Some(pb) = pbs.get(&summary.id) {
+                            submissions::set_progress(pb, &summary.verdict);
+                        }
+                    } else if let Some(pb) = pbs.remove(&summary.id) {
+                        submissions::finish_progress(
+                            &pb,
+                            &summary.verdict,
+                            summary.exec_time.as_deref(),
+                            summary.memory.as_deref(),
+                        );
+                    }
+                }
+            });
+        }
+        Ok(())
+    });
+
+    mp.join()?;
+    rt.block_on(handle)?
+}
+
+impl api::SubmissionRecord {
+    fn into_submission_summary(self) -> SubmissionSummary {
+        let verdict = self.submission_verdict();
+
+        let (exec_time, memory) = if verdict.is_judging() {
+            (None, None)
+        } else {
+            (
+                Some(format!("{} ms", self.cpu_time * 10)),
+                Some(format!("{} KB", self.memory)),
+            )
+        };
+
+        let display_name = match &self.problem_title {
+            Some(title) => format!("{} - {}", self.problem_id, title),
+            None => self.problem_id.clone(),
+        };
+
+        SubmissionSummary {
+            id: self.judge_id.to_string(),
+            url: url!(
+                "/status/users/{}/submissions/1/{}/judge/{}/{}",
+                self.user_id,
+                self.problem_id,
+                self.judge_id,
+                self.language,
+            ),
+            submission_time: Utc
+                .timestamp_millis_opt(self.submission_date)
+                .single()
+                .expect("should be valid")
+                .into(),
+            problem: SubmissionSummaryProblem {
+                display_name,
+                url: url!("/problems/{}", self.problem_id),
+            },
+            language: self.language,
+            verdict,
+            exec_time,
+            memory,
+        }
+    }
+
+    /// The text is the same as the status pages. (e.g. "Accepted", "Wrong Answer")
+    fn submission_verdict(&self) -> SubmissionVerdict {
+        use SubmissionVerdictKind as K;
+
+        let (kind, text) = match self.status {
+            0 => (K::CompilationError, "Compile Error"),
+            1 => (K::WrongAnswer, "Wrong Answer"),
+            2 => (K::TimeLimitExceeded, "Time Limit Exceeded"),
+            3 => (K::MemoryLimitExceeded, "Memory Limit Exceeded"),
+            4 => (K::Accepted, "Accepted"),
+            5 => (K::Waiting, "Waiting Judge"),
+            6 => (K::OutputLimitExceeded, "Output Limit Exceeded"),
+            7 => (K::RuntimeError, "Runtime Error"),
+            8 => (K::PresentationError, "Presentation Error"),
+            9 => (K::Judging, "Running"),
+            _ => (K::Other, "Unknown"),
+        };
+
+        // `accuracy` is "{passed}/{total}".
+        let progress = self
+            .accuracy
+            .as_deref()
+            .and_then(|accuracy| {
+                let (judged, total) = accuracy.split('/').collect_tuple()?;
+                Some(SubmissionVerdictProgress {
+                    judged: judged.trim().parse().ok()?,
+                    total: total.trim().parse().ok(),
+                })
+            })
+            .filter(|_| kind == K::Judging);
+
+        SubmissionVerdict::new(kind, progress, text)
+    }
+}
+
+mod api {
+    //! <http://developers.u-aizu.ac.jp/index>
+
+    use crate::web::{ResponseExt as _, SessionMut};
+    use anyhow::{anyhow, bail};
+    use maplit::hashmap;
+    use once_cell::sync::Lazy;
+    use serde::{de::DeserializeOwned, Deserialize};
+    use url::Url;
+
+    static BASE_URL: Lazy<Url> = lazy_url!("https://judgeapi.u-aizu.ac.jp");
+
+    static JUDGEDAT_URL: Lazy<Url> = lazy_url!("https://judgedat.u-aizu.ac.jp");
+
+    pub(super) trait SessionMutExt: SessionMut {
+        /// > Find user info of the current session
+        ///
+        /// `GET /self`. Returns `None` if not logged in.
+        fn get_self(&mut self) -> anyhow::Result<Option<User>> {
+            let res = self
+                .get(url!("/self"))
+                .colorize_status_code(&[200], &[401], ..)
+                .send()?
+                .ensure_status(&[200, 401])?;
+
+            if res.status() == 200 {
+                res.json().map(Some).map_err(Into::into)
+            } else {
+                Ok(None)
+            }
+        }
+
+        /// > Create a session
+        ///
+        /// `POST /session`. Returns `None` if the ID or the password is wrong.
+        fn post_session(&mut self, id: &str, password: &str) -> anyhow::Result<Option<User>> {
+            let res = self
+                .post(url!("/session"))
+                .json(&hashmap!("id" => id, "password" => password))
+                .colorize_status_code(&[200], &[400, 401], ..)
+                .send()?;
+
+            match res.status().as_u16() {
+                200 => res.json().map(Some).map_err(Into::into),
+                400 | 401 => Ok(None),
+                _ => bail!("expected [200, 400, 401], got {}", res.status()),
+            }
+        }
+
+        /// > Find all languages
+        ///
+        /// `GET /languages`.
+        fn get_languages(&mut self) -> anyhow::Result<Vec<Language>> {
+            json(self, url!("/languages"))
+        }
+
+        /// > Find a problem by problem id
+        ///
+        /// `GET /problems/{problemId}`.
+        fn get_problem(&mut self, problem_id: &str) -> anyhow::Result<Problem> {
+            json(self, url!("/problems/{}", problem_id))
+        }
+
+        /// > Find sample test cases by problem id
+        ///
+        /// `GET https://judgedat.u-aizu.ac.jp/testcases/samples/{problemId}`.
+        fn get_sample_test_cases(&mut self, problem_id: &str) -> anyhow::Result<Vec<TestCase>> {
+            let url = JUDGEDAT_URL.join(&format!("/testcases/samples/{}", problem_id))?;
+            json(self, url)
+        }
+
+        /// > Find test case headers by problem id
+        ///
+        /// `GET https://judgedat.u-aizu.ac.jp/testcases/{problemId}/header`.
+        fn get_test_case_headers(
+            &mut self,
+            problem_id: &str,
+        ) -> anyhow::Result<Vec<TestCaseHeader>> {
+            #[derive(Deserialize)]
+            struct Headers {
+                headers: Vec<TestCaseHeader>,
+            }
+
+            let url = JUDGEDAT_URL.join(&format!("/testcases/{}/header", problem_id))?;
+            let Headers { headers } = json(self, url)?;
+            Ok(headers)
+        }
+
+        /// > Find the input or the output of a test case
+        ///
+        /// `GET https://judgedat.u-aizu.ac.jp/testcases/{problemId}/{serial}/{in|out}`.
+        fn get_test_case_file(
+            &self,
+            problem_id: &str,
+            serial: u64,
+            which: Which,
+        ) -> Result<reqwest::RequestBuilder, url::ParseError> {
+            let url =
+                JUDGEDAT_URL.join(&format!("/testcases/{}/{}/{}", problem_id, serial, which))?;
+            Ok(self.async_client().get(url))
+        }
+
+        /// > Submit a source code
+        ///
+        /// `POST /submissions`. Returns the token.
+        fn post_submission(
+            &mut self,
+            problem_id: &str,
+            language: &str,
+            source_code: &str,
+        ) -> anyhow::Result<String> {
+            #[derive(Deserialize)]
+            struct Ok {
+                token: String,
+            }
+
+            let res = self
+                .post(url!("/submissions"))
+                .json(&hashmap!(
+                    "problemId" => problem_id,
+                    "language" => language,
+                    "sourceCode" => source_code,
+                ))
+                .colorize_status_code(&[200], (), ..)
+                .send()?;
+
+            if res.status() == 200 {
+                let Ok { token } = res.json()?;
+                Result::Ok(token)
+            } else {
+                let status = res.status();
+                let msg = res.text()?;
+                bail!("Submission rejected: ({}, {:?})", status, msg);
+            }
+        }
+
+        /// > Find submission records by user id and problem id
+        ///
+        /// `GET /submission_records/users/{userId}/problems/{problemId}`. The newest comes first.
+        fn get_submission_records(
+            &mut self,
+            user_id: &str,
+            problem_id: &str,
+            size: usize,
+        ) -> anyhow::Result<Vec<SubmissionRecord>> {
+            json(self, submission_records_url(user_id, problem_id, size))
+        }
+    }
+
+    impl<S: SessionMut> SessionMutExt for S {}
+
+    pub(super) async fn get_submission_records_async(
+        client: &reqwest::Client,
+        user_id: &str,
+        problem_id: &str,
+        size: usize,
+    ) -> anyhow::Result<Vec<SubmissionRecord>> {
+        let res = client
+            .get(submission_records_url(user_id, problem_id, size))
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(res.json().await?)
+    }
+
+    fn submission_records_url(user_id: &str, problem_id: &str, size: usize) -> Url {
+        let mut url = url!(
+            "/submission_records/users/{}/problems/{}",
+            user_id,
+            problem_id,
+        );
+        url.query_pairs_mut()
+            .append_pair("page", "0")
+            .append_pair("size", &size.to_string());
+        url
+    }
+
+    fn json<S: SessionMut, T: DeserializeOwned>(mut sess: S, url: Url) -> anyhow::Result<T> {
+        let res = sess.get(url).colorize_status_code(&[200], (), ..).send()?;
+
+        if res.status() == 200 {
+            res.json().map_err(Into::into)
+        } else {
+            let status = res.status();
+            let msg = res.text()?;
+            Err(anyhow!("API error: ({}, {:?})", status, msg))
+        }
+    }
+
+    #[derive(Debug, Deserialize)]
+    pub(super) struct User {
+        pub(super) id: String,
+        // __rest: (),
+    }
+
+    #[derive(Debug, Deserialize)]
+    pub(super) struct Language {
+        /// e.g. "C++17"
+        pub(super) language: String,
+        // __rest: (),
+    }
+
+    #[derive(Debug, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub(super) struct Problem {
+        pub(super) id: String,
+        pub(super) name: String,
+        /// In seconds.
+        pub(super) problem_time_limit: u64,
+        /// In kilobytes.
+        pub(super) problem_memory_limit: u64,
+        // __rest: (),
+    }
+
+    #[derive(Debug, Deserialize)]
+    pub(super) struct TestCase {
+        pub(super) serial: u64,
+        pub(super) r#in: String,
+        pub(super) out: String,
+        // __rest: (),
+    }
+
+    #[derive(Debug, Deserialize)]
+    pub(super) struct TestCaseHeader {
+        pub(super) serial: u64,
+        // __rest: (),
+    }
+
+    #[derive(Debug, Clone, Copy, strum::Display)]
+    #[strum(serialize_all = "lowercase")]
+    pub(super) enum Which {
+        In,
+        Out,
+    }
+
+    #[derive(Debug, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub(super) struct SubmissionRecord {
+        pub(super) judge_id: u64,
+        pub(super) user_id: String,
+        pub(super) problem_id: String,
+        #[serde(default)]
+        pub(super) problem_title: Option<String>,
+        /// In milliseconds since the epoch.
+        pub(super) submission_date: i64,
+        pub(super) language: String,
+        /// 0: CE, 1: WA, 2: TLE, 3: MLE, 4: AC, 5: WJ, 6: OLE, 7: RE, 8: PE, 9: Running
+        pub(super) status: i32,
+        /// In 1/100 seconds.
+        pub(super) cpu_time: u64,
+        /// In kilobytes.
+        pub(super) memory: u64,
+        /// "{passed}/{total}"
+        #[serde(default)]
+        pub(super) accuracy: Option<String>,
+        #[serde(default)]
+        pub(super) token: Option<String>,
+        // __rest: (),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::api::SubmissionRecord;
+    use crate::web::{SubmissionVerdict, SubmissionVerdictKind, SubmissionVerdictProgress};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn submission_verdict() {
+        use SubmissionVerdictKind as K;
+
+        let verdict = |status, accuracy: Option<&str>| {
+            record(status, accuracy.map(ToOwned::to_owned)).submission_verdict()
+        };
+
+        assert_eq!(
+            SubmissionVerdict::new(K::CompilationError, None, "Compile Error"),
+            verdict(0, None),
+        );
+        assert_eq!(
+            SubmissionVerdict::new(K::WrongAnswer, None, "Wrong Answer"),
+            verdict(1, Some("3/10")),
+        );
+        assert_eq!(
+            SubmissionVerdict::new(K::TimeLimitExceeded, None, "Time Limit Exceeded"),
+            verdict(2, Some("9/10")),
+        );
+        assert_eq!(
+            SubmissionVerdict::new(K::MemoryLimitExceeded, None, "Memory Limit Exceeded"),
+            verdict(3, Some("9/10")),
+        );
+        assert_eq!(
+            SubmissionVerdict::new(K::Accepted, None, "Accepted"),
+            verdict(4, Some("10/10")),
+        );
+        assert_eq!(
+            SubmissionVerdict::new(K::Waiting, None, "Waiting Judge"),
+            verdict(5, None),
+        );
+        assert_eq!(
+            SubmissionVerdict::new(K::OutputLimitExceeded, None, "Output Limit Exceeded"),
+            verdict(6, Some("1/10")),
+        );
+        assert_eq!(
+            SubmissionVerdict::new(K::RuntimeError, None, "Runtime Error"),
+            verdict(7, Some("1/10")),
+        );
+        assert_eq!(
+            SubmissionVerdict::new(K::PresentationError, None, "Presentation Error"),
+            verdict(8, Some("1/10")),
+        );
+        assert_eq!(
+            SubmissionVerdict::new(
+                K::Judging,
+                Some(SubmissionVerdictProgress {
+                    judged: 4,
+                    total: Some(10),
+                }),
+                "Running",
+            ),
+            verdict(9, Some("4/10")),
+        );
+        assert_eq!(
+            SubmissionVerdict::new(K::Judging, None, "Running"),
+            verdict(9, None),
+        );
+        assert_eq!(
+            SubmissionVerdict::new(K::Other, None, "Unknown"),
+            verdict(10, None),
+        );
+    }
+
+    #[test]
+    fn into_submission_summary() {
+        let summary = record(4, Some("10/10".to_owned())).into_submission_summary();
+
+        assert_eq!(
+            (
+                "5000001".to_owned(),
+                "https://onlinejudge.u-aizu.ac.jp/status/users/alice/submissions/1/ITP1%5F1%5FA/judge/5000001/Rust".to_owned(),
+                "2021-01-01T00:00:00+00:00".to_owned(),
+                "ITP1_1_A - Hello World".to_owned(),
+                "https://onlinejudge.u-aizu.ac.jp/problems/ITP1%5F1%5FA".to_owned(),
+                "Rust".to_owned(),
+                Some("10 ms".to_owned()),
+                Some("2048 KB".to_owned()),
+            ),
+            (
+                summary.id,
+                summary.url.to_string(),
+                summary.submission_time.to_rfc3339(),
+                summary.problem.display_name,
+                summary.problem.url.to_string(),
+                summary.language,
+                summary.exec_time,
+                summary.memory,
+            ),
+        );
+
+        let summary = record(9, Some("4/10".to_owned())).into_submission_summary();
+
+        assert_eq!((None, None), (summary.exec_time, summary.memory));
+    }
+
+    fn record(status: i32, accuracy: Option<String>) -> SubmissionRecord {
+        SubmissionRecord {
+            judge_id: 5_000_001,
+            user_id: "alice".to_owned(),
+            problem_id: "ITP1_1_A".to_owned(),
+            problem_title: Some("Hello World".to_owned()),
+            submission_date: 1_609_459_200_000,
+            language: "Rust".to_owned(),
+            status,
+            cpu_time: 1,
+            memory: 2048,
+            accuracy,
+            token: None,
+        }
+    }
+}
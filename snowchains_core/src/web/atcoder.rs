@@ -4,35 +4,35 @@ use crate::{
         TestSuite,
     },
     web::{
-        AnsiColored, CaseConverted, CookieStorage, Exec, Login, LoginOutcome, LowerCase,
-        Participate, ParticipateOutcome, Platform, ProblemInContest, ProblemsInContest,
+        submissions, AnsiColored, CaseConverted, CookieStorage, Exec, Login, LoginOutcome,
+        LowerCase, Participate, ParticipateOutcome, Platform, ProblemInContest, ProblemsInContest,
         ResponseExt as _, RetrieveFullTestCases, RetrieveLanguages, RetrieveLanguagesOutcome,
-        RetrieveSubmissionSummaries, RetrieveTestCases, RetrieveTestCasesOutcome,
-        RetrieveTestCasesOutcomeProblem, RetrieveTestCasesOutcomeProblemContest,
-        RetrieveTestCasesOutcomeProblemTextFiles, Session, SessionMut, Shell, Submit,
-        SubmitOutcome, WatchSubmissions,
+        RetrieveSubmissionSummaries, RetrieveSubmissionSummariesOutcome, RetrieveTestCases,
+        RetrieveTestCasesOutcome, RetrieveTestCasesOutcomeProblem,
+        RetrieveTestCasesOutcomeProblemContest, RetrieveTestCasesOutcomeProblemTextFiles, Session,
+        SessionMut, Shell, SubmissionSummary, SubmissionSummaryProblem, SubmissionVerdict,
+        SubmissionVerdictKind, SubmissionVerdictProgress, Submit, SubmitOutcome, WatchSubmissions,
     },
 };
 use anyhow::{anyhow, bail, Context as _};
-use chrono::{DateTime, FixedOffset, Local, Utc};
+use chrono::{DateTime, Local, Utc};
 use easy_ext::ext;
 use either::Either;
 use indexmap::{indexmap, IndexMap};
-use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+use indicatif::MultiProgress;
 use itertools::Itertools as _;
 use maplit::{btreemap, hashmap, hashset};
 use once_cell::sync::Lazy;
 use regex::Regex;
 use reqwest::header;
 use scraper::{ElementRef, Html, Selector};
-use serde::{Deserialize, Serialize, Serializer};
+use serde::Deserialize;
 use serde_json::json;
 use std::{
     collections::{BTreeMap, BTreeSet, HashMap, HashSet},
     convert::Infallible,
     fmt,
     hash::Hash,
-    io,
     marker::PhantomData,
     mem,
     ops::Deref,
@@ -40,9 +40,7 @@ use std::{
     str::FromStr,
     time::Duration,
 };
-use termcolor::{Color, ColorSpec, WriteColor};
 use tokio::runtime::Runtime;
-use unicode_width::UnicodeWidthStr as _;
 use url::Url;
 
 static BASE_URL: Lazy<Url> = lazy_url!("https://atcoder.jp");
@@ -58,6 +56,26 @@ pub fn contest_id_from_url(url: &Url) -> anyhow::Result<String> {
         .with_context(|| "Could not extract contest ID of the problem")
 }
 
+pub(super) fn contest_and_problem_index_from_url(url: &Url) -> anyhow::Result<(String, String)> {
+    if url.domain() != Some("atcoder.jp") {
+        bail!("wrong domain. expected `atcoder.jp`: {}", url);
+    }
+
+    let caps = static_regex!(r"\A/contests/([a-zA-Z0-9_\-]+)/tasks/([a-zA-Z0-9_\-]+)\z")
+        .captures(url.path())
+        .with_context(|| format!("`{}` is not a URL for problem in AtCoder", url))?;
+
+    let (contest_id, task_screen_name) = (&caps[1], &caps[2]);
+
+    let problem_index = task_screen_name
+        .strip_prefix(contest_id)
+        .and_then(|s| s.strip_prefix('_'))
+        .or_else(|| task_screen_name.rsplit('_').next())
+        .unwrap_or(task_screen_name);
+
+    Ok((contest_id.to_owned(), problem_index.to_owned()))
+}
+
 #[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
 pub enum Atcoder<'closures> {
     Infallible(Infallible, PhantomData<fn() -> &'closures ()>),
@@ -464,11 +482,11 @@ impl<S: Shell> Exec<RetrieveTestCases<Self, S>> for Atcoder<'_> {
 }
 
 impl<S: Shell> Exec<RetrieveSubmissionSummaries<Self, S>> for Atcoder<'_> {
-    type Output = AtcoderRetrieveSubmissionSummariesOutcome;
+    type Output = RetrieveSubmissionSummariesOutcome;
 
     fn exec(
         args: RetrieveSubmissionSummaries<Self, S>,
-    ) -> anyhow::Result<AtcoderRetrieveSubmissionSummariesOutcome> {
+    ) -> anyhow::Result<RetrieveSubmissionSummariesOutcome> {
         let RetrieveSubmissionSummaries {
             target: AtcoderRetrieveSubmissionSummariesTarget { contest },
             credentials:
@@ -494,7 +512,7 @@ impl<S: Shell> Exec<RetrieveSubmissionSummaries<Self, S>> for Atcoder<'_> {
             summaries.extend(extend);
         }
 
-        Ok(AtcoderRetrieveSubmissionSummariesOutcome { summaries })
+        Ok(RetrieveSubmissionSummariesOutcome { summaries })
     }
 }
 
@@ -575,7 +593,7 @@ impl<S: Shell> Exec<Submit<Self, S>> for Atcoder<'_> {
 
                 let outcome = SubmitOutcome {
                     problem_screen_name: Some(problem_screen_name),
-                    submission_url: submission_summaries[0].detail.clone(),
+                    submission_url: submission_summaries[0].url.clone(),
                     submissions_url: url!("/contests/{}/submissions/me", contest),
                 };
 
@@ -616,14 +634,10 @@ impl<S: Shell> Exec<WatchSubmissions<Self, S>> for Atcoder<'_> {
         let (summaries, _) =
             retrieve_submission_summaries(&mut sess, &contest, 1, username_and_password)?;
 
-        let any_incomplete = summaries.iter().any(|SubmissionSummary { status, .. }| {
-            matches!(status, Verdict::Wj | Verdict::Judging(..))
-        });
-
-        if any_incomplete {
+        if summaries.iter().any(|summary| summary.verdict.is_judging()) {
             watch_submissions(sess, &contest, &summaries)?;
         } else {
-            let content = AnsiColored::new(|w| print_submissions(w, &summaries))?;
+            let content = AnsiColored::new(|w| submissions::print_submissions(w, &summaries))?;
             shell.print_ansi(content.get())?;
         }
         Ok(())
@@ -670,18 +684,6 @@ pub struct AtcoderRetrieveSubmissionSummariesCredentials<'closures> {
     pub username_and_password: &'closures mut dyn FnMut() -> anyhow::Result<(String, String)>,
 }
 
-#[non_exhaustive]
-#[derive(Debug, Serialize)]
-pub struct AtcoderRetrieveSubmissionSummariesOutcome {
-    summaries: Vec<SubmissionSummary>,
-}
-
-impl AtcoderRetrieveSubmissionSummariesOutcome {
-    pub fn to_json(&self) -> String {
-        serde_json::to_string(self).expect("should not fail")
-    }
-}
-
 #[derive(Debug)]
 pub struct AtcoderWatchSubmissionsTarget {
     pub contest: String,
@@ -815,6 +817,7 @@ fn retrieve_sample_test_cases(
 
                                 TestSuite::Batch(BatchTestSuite {
                                     timelimit: None,
+                                    memorylimit: None,
                                     r#match: Match::Lines,
                                     cases: vec![],
                                     extend: vec![],
@@ -1003,64 +1006,6 @@ fn retrieve_submission_summaries(
     }
 }
 
-fn print_submissions(mut wtr: impl WriteColor, summaries: &[SubmissionSummary]) -> io::Result<()> {
-    let task_display_max_width = summaries
-        .iter()
-        .map(
-            |SubmissionSummary {
-                 task: SubmissionSummaryTask { display_name, .. },
-                 ..
-             }| display_name.width(),
-        )
-        .max()
-        .unwrap_or(0);
-
-    let lang_max_width = summaries
-        .iter()
-        .map(|SubmissionSummary { language, .. }| language.width())
-        .max()
-        .unwrap_or(0);
-
-    for summary in summaries {
-        write!(
-            wtr,
-            "│ {} │ {} │ {} │ ",
-            summary.submission_time,
-            align_left(&summary.task.display_name, task_display_max_width),
-            align_left(&summary.language, lang_max_width),
-        )?;
-
-        match &summary.status {
-            Verdict::Wj => {
-                wtr.set_color(color_spec!(Bold))?;
-                write!(wtr, "WJ")?;
-                wtr.reset()?;
-            }
-            verdict @ Verdict::Judging(..) => {
-                wtr.set_color(&verdict.color_spec())?;
-                write!(wtr, "{}", verdict)?;
-                wtr.reset()?;
-            }
-            verdict => {
-                wtr.set_color(&verdict.color_spec())?;
-                write!(wtr, "{}", align_left(&verdict.to_string(), 3))?;
-                wtr.reset()?;
-                write!(
-                    wtr,
-                    " │ {:>8} │ {:>10} │",
-                    summary.exec_time.as_deref().unwrap_or(""),
-                    summary.memory.as_deref().unwrap_or(""),
-                )?;
-            }
-        }
-
-        writeln!(wtr)?;
-    }
-
-    wtr.flush()?;
-    Ok(())
-}
-
 fn watch_submissions(
     mut sess: impl SessionMut,
     contest: &CaseConverted<LowerCase>,
@@ -1071,159 +1016,105 @@ fn watch_submissions(
 
     let mp = MultiProgress::with_draw_target(sess.shell().progress_draw_target());
 
-    let task_display_max_width = summaries
-        .iter()
-        .map(
-            |SubmissionSummary {
-                 task: SubmissionSummaryTask { display_name, .. },
-                 ..
-             }| display_name.width(),
-        )
-        .max()
-        .unwrap_or(0);
-
-    let lang_max_width = summaries
-        .iter()
-        .map(|SubmissionSummary { language, .. }| language.width())
-        .max()
-        .unwrap_or(0);
-
-    for summary in summaries {
-        let pb = mp.add(ProgressBar::new(0));
-
-        pb.set_style(
-            ProgressStyle::default_bar().template("{prefix}{msg:3.bold}                         │"),
-        );
+    let pbs = submissions::add_progress_bars(&mp, summaries);
 
-        pb.set_prefix(&format!(
-            "│ {} │ {} │ {} │ ",
-            summary.submission_time,
-            align_left(&summary.task.display_name, task_display_max_width),
-            align_left(&summary.language, lang_max_width),
-        ));
+    for (summary, pb) in summaries.iter().zip(pbs) {
+        if !summary.verdict.is_judging() {
+            continue;
+        }
 
-        if matches!(
-            summary.status,
-            Verdict::Wj | Verdict::Wr | Verdict::Judging(..)
-        ) {
-            let id = summary.id().to_owned();
+        let id = summary.id.clone();
 
-            let mut url = url!("/contests/{}/submissions/me/status/json", contest);
+        let mut url = url!("/contests/{}/submissions/me/status/json", contest);
 
-            url.query_pairs_mut()
-                .append_pair("reload", "true")
-                .append_pair("sids[]", &id);
+        url.query_pairs_mut()
+            .append_pair("reload", "true")
+            .append_pair("sids[]", &id);
 
-            let client = sess.async_client().clone();
+        let client = sess.async_client().clone();
 
-            let cookie_header = sess.cookie_header(&"https://atcoder.jp".parse().unwrap());
+        let cookie_header = sess.cookie_header(&"https://atcoder.jp".parse().unwrap());
 
-            handles.push(rt.spawn(async move {
-                let finish_pb = || tokio::task::block_in_place(|| pb.finish_at_current_pos());
+        handles.push(rt.spawn(async move {
+            let finish_pb = || tokio::task::block_in_place(|| pb.finish_at_current_pos());
 
-                macro_rules! trap(($result:expr $(,)?) => {
-                    match $result {
-                        Ok(ok) => ok,
-                        Err(err) => {
-                            finish_pb();
-                            return Err(err.into());
-                        }
+            macro_rules! trap(($result:expr $(,)?) => {
+                match $result {
+                    Ok(ok) => ok,
+                    Err(err) => {
+                        finish_pb();
+                        return Err(err.into());
                     }
-                });
+                }
+            });
 
-                loop {
-                    #[derive(Deserialize)]
-                    #[serde(rename_all = "PascalCase")]
-                    struct VerdictProgress {
-                        interval: Option<u64>,
-                        result: IndexMap<String, VerdictProgressResult>,
-                    }
+            loop {
+                #[derive(Deserialize)]
+                #[serde(rename_all = "PascalCase")]
+                struct VerdictProgress {
+                    interval: Option<u64>,
+                    result: IndexMap<String, VerdictProgressResult>,
+                }
 
-                    #[derive(Deserialize)]
-                    #[serde(rename_all = "PascalCase")]
-                    struct VerdictProgressResult {
-                        html: String,
-                        //score: String,
-                    }
+                #[derive(Deserialize)]
+                #[serde(rename_all = "PascalCase")]
+                struct VerdictProgressResult {
+                    html: String,
+                    //score: String,
+                }
 
-                    let res = trap!(
-                        client
-                            .get(url.clone())
-                            .header(header::COOKIE, &cookie_header)
-                            .send()
-                            .await,
-                    );
+                let res = trap!(
+                    client
+                        .get(url.clone())
+                        .header(header::COOKIE, &cookie_header)
+                        .send()
+                        .await,
+                );
 
-                    let VerdictProgress { interval, result } = trap!(res.json().await);
+                let VerdictProgress { interval, result } = trap!(res.json().await);
 
-                    let VerdictProgressResult { html } = trap!(result
-                        .get(&id)
-                        .with_context(|| format!("Not found: `{}`", id)));
+                let VerdictProgressResult { html } = trap!(result
+                    .get(&id)
+                    .with_context(|| format!("Not found: `{}`", id)));
 
-                    let text = tokio::task::block_in_place(|| {
-                        Html::parse_fragment(html)
-                            .root_element()
-                            .text()
-                            .map(ToOwned::to_owned)
-                            .collect::<Vec<_>>()
-                    });
+                let text = tokio::task::block_in_place(|| {
+                    Html::parse_fragment(html)
+                        .root_element()
+                        .text()
+                        .map(ToOwned::to_owned)
+                        .collect::<Vec<_>>()
+                });
 
-                    if let Some(interval) = interval {
-                        let verdict = match &text[..] {
-                            [verdict] => verdict,
-                            _ => trap!(Err(anyhow!("Could not extract information"))),
-                        };
+                if let Some(interval) = interval {
+                    let verdict = match &text[..] {
+                        [verdict] => Verdict::new(verdict).to_submission_verdict(),
+                        _ => trap!(Err(anyhow!("Could not extract information"))),
+                    };
 
-                        tokio::task::block_in_place(|| {
-                            if let Some(caps) = JUDGING.captures(verdict) {
-                                let position = caps[1].parse().unwrap();
-                                let length = caps[2].parse().unwrap();
-                                let verdict = &caps[3];
-
-                                pb.set_style(ProgressStyle::default_bar().template(&format!(
-                                    "{{prefix}}{{msg:3{style}}} {{pos:>3{style}}}/\
-                                     {{len:>3{style}}} {{bar:15{style}}} │",
-                                    style = style(verdict),
-                                )));
-
-                                pb.set_message(verdict);
-                                pb.set_length(length);
-                                pb.set_position(position);
-                            } else {
-                                pb.set_message(verdict);
-                            }
-                        });
+                    tokio::task::block_in_place(|| submissions::set_progress(&pb, &verdict));
 
-                        tokio::time::sleep(Duration::from_millis(interval)).await;
-                    } else {
-                        let (verdict, time_and_memory) = match &text[..] {
-                            [verdict, time_and_memory] => (verdict, time_and_memory),
-                            _ => trap!(Err(anyhow!("Could not extract information"))),
-                        };
+                    tokio::time::sleep(Duration::from_millis(interval)).await;
+                } else {
+                    let (verdict, time_and_memory) = match &text[..] {
+                        [verdict, time_and_memory] => (verdict, time_and_memory),
+                        _ => trap!(Err(anyhow!("Could not extract information"))),
+                    };
 
-                        let verdict = Verdict::new(verdict);
+                    let verdict = Verdict::new(verdict).to_submission_verdict();
 
-                        let (exec_time, memory) =
-                            match *time_and_memory.split(" ms").collect::<Vec<_>>() {
-                                [time, memory] => (format!("{} ms", time), memory),
-                                _ => trap!(Err(anyhow!("Could not extract information"))),
-                            };
+                    let (exec_time, memory) =
+                        match *time_and_memory.split(" ms").collect::<Vec<_>>() {
+                            [time, memory] => (format!("{} ms", time), memory),
+                            _ => trap!(Err(anyhow!("Could not extract information"))),
+                        };
 
-                        tokio::task::block_in_place(|| {
-                            finish(&pb, &verdict, &exec_time, memory);
-                        });
-                        break Result::<(), anyhow::Error>::Ok(());
-                    }
+                    tokio::task::block_in_place(|| {
+                        submissions::finish_progress(&pb, &verdict, Some(&exec_time), Some(memory));
+                    });
+                    break Result::<(), anyhow::Error>::Ok(());
                 }
-            }));
-        } else {
-            finish(
-                &pb,
-                &summary.status,
-                summary.exec_time.as_deref().unwrap_or(""),
-                summary.memory.as_deref().unwrap_or(""),
-            );
-        }
+            }
+        }));
     }
 
     mp.join()?;
@@ -1232,47 +1123,7 @@ fn watch_submissions(
         rt.block_on(handle)??;
     }
 
-    return Ok(());
-
-    static JUDGING: Lazy<Regex> = lazy_regex!(r"\A\s*([0-9]{1,3})/([0-9]{1,3})\s*(\S*)\s*\z");
-
-    fn finish(pb: &ProgressBar, verdict: &Verdict, exec_time: &str, memory: &str) {
-        pb.set_style(ProgressStyle::default_bar().template(&format!(
-            "{{prefix}}{{msg:3{}}} │ {} │ {} │",
-            verdict.progress_style(),
-            align_right(exec_time, 8),
-            align_right(memory, 10),
-        )));
-        pb.finish_with_message(&verdict.to_string());
-    }
-
-    fn style(verdict: &str) -> &'static str {
-        // https://atcoder.jp/contests/arc001/glossary
-
-        match verdict.trim() {
-            "AC" => ".green.bold",
-            "CE" | "RE" | "WA" => ".yellow.bold",
-            "MLE" | "TLE" | "OLE" => ".red.bold",
-            "IE" | "WJ" | "WR" => ".bold",
-            s => {
-                if let Some(caps) = JUDGING.captures(s) {
-                    style(&caps[3])
-                } else {
-                    ""
-                }
-            }
-        }
-    }
-}
-
-fn align_left(s: &str, n: usize) -> String {
-    let spaces = n.saturating_sub(s.width());
-    s.chars().chain(itertools::repeat_n(' ', spaces)).collect()
-}
-
-fn align_right(s: &str, n: usize) -> String {
-    let spaces = n.saturating_sub(s.width());
-    itertools::repeat_n(' ', spaces).chain(s.chars()).collect()
+    Ok(())
 }
 
 #[derive(Debug)]
@@ -1307,41 +1158,6 @@ impl ContestStatus {
     }
 }
 
-#[derive(Debug, Serialize)]
-struct SubmissionSummary {
-    submission_time: DateTime<FixedOffset>,
-    task: SubmissionSummaryTask,
-    user: SubmissionSummaryUser,
-    language: String,
-    score: String,
-    code_size: String,
-    status: Verdict,
-    exec_time: Option<String>,
-    memory: Option<String>,
-    detail: Url,
-}
-
-impl SubmissionSummary {
-    fn id(&self) -> &str {
-        self.detail
-            .path_segments()
-            .and_then(Iterator::last)
-            .unwrap_or("")
-    }
-}
-
-#[derive(Debug, Serialize)]
-struct SubmissionSummaryTask {
-    display_name: String,
-    url: Url,
-}
-
-#[derive(Debug, Serialize)]
-struct SubmissionSummaryUser {
-    name: String,
-    url: Url,
-}
-
 #[derive(Debug, PartialEq)]
 enum Verdict {
     Ac,
@@ -1390,31 +1206,37 @@ impl Verdict {
         static JUDGING: Lazy<Regex> = lazy_regex!(r"\A\s*([0-9]{1,3})/([0-9]{1,3})\s*(\S*)\s*\z");
     }
 
-    fn color_spec(&self) -> ColorSpec {
-        return match self {
-            Self::Ac => from_fg_and_bold(Some(Color::Green), true),
-            Self::Ce | Self::Re | Self::Wa => from_fg_and_bold(Some(Color::Yellow), true),
-            Self::Mle | Self::Tle | Self::Ole => from_fg_and_bold(Some(Color::Red), true),
-            Self::Ie | Self::Wj | Self::Wr => from_fg_and_bold(None, true),
-            Self::Judging(_, _, Some(v)) => v.color_spec(),
-            Self::Unknown(_) | Self::Judging(_, _, None) => ColorSpec::new(),
-        };
+    fn to_submission_verdict(&self) -> SubmissionVerdict {
+        let text = self.to_string();
 
-        fn from_fg_and_bold(fg: Option<Color>, bold: bool) -> ColorSpec {
-            let mut spec = ColorSpec::new();
-            spec.set_fg(fg).set_bold(bold);
-            spec
+        match self {
+            Self::Judging(judged, total, verdict) => {
+                let kind = verdict
+                    .as_ref()
+                    .map_or(SubmissionVerdictKind::Judging, |v| v.kind());
+                let progress = SubmissionVerdictProgress {
+                    judged: *judged,
+                    total: Some(*total),
+                };
+                SubmissionVerdict::new(kind, Some(progress), text)
+            }
+            verdict => SubmissionVerdict::new(verdict.kind(), None, text),
         }
     }
 
-    fn progress_style(&self) -> &'static str {
+    fn kind(&self) -> SubmissionVerdictKind {
         match self {
-            Self::Ac => ".green.bold",
-            Self::Ce | Self::Re | Self::Wa => ".yellow.bold",
-            Self::Mle | Self::Tle | Self::Ole => ".red.bold",
-            Self::Ie | Self::Wj | Self::Wr => ".bold",
-            Self::Judging(_, _, Some(v)) => v.progress_style(),
-            Self::Unknown(_) | Self::Judging(_, _, None) => "",
+            Self::Ac => SubmissionVerdictKind::Accepted,
+            Self::Ce => SubmissionVerdictKind::CompilationError,
+            Self::Re => SubmissionVerdictKind::RuntimeError,
+            Self::Wa => SubmissionVerdictKind::WrongAnswer,
+            Self::Mle => SubmissionVerdictKind::MemoryLimitExceeded,
+            Self::Tle => SubmissionVerdictKind::TimeLimitExceeded,
+            Self::Ole => SubmissionVerdictKind::OutputLimitExceeded,
+            Self::Ie => SubmissionVerdictKind::InternalError,
+            Self::Wj | Self::Wr => SubmissionVerdictKind::Waiting,
+            Self::Judging(..) => SubmissionVerdictKind::Judging,
+            Self::Unknown(_) => SubmissionVerdictKind::Other,
         }
     }
 }
@@ -1439,15 +1261,6 @@ impl fmt::Display for Verdict {
     }
 }
 
-impl Serialize for Verdict {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        self.to_string().serialize(serializer)
-    }
-}
-
 #[ext]
 impl Html {
     fn extract_title(&self) -> anyhow::Result<&str> {
@@ -1548,6 +1361,13 @@ impl Html {
                         .exactly_one()
                         .map_err(|_| "Could not extract the timelimit")?;
 
+                    let memorylimit = div
+                        .select(static_selector!(":scope > p"))
+                        .flat_map(|r| r.text())
+                        .flat_map(parse_memorylimit)
+                        .exactly_one()
+                        .ok();
+
                     // In `tasks_print`, there are multiple `#task-statement`s.
                     let samples = div
                         .select(static_selector!(":scope > div[id=\"task-statement\"]"))
@@ -1561,6 +1381,7 @@ impl Html {
                     } else if let Samples::Batch(r#match, samples) = samples {
                         TestSuite::Batch(BatchTestSuite {
                             timelimit: Some(timelimit),
+                            memorylimit,
                             r#match,
                             cases: samples
                                 .into_iter()
@@ -1570,6 +1391,7 @@ impl Html {
                                     r#in: input.into(),
                                     out: Some(output.into()),
                                     timelimit: None,
+                                    memorylimit: None,
                                     r#match: None,
                                 })
                                 .collect(),
@@ -1578,6 +1400,8 @@ impl Html {
                     } else {
                         TestSuite::Interactive(InteractiveTestSuite {
                             timelimit: Some(timelimit),
+                            interactor: None,
+                            cases: vec![],
                         })
                     })
                 })()
@@ -1608,6 +1432,18 @@ impl Html {
             Some(Duration::from_millis(timelimit))
         }
 
+        fn parse_memorylimit(text: &str) -> Option<u64> {
+            // "MB" in AtCoder means MiB.
+            let caps = static_regex!(r"/\D*([0-9]{1,9})\s*([KMG])i?B").captures(text)?;
+            let n = caps[1].parse::<u64>().unwrap();
+            let unit = match &caps[2] {
+                "K" => 1024,
+                "M" => 1024 * 1024,
+                _ => 1024 * 1024 * 1024,
+            };
+            Some(n * unit)
+        }
+
         fn extract_samples(task_statement: ElementRef<'_>) -> Option<Samples> {
             // TODO:
             // - https://atcoder.jp/contests/arc019/tasks/arc019_4 (interactive)
@@ -1869,36 +1705,15 @@ impl Html {
                     DateTime::parse_from_str(submission_time, DATETIME).ok()?
                 };
 
-                let task = {
+                let problem = {
                     let a = tr.select(static_selector!("td > a")).next()?;
 
-                    SubmissionSummaryTask {
+                    SubmissionSummaryProblem {
                         display_name: a.text().next()?.to_owned(),
                         url: BASE_URL.join(a.value().attr("href")?).ok()?,
                     }
                 };
 
-                let user = {
-                    let td = tr.select(static_selector!("td")).nth(2)?;
-
-                    SubmissionSummaryUser {
-                        name: td
-                            .text()
-                            .filter(|s| !s.chars().all(|c| c.is_whitespace()))
-                            .exactly_one()
-                            .ok()?
-                            .to_owned(),
-                        url: BASE_URL
-                            .join(
-                                td.select(static_selector!("a"))
-                                    .next()?
-                                    .value()
-                                    .attr("href")?,
-                            )
-                            .ok()?,
-                    }
-                };
-
                 let language = tr
                     .select(static_selector!("td"))
                     .nth(3)?
@@ -1906,26 +1721,12 @@ impl Html {
                     .next()?
                     .to_owned();
 
-                let score = tr
-                    .select(static_selector!("td"))
-                    .nth(4)?
-                    .text()
-                    .next()?
-                    .to_owned();
-
-                let code_size = tr
-                    .select(static_selector!("td"))
-                    .nth(5)?
-                    .text()
-                    .next()?
-                    .to_owned();
-
-                let status = tr
+                let verdict = tr
                     .select(static_selector!("td > span"))
                     .next()?
                     .text()
                     .next()?;
-                let status = Verdict::new(status);
+                let verdict = Verdict::new(verdict).to_submission_verdict();
 
                 let exec_time = tr
                     .select(static_selector!("td"))
@@ -1939,7 +1740,7 @@ impl Html {
                     .and_then(|r| r.text().exactly_one().ok())
                     .map(ToOwned::to_owned);
 
-                let detail = tr
+                let url = tr
                     .select(static_selector!("td.text-center > a"))
                     .flat_map(|a| {
                         let text = a.text().next()?;
@@ -1952,17 +1753,17 @@ impl Html {
                     })
                     .next()?;
 
+                let id = url.path_segments()?.next_back()?.to_owned();
+
                 submissions.push(SubmissionSummary {
+                    id,
+                    url,
                     submission_time,
-                    task,
-                    user,
+                    problem,
                     language,
-                    score,
-                    code_size,
-                    status,
+                    verdict,
                     exec_time,
                     memory,
-                    detail,
                 })
             }
             Some((submissions, num_pages))
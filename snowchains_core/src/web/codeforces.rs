@@ -1,16 +1,21 @@
 use crate::{
     testsuite::{BatchTestSuite, Match, PartialBatchTestCase, TestSuite},
     web::{
-        codeforces::api::SessionMutExt as _, CookieStorage, Exec, Login, LoginOutcome, Participate,
-        ParticipateOutcome, Platform, ProblemInContest, ProblemsInContest, ResponseExt as _,
-        RetrieveLanguages, RetrieveLanguagesOutcome, RetrieveTestCases, RetrieveTestCasesOutcome,
-        RetrieveTestCasesOutcomeProblem, RetrieveTestCasesOutcomeProblemContest, Session,
-        SessionMut, Shell, Submit, SubmitOutcome,
+        codeforces::api::SessionMutExt as _, submissions, AnsiColored, CookieStorage, Exec, Login,
+        LoginOutcome, Participate, ParticipateOutcome, Platform, ProblemInContest,
+        ProblemsInContest, ResponseExt as _, RetrieveLanguages, RetrieveLanguagesOutcome,
+        RetrieveSubmissionSummaries, RetrieveSubmissionSummariesOutcome, RetrieveTestCases,
+        RetrieveTestCasesOutcome, RetrieveTestCasesOutcomeProblem,
+        RetrieveTestCasesOutcomeProblemContest, Session, SessionMut, Shell, SubmissionSummary,
+        SubmissionSummaryProblem, SubmissionVerdict, SubmissionVerdictKind,
+        SubmissionVerdictProgress, Submit, SubmitOutcome, WatchSubmissions,
     },
 };
 use anyhow::{bail, Context as _};
+use chrono::{TimeZone as _, Utc};
 use easy_ext::ext;
 use indexmap::{indexmap, IndexMap};
+use indicatif::MultiProgress;
 use itertools::Itertools as _;
 use maplit::btreemap;
 use once_cell::sync::Lazy;
@@ -21,6 +26,7 @@ use std::{
     marker::PhantomData,
     time::Duration,
 };
+use tokio::runtime::Runtime;
 use url::Url;
 
 pub fn contest_id_from_url(url: &Url) -> anyhow::Result<u64> {
@@ -45,10 +51,11 @@ impl<'closures> Platform for Codeforces<'closures> {
     type RetrieveTestCasesTargets = ProblemsInContest;
     type RetrieveTestCasesCredentials = CodeforcesRetrieveSampleTestCasesCredentials<'closures>;
     type RetrieveFullTestCasesCredentials = Infallible;
-    type RetrieveSubmissionSummariesTarget = Infallible;
-    type RetrieveSubmissionSummariesCredentials = Infallible;
-    type WatchSubmissionsTarget = Infallible;
-    type WatchSubmissionsCredentials = Infallible;
+    type RetrieveSubmissionSummariesTarget = CodeforcesRetrieveSubmissionSummariesTarget;
+    type RetrieveSubmissionSummariesCredentials =
+        CodeforcesRetrieveSubmissionSummariesCredentials<'closures>;
+    type WatchSubmissionsTarget = CodeforcesWatchSubmissionsTarget;
+    type WatchSubmissionsCredentials = CodeforcesWatchSubmissionsCredentials<'closures>;
     type SubmitTarget = ProblemInContest;
     type SubmitCredentials = CodeforcesSubmitCredentials<'closures>;
 }
@@ -251,13 +258,9 @@ impl<S: Shell> Exec<Submit<Self, S>> for Codeforces<'_> {
             watch_submission,
             cookie_storage,
             timeout,
-            mut shell,
+            shell,
         } = args;
 
-        if watch_submission {
-            shell.warn("`watch_submissions` in Codeforces is not yet supported")?;
-        }
-
         let (contest_id, problem_index) = match target {
             ProblemInContest::Index { contest, problem } => (parse_contest_id(&contest)?, problem),
             ProblemInContest::Url { url } => parse_problem_url(&url)?,
@@ -307,21 +310,113 @@ impl<S: Shell> Exec<Submit<Self, S>> for Codeforces<'_> {
             let submissions =
                 sess.api_contest_status(&api_key, &api_secret, contest_id, &handle, 1, Some(1))?;
 
-            let submission = submissions
-                .get(0)
-                .with_context(|| "Recieved no submission")?;
-
-            let submission_url = url!("/contest/{}/submission/{}", contest_id, submission.id);
+            let summary = submissions
+                .into_iter()
+                .next()
+                .with_context(|| "Recieved no submission")?
+                .into_submission_summary(contest_id);
+
+            if watch_submission {
+                watch_submissions(
+                    sess,
+                    &api_key,
+                    &api_secret,
+                    contest_id,
+                    &handle,
+                    std::slice::from_ref(&summary),
+                )?;
+            }
 
             Ok(SubmitOutcome {
                 problem_screen_name: None,
-                submission_url,
+                submission_url: summary.url,
                 submissions_url,
             })
         }
     }
 }
 
+impl<S: Shell> Exec<RetrieveSubmissionSummaries<Self, S>> for Codeforces<'_> {
+    type Output = RetrieveSubmissionSummariesOutcome;
+
+    fn exec(
+        args: RetrieveSubmissionSummaries<Self, S>,
+    ) -> anyhow::Result<RetrieveSubmissionSummariesOutcome> {
+        let RetrieveSubmissionSummaries {
+            target: CodeforcesRetrieveSubmissionSummariesTarget { contest },
+            credentials:
+                CodeforcesRetrieveSubmissionSummariesCredentials {
+                    username_and_password,
+                    api_key,
+                    api_secret,
+                },
+            cookie_storage,
+            timeout,
+            shell,
+        } = args;
+
+        let contest_id = parse_contest_id(&contest)?;
+
+        let mut sess = Session::new(timeout, Some(cookie_storage), shell)?;
+
+        let (_, handle) = login(&mut sess, username_and_password)?;
+
+        let summaries = sess
+            .api_contest_status(&api_key, &api_secret, contest_id, &handle, 1, None)?
+            .into_iter()
+            .map(|submission| submission.into_submission_summary(contest_id))
+            .collect();
+
+        Ok(RetrieveSubmissionSummariesOutcome { summaries })
+    }
+}
+
+impl<S: Shell> Exec<WatchSubmissions<Self, S>> for Codeforces<'_> {
+    type Output = ();
+
+    fn exec(args: WatchSubmissions<Self, S>) -> anyhow::Result<()> {
+        let WatchSubmissions {
+            target: CodeforcesWatchSubmissionsTarget { contest },
+            credentials:
+                CodeforcesWatchSubmissionsCredentials {
+                    username_and_password,
+                    api_key,
+                    api_secret,
+                },
+            cookie_storage,
+            timeout,
+            mut shell,
+        } = args;
+
+        let contest_id = parse_contest_id(&contest)?;
+
+        let mut sess = Session::new(timeout, Some(cookie_storage), &mut shell)?;
+
+        let (_, _, handle) = participate(&mut sess, username_and_password, contest_id)?;
+
+        let summaries = sess
+            .api_contest_status(
+                &api_key,
+                &api_secret,
+                contest_id,
+                &handle,
+                1,
+                Some(SUBMISSIONS_TO_SHOW),
+            )?
+            .into_iter()
+            .map(|submission| submission.into_submission_summary(contest_id))
+            .collect::<Vec<_>>();
+
+        if summaries.iter().any(|summary| summary.verdict.is_judging()) {
+            watch_submissions(sess, &api_key, &api_secret, contest_id, &handle, &summaries)?;
+        } else {
+            let content = AnsiColored::new(|w| submissions::print_submissions(w, &summaries))?;
+            shell.print_ansi(content.get())?;
+        }
+        Ok(())
+    }
+}
+
 pub struct CodeforcesLoginCredentials<'closures> {
     pub username_and_password: &'closures mut dyn FnMut() -> anyhow::Result<(String, String)>,
 }
@@ -348,6 +443,28 @@ pub struct CodeforcesRetrieveSampleTestCasesCredentials<'closures> {
     pub username_and_password: &'closures mut dyn FnMut() -> anyhow::Result<(String, String)>,
 }
 
+#[derive(Debug)]
+pub struct CodeforcesRetrieveSubmissionSummariesTarget {
+    pub contest: String,
+}
+
+pub struct CodeforcesRetrieveSubmissionSummariesCredentials<'closures> {
+    pub username_and_password: &'closures mut dyn FnMut() -> anyhow::Result<(String, String)>,
+    pub api_key: String,
+    pub api_secret: String,
+}
+
+#[derive(Debug)]
+pub struct CodeforcesWatchSubmissionsTarget {
+    pub contest: String,
+}
+
+pub struct CodeforcesWatchSubmissionsCredentials<'closures> {
+    pub username_and_password: &'closures mut dyn FnMut() -> anyhow::Result<(String, String)>,
+    pub api_key: String,
+    pub api_secret: String,
+}
+
 pub struct CodeforcesSubmitCredentials<'closures> {
     pub username_and_password: &'closures mut dyn FnMut() -> anyhow::Result<(String, String)>,
     pub api_key: String,
@@ -363,7 +480,7 @@ fn parse_contest_id(s: &str) -> anyhow::Result<u64> {
     })
 }
 
-fn parse_problem_url(url: &Url) -> anyhow::Result<(u64, String)> {
+pub(super) fn parse_problem_url(url: &Url) -> anyhow::Result<(u64, String)> {
     if url.domain() != Some("codeforces.com") {
         bail!("wrong domain. expected `codeforces.com`: {}", url);
     }
@@ -464,6 +581,169 @@ fn is_gym(contest_id: u64) -> bool {
     contest_id >= 100_000
 }
 
+/// Same as the number of the rows in a page of `/contest/{}/my`.
+const SUBMISSIONS_TO_SHOW: usize = 50;
+
+/// `contest.status` may be called at most once per 2 seconds.
+const POLLING_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Polls `contest.status` until all of the `summaries` are judged.
+fn watch_submissions(
+    mut sess: impl SessionMut,
+    api_key: &str,
+    api_secret: &str,
+    contest_id: u64,
+    handle: &str,
+    summaries: &[SubmissionSummary],
+) -> anyhow::Result<()> {
+    let rt = Runtime::new()?;
+
+    let mp = MultiProgress::with_draw_target(sess.shell().progress_draw_target());
+
+    let mut pbs = summaries
+        .iter()
+        .zip(submissions::add_progress_bars(&mp, summaries))
+        .filter(|(summary, _)| summary.verdict.is_judging())
+        .map(|(summary, pb)| (summary.id.clone(), pb))
+        .collect::<HashMap<_, _>>();
+
+    let client = sess.async_client().clone();
+    let (api_key, api_secret, handle) =
+        (api_key.to_owned(), api_secret.to_owned(), handle.to_owned());
+
+    let handle = rt.spawn(async move {
+        while !pbs.is_empty() {
+            tokio::time::sleep(POLLING_INTERVAL).await;
+
+            let submissions = match api::api_contest_status_async(
+                &client,
+                &api_key,
+                &api_secret,
+                contest_id,
+                &handle,
+                1,
+                None,
+            )
+            .await
+            {
+                Ok(submissions) => submissions,
+                Err(err) => {
+                    tokio::task::block_in_place(|| {
+                        for pb in pbs.values() {
+                            pb.finish_at_current_pos();
+                        }
+                    });
+                    return Err(err);
+                }
+            };
+
+            tokio::task::block_in_place(|| {
+                for submission in submissions {
+                    let summary = submission.into_submission_summary(contest_id);
+
+                    if summary.verdict.is_judging() {
+                        if let Some(pb) = pbs.get(&summary.id) {
+                            submissions::set_progress(pb, &summary.verdict);
+                        }
+                    } else if let Some(pb) = pbs.remove(&summary.id) {
+                        submissions::finish_progress(
+                            &pb,
+                            &summary.verdict,
+                            summary.exec_time.as_deref(),
+                            summary.memory.as_deref(),
+                        );
+                    }
+                }
+            });
+        }
+        Ok(())
+    });
+
+    mp.join()?;
+    rt.block_on(handle)?
+}
+
+impl api::Submission {
+    fn into_submission_summary(self, contest_id: u64) -> SubmissionSummary {
+        let verdict = self.submission_verdict();
+
+        let (exec_time, memory) = if verdict.is_judging() {
+            (None, None)
+        } else {
+            (
+                Some(format!("{} ms", self.time_consumed_millis)),
+                Some(format!("{} KB", self.memory_consumed_bytes / 1024)),
+            )
+        };
+
+        SubmissionSummary {
+            id: self.id.to_string(),
+            url: url!("/contest/{}/submission/{}", contest_id, self.id),
+            submission_time: Utc
+                .timestamp_opt(self.creation_time_seconds, 0)
+                .single()
+                .expect("should be valid")
+                .into(),
+            problem: SubmissionSummaryProblem {
+                display_name: format!("{} - {}", self.problem.index, self.problem.name),
+                url: url!("/contest/{}/problem/{}", contest_id, self.problem.index),
+            },
+            language: self.programming_language,
+            verdict,
+            exec_time,
+            memory,
+        }
+    }
+
+    /// The text is the same as the submissions page. (e.g. "Running on test 3", "Wrong answer on test 2")
+    fn submission_verdict(&self) -> SubmissionVerdict {
+        use api::SubmissionVerdict as V;
+        use SubmissionVerdictKind as K;
+
+        let on_test = |kind: K, s: &str| {
+            let text = format!("{} on test {}", s, self.passed_test_count + 1);
+            SubmissionVerdict::new(kind, None, text)
+        };
+        let other = |kind: K, s: &str| SubmissionVerdict::new(kind, None, s);
+
+        match &self.verdict {
+            None => other(K::Waiting, "In queue"),
+            Some(V::Testing) => {
+                let progress = SubmissionVerdictProgress {
+                    judged: self.passed_test_count,
+                    total: None,
+                };
+                let text = if self.passed_test_count == 0 {
+                    "Running".to_owned()
+                } else {
+                    format!("Running on test {}", self.passed_test_count + 1)
+                };
+                SubmissionVerdict::new(K::Judging, Some(progress), text)
+            }
+            Some(V::Ok) => other(K::Accepted, "Accepted"),
+            Some(V::WrongAnswer) => on_test(K::WrongAnswer, "Wrong answer"),
+            Some(V::TimeLimitExceeded) => on_test(K::TimeLimitExceeded, "Time limit exceeded"),
+            Some(V::MemoryLimitExceeded) => {
+                on_test(K::MemoryLimitExceeded, "Memory limit exceeded")
+            }
+            Some(V::RuntimeError) => on_test(K::RuntimeError, "Runtime error"),
+            Some(V::IdlenessLimitExceeded) => {
+                on_test(K::IdlenessLimitExceeded, "Idleness limit exceeded")
+            }
+            Some(V::PresentationError) => on_test(K::PresentationError, "Presentation error"),
+            Some(V::CompilationError) => other(K::CompilationError, "Compilation error"),
+            Some(V::Partial) => other(K::Other, "Partial result"),
+            Some(V::Challenged) => other(K::Other, "Hacked"),
+            Some(V::Skipped) => other(K::Other, "Skipped"),
+            Some(V::Failed) => other(K::InternalError, "Judgement failed"),
+            Some(V::SecurityViolated) => other(K::Other, "Security violated"),
+            Some(V::Crashed) => other(K::InternalError, "Denial of judgement"),
+            Some(V::InputPreparationCrashed) => other(K::InternalError, "Input preparation failed"),
+            Some(V::Rejected) => other(K::Other, "Rejected"),
+        }
+    }
+}
+
 #[ext]
 impl Html {
     fn extract_hidden_values(&self, form: &Selector) -> anyhow::Result<HashMap<String, String>> {
@@ -558,6 +838,16 @@ impl Html {
             .ok()
             .with_context(|| "Could not extract the timelimit")?;
 
+        let memorylimit = self
+            .select(static_selector!("#pageContent div.memory-limit"))
+            .flat_map(|r| r.text())
+            .flat_map(|text| {
+                let caps = lazy_regex!(r#"\A([0-9]{1,9}) megabytes?\z"#).captures(text)?;
+                Some(caps[1].parse::<u64>().unwrap() * 1024 * 1024)
+            })
+            .exactly_one()
+            .ok();
+
         let input_file_text = self
             .select(static_selector!("#pageContent div.input-file"))
             .flat_map(|r| r.children())
@@ -611,11 +901,13 @@ impl Html {
                 out: Some(out.into()),
                 r#match: None,
                 timelimit: None,
+                memorylimit: None,
             })
             .collect();
 
         return Ok(TestSuite::Batch(BatchTestSuite {
             timelimit: Some(timelimit),
+            memorylimit,
             r#match,
             cases,
             extend: vec![],
@@ -699,8 +991,8 @@ mod api {
     pub(super) struct Problem {
         /// > String. Usually a letter of a letter, followed by a digit, that represent a problem index in a contest.
         pub(super) index: String,
-        ///// > String. Localized.
-        //pub(super) name: String,
+        /// > String. Localized.
+        pub(super) name: String,
         // __rest: (),
     }
 
@@ -712,14 +1004,20 @@ mod api {
     pub(super) struct Submission {
         /// "Integer."
         pub(super) id: u64,
-        ///// "Integer. Time, when submission was created, in unix-format."
-        //pub(super) creation_time_seconds: i64,
-        ///// "Problem object."
-        //pub(super) problem: Problem,
-        ///// "String."
-        //pub(super) programming_language: String,
-        ///// "Enum: FAILED, OK, PARTIAL, COMPILATION_ERROR, RUNTIME_ERROR, WRONG_ANSWER, PRESENTATION_ERROR, TIME_LIMIT_EXCEEDED, MEMORY_LIMIT_EXCEEDED, IDLENESS_LIMIT_EXCEEDED, SECURITY_VIOLATED, CRASHED, INPUT_PREPARATION_CRASHED, CHALLENGED, SKIPPED, TESTING, REJECTED. Can be absent."
-        //pub(super) verdict: Option<SubmissionVerdict>,
+        /// "Integer. Time, when submission was created, in unix-format."
+        pub(super) creation_time_seconds: i64,
+        /// "Problem object."
+        pub(super) problem: Problem,
+        /// "String."
+        pub(super) programming_language: String,
+        /// "Enum: FAILED, OK, PARTIAL, COMPILATION_ERROR, RUNTIME_ERROR, WRONG_ANSWER, PRESENTATION_ERROR, TIME_LIMIT_EXCEEDED, MEMORY_LIMIT_EXCEEDED, IDLENESS_LIMIT_EXCEEDED, SECURITY_VIOLATED, CRASHED, INPUT_PREPARATION_CRASHED, CHALLENGED, SKIPPED, TESTING, REJECTED. Can be absent."
+        pub(super) verdict: Option<SubmissionVerdict>,
+        /// "Integer. Number of passed tests."
+        pub(super) passed_test_count: u64,
+        /// "Integer. Maximum time in milliseconds, consumed by solution for one test."
+        pub(super) time_consumed_millis: u64,
+        /// "Integer. Maximum memory in bytes, consumed by solution for one test."
+        pub(super) memory_consumed_bytes: u64,
         // __rest: (),
     }
 
@@ -810,90 +1108,273 @@ mod api {
             from: usize,
             count: Option<usize>,
         ) -> anyhow::Result<Vec<Submission>> {
-            let time = SystemTime::now()
-                .duration_since(SystemTime::UNIX_EPOCH)?
-                .as_secs()
-                .to_string();
-
-            let mut url = "https://codeforces.com/api/contest.status"
-                .parse::<Url>()
-                .unwrap();
+            let url = contest_status_url(api_key, api_secret, contest_id, handle, from, count)?;
+            api(self, url)
+        }
+    }
 
-            url.query_pairs_mut()
-                .append_pair("apiKey", api_key)
-                .append_pair("contestId", &contest_id.to_string())
-                .append_pair("count", &count.map(|n| n.to_string()).unwrap_or_default())
-                .append_pair("from", &from.to_string())
-                .append_pair("handle", handle)
-                .append_pair("time", &time);
-
-            let api_sig = {
-                let rand = rand::thread_rng().gen_range(100_000u32..1_000_000);
-                let repr = format!("{}{}#{}", rand, &url.as_str()[26..], api_secret);
-                let digest = Sha512::digest(repr.as_ref());
-                format!("{}{}", rand, hex::encode(digest))
-            };
+    impl<S: SessionMut> SessionMutExt for S {}
 
-            url.query_pairs_mut().append_pair("apiSig", &api_sig);
+    /// Same as `SessionMutExt::api_contest_status` but without printing anything.
+    pub(super) async fn api_contest_status_async(
+        client: &reqwest::Client,
+        api_key: &str,
+        api_secret: &str,
+        contest_id: u64,
+        handle: &str,
+        from: usize,
+        count: Option<usize>,
+    ) -> anyhow::Result<Vec<Submission>> {
+        let url = contest_status_url(api_key, api_secret, contest_id, handle, from, count)?;
+        let res = client.get(url).send().await?;
 
-            api(self, url)
+        if res.status() == 200 {
+            let ApiOk(ok) = res.json().await?;
+            Ok(ok)
+        } else {
+            let ApiErr(msg) = res.json().await?;
+            Err(anyhow!("API error: {:?}", msg))
         }
     }
 
-    impl<S: SessionMut> SessionMutExt for S {}
+    fn contest_status_url(
+        api_key: &str,
+        api_secret: &str,
+        contest_id: u64,
+        handle: &str,
+        from: usize,
+        count: Option<usize>,
+    ) -> anyhow::Result<Url> {
+        let time = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)?
+            .as_secs()
+            .to_string();
+
+        let mut url = "https://codeforces.com/api/contest.status"
+            .parse::<Url>()
+            .unwrap();
+
+        url.query_pairs_mut()
+            .append_pair("apiKey", api_key)
+            .append_pair("contestId", &contest_id.to_string())
+            .append_pair("count", &count.map(|n| n.to_string()).unwrap_or_default())
+            .append_pair("from", &from.to_string())
+            .append_pair("handle", handle)
+            .append_pair("time", &time);
+
+        let api_sig = {
+            let rand = rand::thread_rng().gen_range(100_000u32..1_000_000);
+            let repr = format!("{}{}#{}", rand, &url.as_str()[26..], api_secret);
+            let digest = Sha512::digest(repr.as_ref());
+            format!("{}{}", rand, hex::encode(digest))
+        };
+
+        url.query_pairs_mut().append_pair("apiSig", &api_sig);
+
+        Ok(url)
+    }
 
     fn api<S: SessionMut, T: DeserializeOwned>(mut sess: S, url: Url) -> anyhow::Result<T> {
         let res = sess.get(url).colorize_status_code(&[200], (), ..).send()?;
 
-        return if res.status() == 200 {
+        if res.status() == 200 {
             let ApiOk(ok) = res.json()?;
             Ok(ok)
         } else {
             let ApiErr(msg) = res.json()?;
             Err(anyhow!("API error: {:?}", msg))
-        };
+        }
+    }
 
-        struct ApiOk<T: DeserializeOwned>(T);
-
-        impl<'de, T: DeserializeOwned> Deserialize<'de> for ApiOk<T> {
-            fn deserialize<D: Deserializer<'de>>(
-                deserializer: D,
-            ) -> std::result::Result<Self, D::Error> {
-                #[derive(Deserialize)]
-                struct Repr<E: DeserializeOwned> {
-                    status: String,
-                    #[serde(deserialize_with = "E::deserialize")]
-                    result: E,
-                }
+    struct ApiOk<T: DeserializeOwned>(T);
+
+    impl<'de, T: DeserializeOwned> Deserialize<'de> for ApiOk<T> {
+        fn deserialize<D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> std::result::Result<Self, D::Error> {
+            #[derive(Deserialize)]
+            struct Repr<E: DeserializeOwned> {
+                status: String,
+                #[serde(deserialize_with = "E::deserialize")]
+                result: E,
+            }
 
-                let repr = Repr::<T>::deserialize(deserializer)?;
-                if repr.status == "OK" {
-                    Ok(Self(repr.result))
-                } else {
-                    Err(D::Error::custom("`.status` must be \"OK\""))
-                }
+            let repr = Repr::<T>::deserialize(deserializer)?;
+            if repr.status == "OK" {
+                Ok(Self(repr.result))
+            } else {
+                Err(D::Error::custom("`.status` must be \"OK\""))
             }
         }
+    }
 
-        struct ApiErr(String);
+    struct ApiErr(String);
 
-        impl<'de> Deserialize<'de> for ApiErr {
-            fn deserialize<D: Deserializer<'de>>(
-                deserializer: D,
-            ) -> std::result::Result<Self, D::Error> {
-                #[derive(Deserialize)]
-                struct Repr {
-                    status: String,
-                    comment: String,
-                }
+    impl<'de> Deserialize<'de> for ApiErr {
+        fn deserialize<D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> std::result::Result<Self, D::Error> {
+            #[derive(Deserialize)]
+            struct Repr {
+                status: String,
+                comment: String,
+            }
 
-                let repr = Repr::deserialize(deserializer)?;
-                if repr.status == "FAILED" {
-                    Ok(Self(repr.comment))
-                } else {
-                    Err(D::Error::custom("`.status` must be \"FAILED\""))
-                }
+            let repr = Repr::deserialize(deserializer)?;
+            if repr.status == "FAILED" {
+                Ok(Self(repr.comment))
+            } else {
+                Err(D::Error::custom("`.status` must be \"FAILED\""))
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::api;
+    use crate::web::{SubmissionVerdict, SubmissionVerdictKind, SubmissionVerdictProgress};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn submission_verdict() {
+        use api::SubmissionVerdict as V;
+        use SubmissionVerdictKind as K;
+
+        let verdict = |verdict, passed_test_count| {
+            submission(verdict, passed_test_count).submission_verdict()
+        };
+        let new = |kind, text: &str| SubmissionVerdict::new(kind, None, text);
+
+        assert_eq!(new(K::Waiting, "In queue"), verdict(None, 0));
+        assert_eq!(
+            SubmissionVerdict::new(
+                K::Judging,
+                Some(SubmissionVerdictProgress {
+                    judged: 0,
+                    total: None,
+                }),
+                "Running",
+            ),
+            verdict(Some(V::Testing), 0),
+        );
+        assert_eq!(
+            SubmissionVerdict::new(
+                K::Judging,
+                Some(SubmissionVerdictProgress {
+                    judged: 2,
+                    total: None,
+                }),
+                "Running on test 3",
+            ),
+            verdict(Some(V::Testing), 2),
+        );
+        assert_eq!(new(K::Accepted, "Accepted"), verdict(Some(V::Ok), 10));
+        assert_eq!(
+            new(K::WrongAnswer, "Wrong answer on test 2"),
+            verdict(Some(V::WrongAnswer), 1),
+        );
+        assert_eq!(
+            new(K::TimeLimitExceeded, "Time limit exceeded on test 3"),
+            verdict(Some(V::TimeLimitExceeded), 2),
+        );
+        assert_eq!(
+            new(K::MemoryLimitExceeded, "Memory limit exceeded on test 4"),
+            verdict(Some(V::MemoryLimitExceeded), 3),
+        );
+        assert_eq!(
+            new(K::RuntimeError, "Runtime error on test 1"),
+            verdict(Some(V::RuntimeError), 0),
+        );
+        assert_eq!(
+            new(
+                K::IdlenessLimitExceeded,
+                "Idleness limit exceeded on test 5"
+            ),
+            verdict(Some(V::IdlenessLimitExceeded), 4),
+        );
+        assert_eq!(
+            new(K::PresentationError, "Presentation error on test 6"),
+            verdict(Some(V::PresentationError), 5),
+        );
+        assert_eq!(
+            new(K::CompilationError, "Compilation error"),
+            verdict(Some(V::CompilationError), 0),
+        );
+        assert_eq!(
+            new(K::Other, "Partial result"),
+            verdict(Some(V::Partial), 3)
+        );
+        assert_eq!(new(K::Other, "Hacked"), verdict(Some(V::Challenged), 10));
+        assert_eq!(new(K::Other, "Skipped"), verdict(Some(V::Skipped), 10));
+        assert_eq!(
+            new(K::InternalError, "Judgement failed"),
+            verdict(Some(V::Failed), 0),
+        );
+        assert_eq!(
+            new(K::Other, "Security violated"),
+            verdict(Some(V::SecurityViolated), 0),
+        );
+        assert_eq!(
+            new(K::InternalError, "Denial of judgement"),
+            verdict(Some(V::Crashed), 0),
+        );
+        assert_eq!(
+            new(K::InternalError, "Input preparation failed"),
+            verdict(Some(V::InputPreparationCrashed), 0),
+        );
+        assert_eq!(new(K::Other, "Rejected"), verdict(Some(V::Rejected), 0));
+    }
+
+    #[test]
+    fn into_submission_summary() {
+        let summary = submission(Some(api::SubmissionVerdict::Ok), 10).into_submission_summary(340);
+
+        assert_eq!(
+            (
+                "100000001".to_owned(),
+                "https://codeforces.com/contest/340/submission/100000001".to_owned(),
+                "2020-11-27T14:40:00+00:00".to_owned(),
+                "A - The Wall".to_owned(),
+                "https://codeforces.com/contest/340/problem/A".to_owned(),
+                "Rust 2021".to_owned(),
+                Some("15 ms".to_owned()),
+                Some("2048 KB".to_owned()),
+            ),
+            (
+                summary.id,
+                summary.url.to_string(),
+                summary.submission_time.to_rfc3339(),
+                summary.problem.display_name,
+                summary.problem.url.to_string(),
+                summary.language,
+                summary.exec_time,
+                summary.memory,
+            ),
+        );
+
+        let summary =
+            submission(Some(api::SubmissionVerdict::Testing), 2).into_submission_summary(340);
+
+        assert_eq!((None, None), (summary.exec_time, summary.memory));
+    }
+
+    fn submission(
+        verdict: Option<api::SubmissionVerdict>,
+        passed_test_count: u64,
+    ) -> api::Submission {
+        api::Submission {
+            id: 100_000_001,
+            creation_time_seconds: 1_606_488_000,
+            problem: api::Problem {
+                index: "A".to_owned(),
+                name: "The Wall".to_owned(),
+            },
+            programming_language: "Rust 2021".to_owned(),
+            verdict,
+            passed_test_count,
+            time_consumed_millis: 15,
+            memory_consumed_bytes: 2048 * 1024,
+        }
+    }
+}
@@ -53,29 +53,45 @@ macro_rules! url {
     };
 }
 
+mod aoj;
 mod atcoder;
 mod codeforces;
+mod submissions;
 mod yukicoder;
 
 pub use crate::web::{
+    aoj::{
+        Aoj, AojLoginCredentials, AojRetrieveTestCasesTargets, AojSubmitCredentials,
+        AojSubmitTarget, AojWatchSubmissionsCredentials, AojWatchSubmissionsTarget,
+    },
     atcoder::{
         contest_id_from_url as atcoder_contest_id, Atcoder, AtcoderLoginCredentials,
         AtcoderParticipateCredentials, AtcoderParticipateTarget,
         AtcoderRetrieveFullTestCasesCredentials, AtcoderRetrieveLanguagesCredentials,
         AtcoderRetrieveLanguagesTarget, AtcoderRetrieveSampleTestCasesCredentials,
-        AtcoderRetrieveSubmissionSummariesCredentials, AtcoderRetrieveSubmissionSummariesOutcome,
-        AtcoderRetrieveSubmissionSummariesTarget, AtcoderSubmitCredentials,
-        AtcoderWatchSubmissionsCredentials, AtcoderWatchSubmissionsTarget,
+        AtcoderRetrieveSubmissionSummariesCredentials, AtcoderRetrieveSubmissionSummariesTarget,
+        AtcoderSubmitCredentials, AtcoderWatchSubmissionsCredentials,
+        AtcoderWatchSubmissionsTarget,
     },
     codeforces::{
         contest_id_from_url as codeforces_contest_id, Codeforces, CodeforcesLoginCredentials,
         CodeforcesParticipateCredentials, CodeforcesParticipateTarget,
         CodeforcesRetrieveLanguagesCredentials, CodeforcesRetrieveLanguagesTarget,
-        CodeforcesRetrieveSampleTestCasesCredentials, CodeforcesSubmitCredentials,
+        CodeforcesRetrieveSampleTestCasesCredentials,
+        CodeforcesRetrieveSubmissionSummariesCredentials,
+        CodeforcesRetrieveSubmissionSummariesTarget, CodeforcesSubmitCredentials,
+        CodeforcesWatchSubmissionsCredentials, CodeforcesWatchSubmissionsTarget,
+    },
+    submissions::{
+        SubmissionSummary, SubmissionSummaryProblem, SubmissionVerdict, SubmissionVerdictKind,
+        SubmissionVerdictProgress,
     },
     yukicoder::{
-        Yukicoder, YukicoderRetrieveFullTestCasesCredentials, YukicoderRetrieveTestCasesTargets,
-        YukicoderSubmitCredentials, YukicoderSubmitTarget,
+        Yukicoder, YukicoderRetrieveFullTestCasesCredentials,
+        YukicoderRetrieveSubmissionSummariesCredentials,
+        YukicoderRetrieveSubmissionSummariesTarget, YukicoderRetrieveTestCasesTargets,
+        YukicoderSubmitCredentials, YukicoderSubmitTarget, YukicoderWatchSubmissionsCredentials,
+        YukicoderWatchSubmissionsTarget,
     },
 };
 
@@ -85,6 +101,7 @@ use cookie_store::CookieStore;
 use derivative::Derivative;
 use derive_more::{Display, From};
 use easy_ext::ext;
+use either::Either;
 use fs2::FileExt as _;
 use futures_util::StreamExt as _;
 use indexmap::IndexMap;
@@ -116,7 +133,7 @@ use std::{
     time::Duration,
 };
 use strum::EnumString;
-use termcolor::Ansi;
+use termcolor::{Ansi, WriteColor};
 use tokio::runtime::Runtime;
 use unicode_width::UnicodeWidthStr as _;
 use url::Url;
@@ -156,6 +173,7 @@ pub trait Platform: Sized {
 #[strum(serialize_all = "lowercase")]
 #[serde(rename_all = "PascalCase")]
 pub enum PlatformKind {
+    Aoj,
     Atcoder,
     Codeforces,
     Yukicoder,
@@ -163,10 +181,11 @@ pub enum PlatformKind {
 
 impl PlatformKind {
     pub const KEBAB_CASE_VARIANTS: &'static [&'static str] =
-        &["atcoder", "codeforces", "yukicoder"];
+        &["aoj", "atcoder", "codeforces", "yukicoder"];
 
     pub fn from_url(url: &Url) -> anyhow::Result<Self> {
         match url.domain() {
+            Some("onlinejudge.u-aizu.ac.jp" | "judge.u-aizu.ac.jp") => Ok(Self::Aoj),
             Some("atcoder.jp") => Ok(Self::Atcoder),
             Some("codeforces.com") => Ok(Self::Codeforces),
             Some("yukicoder.me") => Ok(Self::Yukicoder),
@@ -175,8 +194,36 @@ impl PlatformKind {
         }
     }
 
+    /// Extracts the contest ID and the problem index from a URL of a problem.
+    ///
+    /// Problems in AOJ and yukicoder are identified by their IDs and numbers instead of indexes in
+    /// contests.
+    pub fn contest_and_problem_index_from_url(
+        url: &Url,
+    ) -> anyhow::Result<(Self, Option<String>, String)> {
+        match Self::from_url(url)? {
+            Self::Aoj => Ok((Self::Aoj, None, aoj::parse_problem_url(url)?)),
+            Self::Atcoder => {
+                let (contest, index) = atcoder::contest_and_problem_index_from_url(url)?;
+                Ok((Self::Atcoder, Some(contest), index))
+            }
+            Self::Codeforces => {
+                let (contest, index) = codeforces::parse_problem_url(url)?;
+                Ok((Self::Codeforces, Some(contest.to_string()), index))
+            }
+            Self::Yukicoder => match yukicoder::parse_problem_url(url)? {
+                Either::Left(no) => Ok((Self::Yukicoder, None, no.to_string())),
+                Either::Right(_) => bail!(
+                    "Specify a yukicoder problem with `/problems/no/{{no}}` instead of its ID: {}",
+                    url,
+                ),
+            },
+        }
+    }
+
     pub fn to_kebab_case_str(self) -> &'static str {
         match self {
+            Self::Aoj => "aoj",
             Self::Atcoder => "atcoder",
             Self::Codeforces => "codeforces",
             Self::Yukicoder => "yukicoder",
@@ -185,6 +232,7 @@ impl PlatformKind {
 
     pub fn to_pascal_case_str(self) -> &'static str {
         match self {
+            Self::Aoj => "Aoj",
             Self::Atcoder => "Atcoder",
             Self::Codeforces => "Codeforces",
             Self::Yukicoder => "Yukicoder",
@@ -336,6 +384,22 @@ pub struct RetrieveSubmissionSummaries<P: Platform, S: Shell> {
     pub shell: S,
 }
 
+#[non_exhaustive]
+#[derive(Debug, Serialize)]
+pub struct RetrieveSubmissionSummariesOutcome {
+    pub summaries: Vec<SubmissionSummary>,
+}
+
+impl RetrieveSubmissionSummariesOutcome {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("should not fail")
+    }
+
+    pub fn print_pretty(&self, wtr: impl WriteColor) -> io::Result<()> {
+        submissions::print_submissions(wtr, &self.summaries)
+    }
+}
+
 #[derive(Debug, Serialize)]
 pub struct RetrieveTestCasesOutcomeProblemTextFiles {
     pub r#in: String,
@@ -1034,9 +1098,14 @@ fn download_with_progress(
     fn progress_style(template: &str) -> ProgressStyle {
         ProgressStyle::default_bar().template(template)
     }
+}
 
-    fn align_left(s: &str, n: usize) -> String {
-        let spaces = n.saturating_sub(s.width());
-        s.chars().chain(itertools::repeat_n(' ', spaces)).collect()
-    }
+fn align_left(s: &str, n: usize) -> String {
+    let spaces = n.saturating_sub(s.width());
+    s.chars().chain(itertools::repeat_n(' ', spaces)).collect()
+}
+
+fn align_right(s: &str, n: usize) -> String {
+    let spaces = n.saturating_sub(s.width());
+    itertools::repeat_n(' ', spaces).chain(s.chars()).collect()
 }
@@ -0,0 +1,442 @@
+//! Submissions and verdicts common to all the platforms.
+
+use crate::web::{align_left, align_right};
+use chrono::{DateTime, FixedOffset, Local};
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+use serde::Serialize;
+use std::io;
+use termcolor::{Color, ColorSpec, WriteColor};
+use unicode_width::UnicodeWidthStr as _;
+use url::Url;
+
+#[non_exhaustive]
+#[derive(Debug, Clone, Serialize)]
+pub struct SubmissionSummary {
+    pub id: String,
+    pub url: Url,
+    pub submission_time: DateTime<FixedOffset>,
+    pub problem: SubmissionSummaryProblem,
+    pub language: String,
+    pub verdict: SubmissionVerdict,
+    /// e.g. "15 ms"
+    pub exec_time: Option<String>,
+    /// e.g. "3616 KB"
+    pub memory: Option<String>,
+}
+
+#[non_exhaustive]
+#[derive(Debug, Clone, Serialize)]
+pub struct SubmissionSummaryProblem {
+    pub display_name: String,
+    pub url: Url,
+}
+
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SubmissionVerdict {
+    /// While judging, the provisional result if any.
+    pub kind: SubmissionVerdictKind,
+    /// Present while judging.
+    pub progress: Option<SubmissionVerdictProgress>,
+    /// Verdict in the platform's own notation. (e.g. "AC", "3/10 WA", "Wrong answer on test 2")
+    pub text: String,
+}
+
+impl SubmissionVerdict {
+    pub(super) fn new(
+        kind: SubmissionVerdictKind,
+        progress: Option<SubmissionVerdictProgress>,
+        text: impl Into<String>,
+    ) -> Self {
+        Self {
+            kind,
+            progress,
+            text: text.into(),
+        }
+    }
+
+    pub fn is_judging(&self) -> bool {
+        self.kind.is_judging() || self.progress.is_some()
+    }
+
+    /// "AC", "WA", "TLE", ... or `text` for `Other`.
+    pub fn abbreviation(&self) -> &str {
+        self.kind.abbreviation().unwrap_or(&self.text)
+    }
+
+    /// `text` if it tells more than `abbreviation` and `progress`. (e.g. "Wrong answer on test 2")
+    pub fn detail(&self) -> Option<&str> {
+        let tells_more = self.kind != SubmissionVerdictKind::Other
+            && self.progress.is_none()
+            && self.text.contains(|c: char| c.is_ascii_digit());
+        Some(&*self.text).filter(|_| tells_more)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct SubmissionVerdictProgress {
+    /// Number of the judged test cases. For Codeforces, number of the passed ones.
+    pub judged: u64,
+    /// Not known for Codeforces.
+    pub total: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum SubmissionVerdictKind {
+    Waiting,
+    Judging,
+    Accepted,
+    WrongAnswer,
+    TimeLimitExceeded,
+    MemoryLimitExceeded,
+    OutputLimitExceeded,
+    RuntimeError,
+    CompilationError,
+    PresentationError,
+    IdlenessLimitExceeded,
+    InternalError,
+    Other,
+}
+
+impl SubmissionVerdictKind {
+    pub fn is_judging(self) -> bool {
+        matches!(self, Self::Waiting | Self::Judging)
+    }
+
+    fn abbreviation(self) -> Option<&'static str> {
+        match self {
+            Self::Waiting | Self::Judging => Some("WJ"),
+            Self::Accepted => Some("AC"),
+            Self::WrongAnswer => Some("WA"),
+            Self::TimeLimitExceeded => Some("TLE"),
+            Self::MemoryLimitExceeded => Some("MLE"),
+            Self::OutputLimitExceeded => Some("OLE"),
+            Self::RuntimeError => Some("RE"),
+            Self::CompilationError => Some("CE"),
+            Self::PresentationError => Some("PE"),
+            Self::IdlenessLimitExceeded => Some("ILE"),
+            Self::InternalError => Some("IE"),
+            Self::Other => None,
+        }
+    }
+
+    fn color(self) -> (Option<Color>, bool) {
+        match self {
+            Self::Accepted => (Some(Color::Green), true),
+            Self::WrongAnswer
+            | Self::RuntimeError
+            | Self::CompilationError
+            | Self::PresentationError => (Some(Color::Yellow), true),
+            Self::TimeLimitExceeded
+            | Self::MemoryLimitExceeded
+            | Self::OutputLimitExceeded
+            | Self::IdlenessLimitExceeded => (Some(Color::Red), true),
+            Self::Waiting | Self::Judging | Self::InternalError => (None, true),
+            Self::Other => (None, false),
+        }
+    }
+
+    fn color_spec(self) -> ColorSpec {
+        let (fg, bold) = self.color();
+        let mut spec = ColorSpec::new();
+        spec.set_fg(fg).set_bold(bold);
+        spec
+    }
+
+    fn progress_style(self) -> &'static str {
+        match self.color() {
+            (Some(Color::Green), _) => ".green.bold",
+            (Some(Color::Yellow), _) => ".yellow.bold",
+            (Some(Color::Red), _) => ".red.bold",
+            (_, true) => ".bold",
+            (_, false) => "",
+        }
+    }
+}
+
+/// Width of the verdict column. Wider abbreviations (texts of `Other`) overflow.
+const VERDICT_WIDTH: usize = 3;
+
+/// Width of the columns after the verdict. (" 123/456 ▉▉▉▉▉▉▉▉▉▉▉▉▉▉▉" or " │ 15 ms │ 3616 KB")
+const RIGHT_COLUMNS_WIDTH: usize = 24;
+
+pub(super) fn print_submissions(
+    mut wtr: impl WriteColor,
+    summaries: &[SubmissionSummary],
+) -> io::Result<()> {
+    let prefixes = prefixes(summaries);
+
+    for (summary, prefix) in summaries.iter().zip(prefixes) {
+        let SubmissionVerdict { kind, progress, .. } = &summary.verdict;
+
+        write!(wtr, "{}", prefix)?;
+
+        wtr.set_color(&kind.color_spec())?;
+        write!(
+            wtr,
+            "{}",
+            align_left(summary.verdict.abbreviation(), VERDICT_WIDTH),
+        )?;
+        wtr.reset()?;
+
+        match progress {
+            Some(SubmissionVerdictProgress { judged, total }) => {
+                let progress = match total {
+                    Some(total) => format!(" {:>3}/{:>3}", judged, total),
+                    None => format!(" {:>3} passed", judged),
+                };
+                writeln!(wtr, "{} │", align_left(&progress, RIGHT_COLUMNS_WIDTH))?;
+            }
+            None if summary.verdict.is_judging() => {
+                writeln!(wtr, "{} │", align_left("", RIGHT_COLUMNS_WIDTH))?;
+            }
+            None => {
+                write!(
+                    wtr,
+                    " │ {} │ {} │",
+                    align_right(summary.exec_time.as_deref().unwrap_or(""), 8),
+                    align_right(summary.memory.as_deref().unwrap_or(""), 10),
+                )?;
+                if let Some(detail) = summary.verdict.detail() {
+                    write!(wtr, " {}", detail)?;
+                }
+                writeln!(wtr)?;
+            }
+        }
+    }
+
+    wtr.flush()
+}
+
+/// Adds a row for each of the `summaries`. The rows for judged ones are finished immediately.
+pub(super) fn add_progress_bars(
+    mp: &MultiProgress,
+    summaries: &[SubmissionSummary],
+) -> Vec<ProgressBar> {
+    summaries
+        .iter()
+        .zip(prefixes(summaries))
+        .map(|(summary, prefix)| {
+            let pb = mp.add(ProgressBar::new(0));
+            pb.set_prefix(&prefix);
+
+            if summary.verdict.is_judging() {
+                set_progress(&pb, &summary.verdict);
+            } else {
+                finish_progress(
+                    &pb,
+                    &summary.verdict,
+                    summary.exec_time.as_deref(),
+                    summary.memory.as_deref(),
+                );
+            }
+            pb
+        })
+        .collect()
+}
+
+pub(super) fn set_progress(pb: &ProgressBar, verdict: &SubmissionVerdict) {
+    let style = verdict.kind.progress_style();
+
+    let right = match verdict.progress {
+        Some(SubmissionVerdictProgress {
+            total: Some(total), ..
+        }) => {
+            pb.set_length(total);
+            format!(
+                " {{pos:>3{style}}}/{{len:>3{style}}} {{bar:15{style}}}",
+                style = style,
+            )
+        }
+        Some(SubmissionVerdictProgress { total: None, .. }) => format!(
+            " {{pos:>3{style}}} passed{}",
+            " ".repeat(RIGHT_COLUMNS_WIDTH - 11),
+            style = style,
+        ),
+        None => " ".repeat(RIGHT_COLUMNS_WIDTH),
+    };
+
+    pb.set_style(ProgressStyle::default_bar().template(&format!(
+        "{{prefix}}{{msg:{}{}}}{} │",
+        VERDICT_WIDTH, style, right,
+    )));
+
+    if let Some(SubmissionVerdictProgress { judged, .. }) = verdict.progress {
+        pb.set_position(judged);
+    }
+    pb.set_message(verdict.abbreviation());
+}
+
+pub(super) fn finish_progress(
+    pb: &ProgressBar,
+    verdict: &SubmissionVerdict,
+    exec_time: Option<&str>,
+    memory: Option<&str>,
+) {
+    let detail = verdict
+        .detail()
+        .map(|detail| format!(" {}", detail.replace('{', "{{").replace('}', "}}")))
+        .unwrap_or_default();
+
+    pb.set_style(ProgressStyle::default_bar().template(&format!(
+        "{{prefix}}{{msg:{}{}}} │ {} │ {} │{}",
+        VERDICT_WIDTH,
+        verdict.kind.progress_style(),
+        align_right(exec_time.unwrap_or(""), 8),
+        align_right(memory.unwrap_or(""), 10),
+        detail,
+    )));
+    pb.finish_with_message(verdict.abbreviation());
+}
+
+/// "│ {submission time} │ {problem} │ {language} │ "
+fn prefixes(summaries: &[SubmissionSummary]) -> Vec<String> {
+    let problem_max_width = summaries
+        .iter()
+        .map(|s| s.problem.display_name.width())
+        .max()
+        .unwrap_or(0);
+
+    let lang_max_width = summaries
+        .iter()
+        .map(|s| s.language.width())
+        .max()
+        .unwrap_or(0);
+
+    summaries
+        .iter()
+        .map(|summary| {
+            format!(
+                "│ {} │ {} │ {} │ ",
+                summary.submission_time.with_timezone(&Local),
+                align_left(&summary.problem.display_name, problem_max_width),
+                align_left(&summary.language, lang_max_width),
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        SubmissionSummary, SubmissionSummaryProblem, SubmissionVerdict, SubmissionVerdictKind,
+        SubmissionVerdictProgress,
+    };
+    use pretty_assertions::assert_eq;
+    use termcolor::NoColor;
+
+    fn summary(
+        problem: &str,
+        verdict: SubmissionVerdict,
+        exec_time_and_memory: Option<(&str, &str)>,
+    ) -> SubmissionSummary {
+        SubmissionSummary {
+            id: "1".to_owned(),
+            url: "https://atcoder.jp/contests/practice/submissions/1"
+                .parse()
+                .unwrap(),
+            submission_time: "2020-01-01T00:00:00+09:00".parse().unwrap(),
+            problem: SubmissionSummaryProblem {
+                display_name: problem.to_owned(),
+                url: "https://atcoder.jp/contests/practice/tasks/practice_1"
+                    .parse()
+                    .unwrap(),
+            },
+            language: "Rust (1.42.0)".to_owned(),
+            verdict,
+            exec_time: exec_time_and_memory.map(|(t, _)| t.to_owned()),
+            memory: exec_time_and_memory.map(|(_, m)| m.to_owned()),
+        }
+    }
+
+    #[test]
+    fn detail() {
+        use SubmissionVerdictKind as K;
+
+        let detail = |kind, progress, text| {
+            SubmissionVerdict::new(kind, progress, text)
+                .detail()
+                .map(ToOwned::to_owned)
+        };
+        let progress = Some(SubmissionVerdictProgress {
+            judged: 3,
+            total: Some(10),
+        });
+
+        assert_eq!(
+            Some("Wrong answer on test 2".to_owned()),
+            detail(K::WrongAnswer, None, "Wrong answer on test 2"),
+        );
+        assert_eq!(None, detail(K::Accepted, None, "Accepted"));
+        assert_eq!(None, detail(K::WrongAnswer, None, "WA"));
+        assert_eq!(None, detail(K::WrongAnswer, progress, "3/10 WA"));
+        assert_eq!(None, detail(K::Other, None, "Hacked"));
+    }
+
+    #[test]
+    fn print_submissions() {
+        let summaries = &[
+            summary(
+                "A - Welcome to AtCoder",
+                SubmissionVerdict::new(SubmissionVerdictKind::Accepted, None, "AC"),
+                Some(("15 ms", "3616 KB")),
+            ),
+            summary(
+                "B - Interactive Sorting",
+                SubmissionVerdict::new(
+                    SubmissionVerdictKind::WrongAnswer,
+                    Some(SubmissionVerdictProgress {
+                        judged: 3,
+                        total: Some(10),
+                    }),
+                    "3/10 WA",
+                ),
+                None,
+            ),
+            summary(
+                "A - Welcome to AtCoder",
+                SubmissionVerdict::new(
+                    SubmissionVerdictKind::Judging,
+                    Some(SubmissionVerdictProgress {
+                        judged: 2,
+                        total: None,
+                    }),
+                    "Running on test 3",
+                ),
+                None,
+            ),
+            summary(
+                "B - Interactive Sorting",
+                SubmissionVerdict::new(
+                    SubmissionVerdictKind::WrongAnswer,
+                    None,
+                    "Wrong answer on test 2",
+                ),
+                Some(("46 ms", "0 KB")),
+            ),
+        ];
+
+        let mut wtr = NoColor::new(vec![]);
+        super::print_submissions(&mut wtr, summaries).unwrap();
+        let output = String::from_utf8(wtr.into_inner()).unwrap();
+
+        let right_columns = output
+            .lines()
+            .map(|line| line.splitn(5, '│').nth(4).unwrap())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            [
+                " AC  │    15 ms │    3616 KB │",
+                " WA    3/ 10                 │",
+                " WJ    2 passed              │",
+                " WA  │    46 ms │       0 KB │ Wrong answer on test 2",
+            ],
+            &*right_columns,
+        );
+
+        assert!(output.lines().all(|line| line
+            .contains("│ A - Welcome to AtCoder  │ Rust (1.42.0) │")
+            || line.contains("│ B - Interactive Sorting │ Rust (1.42.0) │")));
+    }
+}
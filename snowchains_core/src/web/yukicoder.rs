@@ -4,21 +4,26 @@ use crate::{
         TestSuite,
     },
     web::{
-        yukicoder::api::SessionMutExt as _, Exec, Platform, ResponseExt as _,
-        RetrieveFullTestCases, RetrieveLanguages, RetrieveLanguagesOutcome, RetrieveTestCases,
+        submissions, yukicoder::api::SessionMutExt as _, AnsiColored, Exec, Platform,
+        ResponseExt as _, RetrieveFullTestCases, RetrieveLanguages, RetrieveLanguagesOutcome,
+        RetrieveSubmissionSummaries, RetrieveSubmissionSummariesOutcome, RetrieveTestCases,
         RetrieveTestCasesOutcome, RetrieveTestCasesOutcomeProblem,
         RetrieveTestCasesOutcomeProblemContest, RetrieveTestCasesOutcomeProblemTextFiles, Session,
-        SessionMut, Shell, Submit, SubmitOutcome,
+        SessionMut, Shell, SubmissionSummary, SubmissionSummaryProblem, SubmissionVerdict,
+        SubmissionVerdictKind, SubmissionVerdictProgress, Submit, SubmitOutcome, WatchSubmissions,
     },
 };
 use anyhow::{bail, Context as _};
+use chrono::{FixedOffset, Local, NaiveDateTime, TimeZone as _};
 use easy_ext::ext;
 use either::Either;
 use indexmap::indexmap;
+use indicatif::MultiProgress;
 use itertools::Itertools as _;
 use once_cell::sync::Lazy;
 use scraper::{ElementRef, Html, Node};
 use std::{collections::BTreeSet, convert::Infallible, hash::Hash, time::Duration};
+use tokio::runtime::Runtime;
 use url::Url;
 
 static BASE_URL: Lazy<Url> = lazy_url!("https://yukicoder.me");
@@ -45,10 +50,10 @@ impl Platform for Yukicoder {
     type RetrieveTestCasesTargets = YukicoderRetrieveTestCasesTargets;
     type RetrieveTestCasesCredentials = ();
     type RetrieveFullTestCasesCredentials = YukicoderRetrieveFullTestCasesCredentials;
-    type RetrieveSubmissionSummariesTarget = Infallible;
-    type RetrieveSubmissionSummariesCredentials = Infallible;
-    type WatchSubmissionsTarget = Infallible;
-    type WatchSubmissionsCredentials = Infallible;
+    type RetrieveSubmissionSummariesTarget = YukicoderRetrieveSubmissionSummariesTarget;
+    type RetrieveSubmissionSummariesCredentials = YukicoderRetrieveSubmissionSummariesCredentials;
+    type WatchSubmissionsTarget = YukicoderWatchSubmissionsTarget;
+    type WatchSubmissionsCredentials = YukicoderWatchSubmissionsCredentials;
     type SubmitTarget = YukicoderSubmitTarget;
     type SubmitCredentials = YukicoderSubmitCredentials;
 }
@@ -161,6 +166,88 @@ impl<S: Shell> Exec<RetrieveTestCases<Self, S>> for Yukicoder {
     }
 }
 
+impl<S: Shell> Exec<RetrieveSubmissionSummaries<Self, S>> for Yukicoder {
+    type Output = RetrieveSubmissionSummariesOutcome;
+
+    fn exec(
+        args: RetrieveSubmissionSummaries<Self, S>,
+    ) -> anyhow::Result<RetrieveSubmissionSummariesOutcome> {
+        let RetrieveSubmissionSummaries {
+            target,
+            credentials: YukicoderRetrieveSubmissionSummariesCredentials { username },
+            cookie_storage: (),
+            timeout,
+            shell,
+        } = args;
+
+        let submissions_url = match &target {
+            YukicoderRetrieveSubmissionSummariesTarget::Contest(contest_id) => {
+                submissions_url(Either::Left(contest_id))?
+            }
+            YukicoderRetrieveSubmissionSummariesTarget::ProblemNo(problem_no) => {
+                submissions_url(Either::Right(problem_no))?
+            }
+        };
+
+        let mut sess = Session::new(timeout, None, shell)?;
+
+        let mut summaries = vec![];
+
+        for page in 1.. {
+            if page > MAX_SUBMISSIONS_PAGES {
+                sess.shell().warn(format!(
+                    "Stopped at page {} of {}. Older submissions are not retrieved",
+                    MAX_SUBMISSIONS_PAGES, submissions_url,
+                ))?;
+                break;
+            }
+
+            match retrieve_submissions_page(&mut sess, &submissions_url, page, &username)? {
+                Some(extend) => summaries.extend(extend),
+                None => break,
+            }
+        }
+
+        Ok(RetrieveSubmissionSummariesOutcome { summaries })
+    }
+}
+
+impl<S: Shell> Exec<WatchSubmissions<Self, S>> for Yukicoder {
+    type Output = ();
+
+    fn exec(args: WatchSubmissions<Self, S>) -> anyhow::Result<()> {
+        let WatchSubmissions {
+            target,
+            credentials: YukicoderWatchSubmissionsCredentials { username },
+            cookie_storage: (),
+            timeout,
+            mut shell,
+        } = args;
+
+        let submissions_url = match &target {
+            YukicoderWatchSubmissionsTarget::Contest(contest_id) => {
+                submissions_url(Either::Left(contest_id))?
+            }
+            YukicoderWatchSubmissionsTarget::ProblemNo(problem_no) => {
+                submissions_url(Either::Right(problem_no))?
+            }
+        };
+
+        let mut sess = Session::new(timeout, None, &mut shell)?;
+
+        let summaries = retrieve_submissions_page(&mut sess, &submissions_url, 1, &username)?
+            .unwrap_or_default();
+
+        if summaries.iter().any(|summary| summary.verdict.is_judging()) {
+            watch_submissions(sess, &summaries)?;
+        } else {
+            let content = AnsiColored::new(|w| submissions::print_submissions(w, &summaries))?;
+            shell.print_ansi(content.get())?;
+        }
+        Ok(())
+    }
+}
+
 impl<S: Shell> Exec<Submit<Self, S>> for Yukicoder {
     type Output = SubmitOutcome;
 
@@ -173,13 +260,9 @@ impl<S: Shell> Exec<Submit<Self, S>> for Yukicoder {
             watch_submission,
             cookie_storage: (),
             timeout,
-            mut shell,
+            shell,
         } = args;
 
-        if watch_submission {
-            shell.warn("`watch_submissions` in yukicoder is not yet supported")?;
-        }
-
         let mut sess = Session::new(timeout, None, shell)?;
 
         let problem_id = match target.parse()? {
@@ -218,11 +301,39 @@ impl<S: Shell> Exec<Submit<Self, S>> for Yukicoder {
             language_id.as_ref(),
             code.as_ref(),
         )? {
-            Ok(submission_id) => Ok(SubmitOutcome {
-                problem_screen_name: Some(problem_id.to_string()),
-                submission_url: url!("/submissions/{}", submission_id),
-                submissions_url: url!("/problems/{}/submissions?my_submission=enabled", problem_id),
-            }),
+            Ok(submission_id) => {
+                let submission_url = url!("/submissions/{}", submission_id);
+
+                if watch_submission {
+                    let api::Problem { no, title, .. } =
+                        sess.get_problem_by_problem_id(problem_id)?;
+
+                    let summary = SubmissionSummary {
+                        id: submission_id.to_string(),
+                        url: submission_url.clone(),
+                        submission_time: Local::now().into(),
+                        problem: SubmissionSummaryProblem {
+                            display_name: format!("No.{} {}", no, title),
+                            url: url!("/problems/no/{}", no),
+                        },
+                        language: language_id.clone(),
+                        verdict: submission_verdict("WJ", None),
+                        exec_time: None,
+                        memory: None,
+                    };
+
+                    watch_submissions(&mut sess, &[summary])?;
+                }
+
+                Ok(SubmitOutcome {
+                    problem_screen_name: Some(problem_id.to_string()),
+                    submission_url,
+                    submissions_url: url!(
+                        "/problems/{}/submissions?my_submission=enabled",
+                        problem_id,
+                    ),
+                })
+            }
             Err((status_code, message)) => {
                 bail!("Submission rejected: ({}, {:?})", status_code, message);
             }
@@ -271,6 +382,29 @@ impl YukicoderSubmitTarget {
     }
 }
 
+#[derive(Debug)]
+pub enum YukicoderRetrieveSubmissionSummariesTarget {
+    Contest(String),
+    ProblemNo(String),
+}
+
+#[derive(Debug)]
+pub struct YukicoderRetrieveSubmissionSummariesCredentials {
+    pub username: String,
+}
+
+#[derive(Debug)]
+pub enum YukicoderWatchSubmissionsTarget {
+    Contest(String),
+    ProblemNo(String),
+}
+
+/// No API key is needed since the submissions are watched through the public pages.
+#[derive(Debug)]
+pub struct YukicoderWatchSubmissionsCredentials {
+    pub username: String,
+}
+
 #[derive(Debug)]
 pub struct YukicoderSubmitCredentials {
     pub api_key: String,
@@ -294,7 +428,7 @@ fn parse_contest_id(s: &str) -> anyhow::Result<u64> {
     })
 }
 
-fn parse_problem_url(url: &Url) -> anyhow::Result<Either<u64, u64>> {
+pub(super) fn parse_problem_url(url: &Url) -> anyhow::Result<Either<u64, u64>> {
     if url.domain() != Some("yukicoder.me") {
         bail!("wrong domain. expected `yukicoder.me`: {}", url);
     }
@@ -312,6 +446,162 @@ fn parse_problem_url(url: &Url) -> anyhow::Result<Either<u64, u64>> {
     }
 }
 
+/// `/contests/{}/submissions` or `/problems/no/{}/submissions`.
+fn submissions_url(contest_id_or_problem_no: Either<&str, &str>) -> anyhow::Result<Url> {
+    Ok(match contest_id_or_problem_no {
+        Either::Left(contest_id) => url!("/contests/{}/submissions", parse_contest_id(contest_id)?),
+        Either::Right(problem_no) => {
+            url!("/problems/no/{}/submissions", parse_problem_no(problem_no)?)
+        }
+    })
+}
+
+/// The submissions pages come newest first, and a large contest has thousands of them.
+const MAX_SUBMISSIONS_PAGES: u32 = 10;
+
+/// Returns `None` if the page is empty.
+///
+/// The pages list everyone's submissions. Since we cannot log in to yukicoder, the rows are filtered
+/// by the submitter.
+fn retrieve_submissions_page(
+    mut sess: impl SessionMut,
+    submissions_url: &Url,
+    page: u32,
+    username: &str,
+) -> anyhow::Result<Option<Vec<SubmissionSummary>>> {
+    let mut url = submissions_url.clone();
+    url.query_pairs_mut().append_pair("page", &page.to_string());
+
+    let rows = sess
+        .get(url)
+        .colorize_status_code(&[200], (), ..)
+        .send()?
+        .ensure_status(&[200])?
+        .html()?
+        .extract_submissions()?;
+
+    if rows.is_empty() {
+        return Ok(None);
+    }
+
+    Ok(Some(
+        rows.into_iter()
+            .filter(|(submitter, _)| submitter == username)
+            .map(|(_, summary)| summary)
+            .collect(),
+    ))
+}
+
+/// Polls `/submissions/{}` of each of the judging `summaries` until it is judged.
+fn watch_submissions(
+    mut sess: impl SessionMut,
+    summaries: &[SubmissionSummary],
+) -> anyhow::Result<()> {
+    let rt = Runtime::new()?;
+    let mut handles = vec![];
+
+    let mp = MultiProgress::with_draw_target(sess.shell().progress_draw_target());
+
+    let pbs = submissions::add_progress_bars(&mp, summaries);
+
+    for (summary, pb) in summaries.iter().zip(pbs) {
+        if !summary.verdict.is_judging() {
+            continue;
+        }
+
+        let client = sess.async_client().clone();
+        let url = summary.url.clone();
+
+        handles.push(rt.spawn(async move {
+            let finish_pb = || tokio::task::block_in_place(|| pb.finish_at_current_pos());
+
+            macro_rules! trap(($result:expr $(,)?) => {
+                match $result {
+                    Ok(ok) => ok,
+                    Err(err) => {
+                        finish_pb();
+                        return Err(err.into());
+                    }
+                }
+            });
+
+            loop {
+                let res = trap!(client.get(url.clone()).send().await);
+                let res = trap!(res.error_for_status());
+                let text = trap!(res.text().await);
+
+                let SubmissionProgress {
+                    verdict,
+                    exec_time,
+                    memory,
+                } = trap!(tokio::task::block_in_place(|| {
+                    Html::parse_document(&text).extract_submission_progress()
+                }));
+
+                if verdict.is_judging() {
+                    tokio::task::block_in_place(|| submissions::set_progress(&pb, &verdict));
+
+                    tokio::time::sleep(POLLING_INTERVAL).await;
+                } else {
+                    tokio::task::block_in_place(|| {
+                        submissions::finish_progress(
+                            &pb,
+                            &verdict,
+                            exec_time.as_deref(),
+                            memory.as_deref(),
+                        );
+                    });
+                    break Result::<(), anyhow::Error>::Ok(());
+                }
+            }
+        }));
+    }
+
+    mp.join()?;
+
+    for handle in handles {
+        rt.block_on(handle)??;
+    }
+
+    Ok(())
+}
+
+const POLLING_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Converts a verdict shown in the pages. (e.g. "AC", "WA", "WJ")
+fn submission_verdict(
+    text: &str,
+    progress: Option<SubmissionVerdictProgress>,
+) -> SubmissionVerdict {
+    use SubmissionVerdictKind as K;
+
+    let kind = match text {
+        "AC" => K::Accepted,
+        "WA" => K::WrongAnswer,
+        "TLE" => K::TimeLimitExceeded,
+        "MLE" => K::MemoryLimitExceeded,
+        "OLE" => K::OutputLimitExceeded,
+        "RE" => K::RuntimeError,
+        "CE" => K::CompilationError,
+        "IE" => K::InternalError,
+        "" | "WJ" => K::Waiting,
+        "Judge" | "Judging" => K::Judging,
+        text if text.contains("判定") => K::Waiting,
+        _ => K::Other,
+    };
+
+    SubmissionVerdict::new(kind, progress, text)
+}
+
+#[derive(Debug)]
+struct SubmissionProgress {
+    verdict: SubmissionVerdict,
+    /// Maximum of the test cases.
+    exec_time: Option<String>,
+    /// Maximum of the test cases.
+    memory: Option<String>,
+}
+
 fn retrieve_samples(
     mut sess: impl SessionMut,
     targets: YukicoderRetrieveTestCasesTargets,
@@ -454,10 +744,10 @@ fn retrieve_samples(
     }
 }
 
-#[ext]
+#[ext(HtmlExt)]
 impl Html {
     fn extract_samples(&self) -> anyhow::Result<TestSuite> {
-        let (timelimit, kind) = self
+        let (timelimit, memorylimit, kind) = self
             .select(static_selector!("#content > div"))
             .flat_map(|r| r.text())
             .find_map(|text| {
@@ -470,6 +760,10 @@ impl Html {
                     Duration::from_millis(1000 * secs + millis)
                 };
 
+                let memorylimit = static_regex!(r"メモリ制限\s*:\s*([0-9]{1,6})\s*MB")
+                    .captures(text)
+                    .map(|caps| caps[1].parse::<u64>().unwrap() * 1024 * 1024);
+
                 let kind = if text.contains("標準ジャッジ問題") {
                     Kind::Regular
                 } else if text.contains("スペシャルジャッジ問題") {
@@ -494,7 +788,7 @@ impl Html {
                     return None;
                 };
 
-                Some((timelimit, kind))
+                Some((timelimit, memorylimit, kind))
             })
             .with_context(|| "Could not parse the page")?;
 
@@ -515,6 +809,7 @@ impl Html {
 
                 let mut test_suite = BatchTestSuite {
                     timelimit: Some(timelimit),
+                    memorylimit,
                     r#match,
                     cases: vec![],
                     extend: vec![],
@@ -540,6 +835,7 @@ impl Html {
                                 _ => None,
                             },
                             timelimit: None,
+                            memorylimit: None,
                             r#match: None,
                         });
                     } else {
@@ -551,6 +847,8 @@ impl Html {
             }
             Kind::Reactive => TestSuite::Interactive(InteractiveTestSuite {
                 timelimit: Some(timelimit),
+                interactor: None,
+                cases: vec![],
             }),
         };
 
@@ -581,6 +879,168 @@ impl Html {
             }
         }
     }
+
+    /// Extracts the rows of `/contests/{}/submissions` or `/problems/no/{}/submissions` with their submitters.
+    fn extract_submissions(&self) -> anyhow::Result<Vec<(String, SubmissionSummary)>> {
+        (|| {
+            let table = self.select(static_selector!("table")).find(|table| {
+                table
+                    .select(static_selector!("thead > tr > th"))
+                    .any(|th| th.text().collect::<String>().trim() == "提出者")
+            })?;
+
+            let headers = table
+                .select(static_selector!("thead > tr > th"))
+                .map(|th| th.text().collect::<String>().trim().to_owned())
+                .collect::<Vec<_>>();
+            let column = |name: &str| headers.iter().position(|h| h == name);
+
+            let submission_time_column = column("提出日時")?;
+            let submitter_column = column("提出者")?;
+            let problem_column = column("問題")?;
+            let language_column = column("言語")?;
+            let verdict_column = column("結果")?;
+            let exec_time_column = column("実行時間");
+
+            let mut submissions = vec![];
+
+            for tr in table.select(static_selector!("tbody > tr")) {
+                let tds = tr.select(static_selector!("td")).collect::<Vec<_>>();
+
+                let text = |i: usize| -> _ {
+                    Some(tds.get(i)?.text().collect::<String>().trim().to_owned())
+                };
+
+                let url = tr
+                    .select(static_selector!("a"))
+                    .flat_map(|a| a.value().attr("href"))
+                    .find(|href| static_regex!(r"\A/submissions/[0-9]+\z").is_match(href))?;
+                let url = BASE_URL.join(url).ok()?;
+                let id = url.path_segments()?.next_back()?.to_owned();
+
+                // Displayed in JST.
+                let submission_time = FixedOffset::east_opt(9 * 60 * 60)?
+                    .from_local_datetime(
+                        &NaiveDateTime::parse_from_str(&text(submission_time_column)?, "%F %T")
+                            .ok()?,
+                    )
+                    .single()?;
+
+                let problem = {
+                    let a = tds
+                        .get(problem_column)?
+                        .select(static_selector!("a"))
+                        .next()?;
+
+                    SubmissionSummaryProblem {
+                        display_name: a.text().collect::<String>().trim().to_owned(),
+                        url: BASE_URL.join(a.value().attr("href")?).ok()?,
+                    }
+                };
+
+                let exec_time = exec_time_column.and_then(text).filter(|s| !s.is_empty());
+
+                submissions.push((
+                    text(submitter_column)?,
+                    SubmissionSummary {
+                        id,
+                        url,
+                        submission_time,
+                        problem,
+                        language: text(language_column)?,
+                        verdict: submission_verdict(&text(verdict_column)?, None),
+                        exec_time,
+                        memory: None,
+                    },
+                ));
+            }
+            Some(submissions)
+        })()
+        .with_context(|| "Could not parse the submissions page")
+    }
+
+    /// Extracts the verdict and the results of the test cases from `/submissions/{}`.
+    fn extract_submission_progress(&self) -> anyhow::Result<SubmissionProgress> {
+        (|| {
+            let verdict = self.select(static_selector!("tr")).find_map(|tr| {
+                let cells = tr.select(static_selector!("th, td")).collect::<Vec<_>>();
+                match &*cells {
+                    [header, value, ..] if header.text().collect::<String>().trim() == "結果" => {
+                        Some(value.text().collect::<String>().trim().to_owned())
+                    }
+                    _ => None,
+                }
+            })?;
+
+            let mut num_judged = 0;
+            let mut num_test_cases = 0;
+            let mut exec_time = None;
+            let mut memory = None;
+
+            let table = self.select(static_selector!("table")).find(|table| {
+                table
+                    .select(static_selector!("thead > tr > th"))
+                    .any(|th| th.text().collect::<String>().contains("テストケース"))
+            });
+
+            if let Some(table) = table {
+                let headers = table
+                    .select(static_selector!("thead > tr > th"))
+                    .map(|th| th.text().collect::<String>().trim().to_owned())
+                    .collect::<Vec<_>>();
+
+                let verdict_column = headers.iter().position(|h| h == "結果")?;
+                let exec_time_column = headers.iter().position(|h| h.contains("実行時間"));
+                let memory_column = headers
+                    .iter()
+                    .position(|h| h.contains("メモリ") && !h.contains("実行時間"));
+
+                for tr in table.select(static_selector!("tbody > tr")) {
+                    let tds = tr.select(static_selector!("td")).collect::<Vec<_>>();
+
+                    let number = |i: Option<usize>| -> Option<u64> {
+                        tds.get(i?)?
+                            .text()
+                            .collect::<String>()
+                            .chars()
+                            .take_while(|&c| c.is_ascii_digit() || c == ',')
+                            .filter(char::is_ascii_digit)
+                            .collect::<String>()
+                            .parse()
+                            .ok()
+                    };
+
+                    num_test_cases += 1;
+
+                    let case_verdict = tds.get(verdict_column)?.text().collect::<String>();
+                    if !submission_verdict(case_verdict.trim(), None).is_judging() {
+                        num_judged += 1;
+                    }
+
+                    exec_time = exec_time.max(number(exec_time_column));
+                    memory = memory.max(number(memory_column));
+                }
+            }
+
+            let progress = if num_test_cases > 0 && submission_verdict(&verdict, None).is_judging()
+            {
+                Some(SubmissionVerdictProgress {
+                    judged: num_judged,
+                    total: Some(num_test_cases),
+                })
+            } else {
+                None
+            };
+            let verdict = submission_verdict(&verdict, progress);
+
+            Some(SubmissionProgress {
+                verdict,
+                exec_time: exec_time.map(|t| format!("{} ms", t)),
+                memory: memory.map(|m| format!("{} KB", m)),
+            })
+        })()
+        .with_context(|| "Could not parse the submission page")
+    }
 }
 
 mod api {
@@ -832,3 +1292,130 @@ mod api {
         pub(super) problem_id_list: Vec<u64>,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{HtmlExt as _, SubmissionProgress};
+    use crate::web::{SubmissionVerdict, SubmissionVerdictKind, SubmissionVerdictProgress};
+    use pretty_assertions::assert_eq;
+    use scraper::Html;
+
+    #[test]
+    fn extract_submissions() {
+        let html = Html::parse_document(include_str!(
+            "../../tests/fixtures/yukicoder/problem-no-1-submissions.html"
+        ));
+
+        let submissions = html
+            .extract_submissions()
+            .unwrap()
+            .into_iter()
+            .map(|(submitter, summary)| {
+                (
+                    submitter,
+                    summary.id,
+                    summary.url.to_string(),
+                    summary.submission_time.to_rfc3339(),
+                    summary.problem.display_name,
+                    summary.problem.url.to_string(),
+                    summary.language,
+                    summary.verdict,
+                    summary.exec_time,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            [
+                (
+                    "bob".to_owned(),
+                    "700003".to_owned(),
+                    "https://yukicoder.me/submissions/700003".to_owned(),
+                    "2021-10-01T12:34:56+09:00".to_owned(),
+                    "No.1 道のショートカット".to_owned(),
+                    "https://yukicoder.me/problems/no/1".to_owned(),
+                    "C++17 (gcc 11.2.0 + boost 1.77.0)".to_owned(),
+                    SubmissionVerdict::new(SubmissionVerdictKind::WrongAnswer, None, "WA"),
+                    Some("5 ms".to_owned()),
+                ),
+                (
+                    "alice".to_owned(),
+                    "700002".to_owned(),
+                    "https://yukicoder.me/submissions/700002".to_owned(),
+                    "2021-10-01T12:30:00+09:00".to_owned(),
+                    "No.1 道のショートカット".to_owned(),
+                    "https://yukicoder.me/problems/no/1".to_owned(),
+                    "Rust (1.55.0)".to_owned(),
+                    SubmissionVerdict::new(SubmissionVerdictKind::Waiting, None, "WJ"),
+                    None,
+                ),
+                (
+                    "alice".to_owned(),
+                    "700001".to_owned(),
+                    "https://yukicoder.me/submissions/700001".to_owned(),
+                    "2021-10-01T00:00:00+09:00".to_owned(),
+                    "No.1 道のショートカット".to_owned(),
+                    "https://yukicoder.me/problems/no/1".to_owned(),
+                    "Rust (1.55.0)".to_owned(),
+                    SubmissionVerdict::new(SubmissionVerdictKind::Accepted, None, "AC"),
+                    Some("12 ms".to_owned()),
+                ),
+            ],
+            &*submissions,
+        );
+    }
+
+    #[test]
+    fn extract_submissions_from_empty_page() {
+        let html = Html::parse_document(
+            r#"<table><thead><tr><th>提出日時</th><th>提出者</th><th>問題</th><th>言語</th><th>結果</th></tr></thead><tbody></tbody></table>"#,
+        );
+        assert!(html.extract_submissions().unwrap().is_empty());
+
+        let html = Html::parse_document("<p>Not Found</p>");
+        assert!(html.extract_submissions().is_err());
+    }
+
+    #[test]
+    fn extract_submission_progress() {
+        let extract = |html| {
+            let SubmissionProgress {
+                verdict,
+                exec_time,
+                memory,
+            } = Html::parse_document(html)
+                .extract_submission_progress()
+                .unwrap();
+            (verdict, exec_time, memory)
+        };
+
+        assert_eq!(
+            (
+                SubmissionVerdict::new(
+                    SubmissionVerdictKind::Waiting,
+                    Some(SubmissionVerdictProgress {
+                        judged: 2,
+                        total: Some(4),
+                    }),
+                    "判定中",
+                ),
+                Some("3 ms".to_owned()),
+                Some("2048 KB".to_owned()),
+            ),
+            extract(include_str!(
+                "../../tests/fixtures/yukicoder/submission-700002-judging.html"
+            )),
+        );
+
+        assert_eq!(
+            (
+                SubmissionVerdict::new(SubmissionVerdictKind::WrongAnswer, None, "WA"),
+                Some("15 ms".to_owned()),
+                Some("3456 KB".to_owned()),
+            ),
+            extract(include_str!(
+                "../../tests/fixtures/yukicoder/submission-700002-judged.html"
+            )),
+        );
+    }
+}
@@ -0,0 +1,91 @@
+use cookie_store::CookieStore;
+use insta::{assert_debug_snapshot, assert_json_snapshot};
+use maplit::btreeset;
+use reqwest::{Method, StatusCode};
+use snowchains_core::web::{
+    Aoj, AojRetrieveTestCasesTargets, CookieStorage, RetrieveTestCases, StatusCodeColor,
+};
+use std::{
+    fmt,
+    io::{self, Read as _},
+    time::Duration,
+};
+use url::Url;
+
+#[test]
+fn itp1_1_a() -> anyhow::Result<()> {
+    test("ITP1_1_A")
+}
+
+#[test]
+fn dpl_1_b() -> anyhow::Result<()> {
+    test("DPL_1_B")
+}
+
+fn test(problem_id: &str) -> anyhow::Result<()> {
+    const TIMEOUT: Option<Duration> = Some(Duration::from_secs(30));
+
+    struct Shell<'a>(&'a mut Vec<Message>);
+
+    impl snowchains_core::web::Shell for Shell<'_> {
+        fn print_ansi(&mut self, message: &[u8]) -> io::Result<()> {
+            fn from_utf8(bytes: impl AsRef<[u8]>) -> io::Result<String> {
+                let mut ret = "".to_owned();
+                bytes.as_ref().read_to_string(&mut ret)?;
+                Ok(ret)
+            }
+
+            self.0.push(Message::PrintAnsi(from_utf8(message)?));
+            Ok(())
+        }
+
+        fn warn<T: fmt::Display>(&mut self, message: T) -> io::Result<()> {
+            self.0.push(Message::Warn(message.to_string()));
+            Ok(())
+        }
+
+        fn on_request(&mut self, req: &reqwest::blocking::Request) -> io::Result<()> {
+            self.0
+                .push(Message::OnRequest(req.method().clone(), req.url().clone()));
+            Ok(())
+        }
+
+        fn on_response(
+            &mut self,
+            res: &reqwest::blocking::Response,
+            status_code_color: StatusCodeColor,
+        ) -> std::io::Result<()> {
+            self.0
+                .push(Message::OnResponse(res.status(), status_code_color));
+            Ok(())
+        }
+    }
+
+    #[derive(Debug)]
+    enum Message {
+        PrintAnsi(String),
+        Warn(String),
+        OnRequest(Method, Url),
+        OnResponse(StatusCode, StatusCodeColor),
+    }
+
+    let mut messages = vec![];
+
+    let outcome = Aoj::exec(RetrieveTestCases {
+        targets: AojRetrieveTestCasesTargets::ProblemIds(btreeset!(problem_id.to_owned())),
+        credentials: (),
+        full: None,
+        cookie_storage: CookieStorage {
+            cookie_store: CookieStore::default(),
+            on_update: Box::new(|_| Ok(())),
+        },
+        timeout: TIMEOUT,
+        shell: Shell(&mut messages),
+    })?;
+
+    let problem_id = problem_id.to_lowercase();
+
+    assert_json_snapshot!(format!("{}_samples_outcome", problem_id), outcome);
+    assert_debug_snapshot!(format!("{}_samples_messages", problem_id), messages);
+    Ok(())
+}
@@ -0,0 +1,152 @@
+use crate::config;
+use anyhow::Context as _;
+use serde::Serialize;
+use snowchains_core::web::PlatformKind;
+use std::path::PathBuf;
+use structopt::StructOpt;
+use strum::VariantNames as _;
+use termcolor::WriteColor;
+
+#[derive(StructOpt, Debug)]
+pub struct OptConfigShow {
+    /// Prints JSON data instead of YAML
+    #[structopt(long)]
+    pub json: bool,
+
+    /// Evaluates `languages` in `Release` mode
+    #[structopt(long)]
+    pub release: bool,
+
+    /// Path to `snowchains.dhall` or `snowchains.yaml`
+    #[structopt(long)]
+    pub config: Option<PathBuf>,
+
+    /// Coloring
+    #[structopt(
+        long,
+        possible_values(crate::ColorChoice::VARIANTS),
+        default_value("auto")
+    )]
+    pub color: crate::ColorChoice,
+
+    /// Platform
+    #[structopt(
+        short,
+        long,
+        value_name("SERVICE"),
+        possible_values(PlatformKind::KEBAB_CASE_VARIANTS)
+    )]
+    pub service: Option<PlatformKind>,
+
+    /// Contest ID
+    #[structopt(short, long, value_name("STRING"))]
+    pub contest: Option<String>,
+
+    /// Problem index (e.g. "a", "b", "c")
+    #[structopt(short, long, value_name("STRING"))]
+    pub problem: Option<String>,
+
+    /// Language name
+    #[structopt(short, long, value_name("STRING"))]
+    pub language: Option<String>,
+
+    /// Path to detect the target from, instead of the current directory
+    pub path: Option<PathBuf>,
+}
+
+#[derive(Debug, Serialize)]
+struct Outcome {
+    config: String,
+    detected: config::Detected,
+    target: Option<config::Target>,
+    language: Option<OutcomeLanguage>,
+}
+
+#[derive(Debug, Serialize)]
+struct OutcomeLanguage {
+    name: String,
+    #[serde(flatten)]
+    value: config::Language,
+}
+
+pub(crate) fn run(
+    opt: OptConfigShow,
+    ctx: crate::Context<impl Sized, impl WriteColor, impl WriteColor>,
+) -> anyhow::Result<()> {
+    let OptConfigShow {
+        json,
+        release,
+        config,
+        color: _,
+        service,
+        contest,
+        problem,
+        language,
+        path,
+    } = opt;
+
+    let crate::Context { cwd, mut shell } = ctx;
+
+    let mode = if release {
+        config::Mode::Release
+    } else {
+        config::Mode::Debug
+    };
+
+    let detection_dir = match path {
+        Some(path) => {
+            let path = cwd.join(path.strip_prefix(".").unwrap_or(&path));
+            match path.parent() {
+                Some(parent) if path.is_file() => parent.to_owned(),
+                _ => path,
+            }
+        }
+        None => cwd.clone(),
+    };
+
+    let config_path = config::find_config_file(&cwd, config.as_deref())?;
+    let detected =
+        config::Detected::load_and_eval(&mut shell.stderr, &detection_dir, &config_path)?;
+
+    let (target, language) = match detected.merge_with_cli_options(
+        service,
+        contest.as_deref(),
+        problem.as_deref(),
+        language.as_deref(),
+        mode,
+    ) {
+        Ok((target, name)) => {
+            let value = config::eval_language(&mut shell.stderr, &config_path, &target, &name)
+                .with_context(|| format!("Could not evaluate `languages` for {:?}", target))?;
+            (Some(target), Some(OutcomeLanguage { name, value }))
+        }
+        Err(err) => {
+            shell.warn(format_args!("Skipped evaluating `languages`: {}", err))?;
+            (None, None)
+        }
+    };
+
+    let outcome = Outcome {
+        config: config_path,
+        detected,
+        target,
+        language,
+    };
+
+    if json {
+        writeln!(
+            shell.stdout,
+            "{}",
+            serde_json::to_string(&outcome).expect("should not fail"),
+        )
+    } else {
+        write!(
+            shell.stdout,
+            "{}",
+            serde_yaml::to_string(&outcome).expect("should not fail"),
+        )
+    }?;
+
+    shell.stdout.flush()?;
+    Ok(())
+}
@@ -1,15 +1,40 @@
 use anyhow::{bail, Context as _};
-use std::{fs, path::PathBuf};
+use itertools::Itertools as _;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
 use structopt::StructOpt;
-use strum::VariantNames as _;
+use strum::{EnumString, EnumVariantNames, VariantNames as _};
 use termcolor::WriteColor;
 
+const DEFAULT_CONFIG: &str = include_str!("../../resources/config/default-config.dhall");
+
+/// Files under `resources/config/schema`, which `default-config.dhall` imports as `./schema/<path>`.
+const SCHEMA: &[(&str, &str)] = &[
+    (
+        "Prelude/List/index.dhall",
+        include_str!("../../resources/config/schema/Prelude/List/index.dhall"),
+    ),
+    (
+        "Snowchains/package.dhall",
+        include_str!("../../resources/config/schema/Snowchains/package.dhall"),
+    ),
+];
+
+/// Where the schema is installed, relative to `snowchains.dhall`.
+const SCHEMA_DIR: &str = ".snowchains/schema";
+
 #[derive(StructOpt, Debug)]
 pub struct OptInit {
     /// Overwrites the existing config
     #[structopt(short, long)]
     pub force: bool,
 
+    /// How the config imports the schema
+    #[structopt(long, possible_values(Schema::VARIANTS), default_value("local"))]
+    pub schema: Schema,
+
     /// Coloring
     #[structopt(
         long,
@@ -29,6 +54,7 @@ pub(crate) fn run(
 ) -> anyhow::Result<()> {
     let OptInit {
         force,
+        schema,
         color: _,
         directory,
     } = opt;
@@ -38,9 +64,8 @@ pub(crate) fn run(
         shell: crate::shell::Shell { mut stderr, .. },
     } = ctx;
 
-    let path = cwd
-        .join(directory.strip_prefix(".").unwrap_or(&directory))
-        .join("snowchains.dhall");
+    let dir = cwd.join(directory.strip_prefix(".").unwrap_or(&directory));
+    let path = dir.join("snowchains.dhall");
 
     if !force && path.exists() {
         bail!(
@@ -49,33 +74,87 @@ pub(crate) fn run(
         );
     }
 
-    fs::write(
-        &path,
-        include_str!("../../resources/config/default-config.dhall"),
-    )
-    .with_context(|| format!("Could not write `{}`", path.display()))?;
-
-    writeln!(stderr, "Wrote `{}`", path.display())?;
+    for path in write_default_config(&dir, schema)? {
+        writeln!(stderr, "Wrote `{}`", path.display())?;
+    }
     stderr.flush()?;
 
     Ok(())
 }
 
+/// Writes `snowchains.dhall` and, for `Schema::Local`, the schema files. Returns the written paths.
+fn write_default_config(dir: &Path, schema: Schema) -> anyhow::Result<Vec<PathBuf>> {
+    let mut written = vec![];
+
+    if let Schema::Local = schema {
+        for (rel_path, content) in SCHEMA {
+            let path = dir.join(SCHEMA_DIR).join(rel_path);
+            crate::fs::write(&path, content, true)?;
+            written.push(path);
+        }
+    }
+
+    let path = dir.join("snowchains.dhall");
+    fs::write(&path, default_config(schema))
+        .with_context(|| format!("Could not write `{}`", path.display()))?;
+    written.push(path);
+
+    Ok(written)
+}
+
+#[derive(EnumVariantNames, EnumString, Debug, Clone, Copy)]
+#[strum(serialize_all = "lowercase")]
+pub enum Schema {
+    /// Installs the schema into `.snowchains/schema` and imports it with relative paths.
+    Local,
+    /// Embeds the schema in `snowchains.dhall` itself.
+    Inline,
+}
+
+fn default_config(schema: Schema) -> String {
+    SCHEMA
+        .iter()
+        .fold(DEFAULT_CONFIG.to_owned(), |config, (rel_path, content)| {
+            let import = format!("./schema/{}", rel_path);
+            let replacement = match schema {
+                Schema::Local => format!("./{}/{}", SCHEMA_DIR, rel_path),
+                Schema::Inline => format!(
+                    "(\n{}\n      )",
+                    content
+                        .lines()
+                        .map(|line| match line {
+                            "" => "".to_owned(),
+                            line => format!("        {}", line),
+                        })
+                        .join("\n"),
+                ),
+            };
+            config.replacen(&import, &replacement, 1)
+        })
+}
+
 #[cfg(test)]
 mod tests {
+    use super::Schema;
+    use crate::config::{self, Mode};
     use rusty_fork::rusty_fork_test;
+    use snowchains_core::web::PlatformKind;
 
     rusty_fork_test! {
         #[test]
-        fn resolve_default_config_dhall() {
-            run_resolve_default_config_dhall().unwrap();
+        fn eval_default_config_dhall_with_local_schema() {
+            run_eval_default_config_dhall(Schema::Local).unwrap();
+        }
+
+        #[test]
+        fn eval_default_config_dhall_with_inline_schema() {
+            run_eval_default_config_dhall(Schema::Inline).unwrap();
         }
     }
 
-    fn run_resolve_default_config_dhall() -> anyhow::Result<()> {
+    fn run_eval_default_config_dhall(schema: Schema) -> anyhow::Result<()> {
         // https://docs.rs/dhall/0.6.0/src/dhall/semantics/resolve/cache.rs.html#15-35
 
-        use dhall::Ctxt;
         use std::{env, thread};
 
         crossbeam_utils::thread::scope(|scope| {
@@ -93,21 +172,32 @@ mod tests {
         thread::Builder::new()
             .name("with-stack-size".to_owned())
             .stack_size(crate::STACK_SIZE)
-            .spawn(|| {
+            .spawn(move || {
                 let cache_dir = tempfile::Builder::new()
                     .prefix("snowchains-tests-")
                     .tempdir()?;
 
                 env::set_var("XDG_CACHE_HOME", cache_dir.path());
 
-                Ctxt::with_new(|ctxt| {
-                    dhall::semantics::parse::parse_str(include_str!(
-                        "../../resources/config/default-config.dhall",
-                    ))?
-                    .resolve(ctxt)
-                    .map(|_| ())
-                })?;
+                let workspace = tempfile::Builder::new()
+                    .prefix("snowchains-tests-")
+                    .tempdir()?;
 
+                super::write_default_config(workspace.path(), schema)?;
+
+                // The whole config is type-checked here, so one language is enough.
+                config::target_and_language(
+                    termcolor::NoColor::new(std::io::sink()),
+                    workspace.path(),
+                    None,
+                    Some(PlatformKind::Atcoder),
+                    Some("abc100"),
+                    Some("a"),
+                    Some("cpp"),
+                    Mode::Debug,
+                )?;
+
+                workspace.close()?;
                 cache_dir.close()?;
                 Ok(())
             })?
@@ -1,17 +1,69 @@
 use crate::config;
 use human_size::Size;
-use snowchains_core::web::PlatformKind;
-use std::path::PathBuf;
+use snowchains_core::{
+    judge::{JudgeOptions, Sandbox, Statistic},
+    web::PlatformKind,
+};
+use std::{num::NonZeroUsize, path::PathBuf};
 use structopt::StructOpt;
 use strum::VariantNames as _;
 use termcolor::WriteColor;
 
 #[derive(StructOpt, Debug)]
 pub struct OptJudge {
+    /// Prints the result as a JSON value
+    #[structopt(long, conflicts_with("junit"))]
+    pub json: bool,
+
+    /// Prints the result as a JUnit XML report
+    #[structopt(long)]
+    pub junit: bool,
+
     /// Build in `Release` mode
     #[structopt(long)]
     pub release: bool,
 
+    /// Builds even if the source, the command and the mode are unchanged since the last build
+    #[structopt(long)]
+    pub force_build: bool,
+
+    /// Judges again every time the source file or the test file changes
+    #[structopt(long)]
+    pub watch: bool,
+
+    /// Runs the solution with resource limits, without network access and without write access outside `$TMPDIR` (Linux only)
+    #[structopt(long)]
+    pub sandbox: bool,
+
+    /// Sets `RLIMIT_NPROC` with `--sandbox`. Note that this counts all of the processes of the user
+    #[structopt(long, value_name("N"), requires("sandbox"))]
+    pub max_processes: Option<u64>,
+
+    /// Number of test cases run concurrently. Defaults to the number of logical CPUs
+    #[structopt(short, long, value_name("N"))]
+    pub jobs: Option<NonZeroUsize>,
+
+    /// Cancels the remaining test cases once one of them fails
+    #[structopt(long)]
+    pub fail_fast: bool,
+
+    /// Runs each test case N times and shows min/median/max of the elapsed times
+    #[structopt(long, value_name("N"))]
+    pub repeat: Option<NonZeroUsize>,
+
+    /// Statistic of the elapsed times that verdicts are based on with `--repeat`
+    #[structopt(
+        long,
+        value_name("STATISTIC"),
+        possible_values(Statistic::VARIANTS),
+        default_value("median")
+    )]
+    pub statistic: Statistic,
+
+    /// Flags test cases whose statistic exceeds this fraction of the timelimit with `--repeat`
+    #[structopt(long, value_name("RATIO"), default_value("0.9"))]
+    pub warning_ratio: f64,
+
     /// Test for only the test cases
     #[structopt(long, value_name("NAME"))]
     pub testcases: Option<Vec<String>>,
@@ -20,7 +72,7 @@ pub struct OptJudge {
     #[structopt(long, value_name("SIZE"), default_value("4KiB"))]
     pub display_limit: Size,
 
-    /// Path to `snowchains.dhall`
+    /// Path to `snowchains.dhall` or `snowchains.yaml`
     #[structopt(long)]
     pub config: Option<PathBuf>,
 
@@ -58,7 +110,18 @@ pub(crate) fn run(
     ctx: crate::Context<impl Sized, impl WriteColor, impl WriteColor>,
 ) -> anyhow::Result<()> {
     let OptJudge {
+        json,
+        junit,
         release,
+        force_build,
+        watch,
+        sandbox,
+        max_processes,
+        jobs,
+        fail_fast,
+        repeat,
+        statistic,
+        warning_ratio,
         testcases,
         display_limit,
         config,
@@ -69,13 +132,37 @@ pub(crate) fn run(
         problem,
     } = opt;
 
-    let crate::Context { cwd, shell } = ctx;
+    let crate::Context { cwd, mut shell } = ctx;
+
+    let mode = if release {
+        config::Mode::Release
+    } else {
+        config::Mode::Debug
+    };
+
+    let sandbox = if sandbox {
+        let (sandbox, reasons) = Sandbox {
+            address_space: None,
+            file_size: Some(64 * 1024 * 1024),
+            processes: max_processes,
+            unshare_network: true,
+            protect_files: true,
+            filter_syscalls: true,
+        }
+        .degrade();
+        for reason in reasons {
+            shell.warn(reason)?;
+        }
+        Some(sandbox)
+    } else {
+        None
+    };
 
     let progress_draw_target = shell.progress_draw_target();
 
     let crate::shell::Shell {
         stdout,
-        stderr,
+        mut stderr,
         stdin_process_redirection,
         stdout_process_redirection,
         stderr_process_redirection,
@@ -95,24 +182,26 @@ pub(crate) fn run(
             compile,
             run,
             languageId: _,
+            env,
+            cwd: language_cwd,
+            template: _,
         },
         base_dir,
     ) = config::target_and_language(
+        &mut stderr,
         &cwd,
         config.as_deref(),
         service,
         contest.as_deref(),
         problem.as_deref(),
         language.as_deref(),
-        if release {
-            config::Mode::Release
-        } else {
-            config::Mode::Debug
-        },
+        mode,
     )?;
 
     let test_case_names = testcases.map(|ss| ss.into_iter().collect());
 
+    let hooks = config::hooks(&mut stderr, &cwd, config.as_deref())?;
+
     crate::judge::judge(crate::judge::Args {
         stdout,
         stderr,
@@ -128,7 +217,30 @@ pub(crate) fn run(
         transpile,
         compile,
         run,
+        env,
+        cwd: language_cwd,
         test_case_names,
         display_limit,
+        report: if json {
+            crate::judge::Report::Json
+        } else if junit {
+            crate::judge::Report::Junit
+        } else {
+            crate::judge::Report::Pretty
+        },
+        sandbox,
+        judge_options: JudgeOptions {
+            jobs,
+            fail_fast,
+            repeat,
+            statistic,
+            warning_ratio: Some(warning_ratio),
+        },
+        watch,
+        build_cache: crate::judge::BuildCache {
+            mode,
+            force: force_build,
+        },
+        hooks,
     })
 }
@@ -1,7 +1,7 @@
 use serde::Serialize;
 use snowchains_core::web::{
-    Atcoder, AtcoderLoginCredentials, Codeforces, CodeforcesLoginCredentials, CookieStorage, Login,
-    PlatformKind,
+    Aoj, AojLoginCredentials, Atcoder, AtcoderLoginCredentials, Codeforces,
+    CodeforcesLoginCredentials, CookieStorage, Login, PlatformKind,
 };
 use std::{
     cell::RefCell,
@@ -26,7 +26,7 @@ pub struct OptLogin {
     pub color: crate::ColorChoice,
 
     /// Target platform
-    #[structopt(possible_values(&["atcoder", "codeforces"]))]
+    #[structopt(possible_values(&["aoj", "atcoder", "codeforces"]))]
     pub service: PlatformKind,
 }
 
@@ -58,6 +58,22 @@ pub(crate) fn run(
     let timeout = Some(crate::web::SESSION_TIMEOUT);
 
     let outcome = match service {
+        PlatformKind::Aoj => {
+            let shell = RefCell::new(&mut shell);
+
+            let credentials = AojLoginCredentials {
+                username_and_password: &mut crate::web::credentials::aoj_username_and_password(
+                    &shell,
+                ),
+            };
+
+            Aoj::exec(Login {
+                credentials,
+                cookie_storage,
+                timeout,
+                shell: &shell,
+            })
+        }
         PlatformKind::Atcoder => {
             let shell = RefCell::new(&mut shell);
 
@@ -1,3 +1,4 @@
+pub(crate) mod config_show;
 pub(crate) mod init;
 pub(crate) mod judge;
 pub(crate) mod login;
@@ -5,6 +6,7 @@ pub(crate) mod participate;
 pub(crate) mod retrieve_languages;
 pub(crate) mod retrieve_submission_summaries;
 pub(crate) mod retrieve_testcases;
+pub(crate) mod stress;
 pub(crate) mod submit;
 pub(crate) mod watch_submissions;
 pub(crate) mod xtask;
@@ -1,6 +1,6 @@
 use anyhow::Context as _;
 use snowchains_core::web::{
-    Atcoder, AtcoderRetrieveLanguagesCredentials, AtcoderRetrieveLanguagesTarget, Codeforces,
+    Aoj, Atcoder, AtcoderRetrieveLanguagesCredentials, AtcoderRetrieveLanguagesTarget, Codeforces,
     CodeforcesRetrieveLanguagesCredentials, CodeforcesRetrieveLanguagesTarget, CookieStorage,
     PlatformKind, RetrieveLanguages, Yukicoder,
 };
@@ -19,7 +19,7 @@ pub struct OptRetrieveLanguages {
     #[structopt(long)]
     pub json: bool,
 
-    /// Path to `snowchains.dhall`
+    /// Path to `snowchains.dhall` or `snowchains.yaml`
     #[structopt(long)]
     pub config: Option<PathBuf>,
 
@@ -64,7 +64,8 @@ pub(crate) fn run(
 
     let crate::Context { cwd, mut shell } = ctx;
 
-    let (detected_target, _) = crate::config::detect_target(&cwd, config.as_deref())?;
+    let (detected_target, _) =
+        crate::config::detect_target(&mut shell.stderr, &cwd, config.as_deref())?;
 
     let service = service
         .map(Ok)
@@ -82,6 +83,13 @@ pub(crate) fn run(
 
     let outcome =
         match service {
+            PlatformKind::Aoj => Aoj::exec(RetrieveLanguages {
+                target: (),
+                credentials: (),
+                cookie_storage,
+                timeout,
+                shell: &mut shell,
+            }),
             PlatformKind::Atcoder => {
                 let shell = RefCell::new(&mut shell);
 
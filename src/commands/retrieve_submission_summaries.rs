@@ -1,8 +1,10 @@
-use anyhow::Context as _;
+use anyhow::{bail, Context as _};
 use snowchains_core::web::{
     Atcoder, AtcoderRetrieveSubmissionSummariesCredentials,
-    AtcoderRetrieveSubmissionSummariesTarget, CookieStorage, PlatformKind,
-    RetrieveSubmissionSummaries,
+    AtcoderRetrieveSubmissionSummariesTarget, Codeforces,
+    CodeforcesRetrieveSubmissionSummariesCredentials, CodeforcesRetrieveSubmissionSummariesTarget,
+    CookieStorage, PlatformKind, RetrieveSubmissionSummaries, Yukicoder,
+    YukicoderRetrieveSubmissionSummariesCredentials, YukicoderRetrieveSubmissionSummariesTarget,
 };
 use std::{
     cell::RefCell,
@@ -15,7 +17,7 @@ use termcolor::WriteColor;
 
 #[derive(StructOpt, Debug)]
 pub struct OptRetrieveSubmissionSummaries {
-    /// Path to `snowchains.dhall`
+    /// Path to `snowchains.dhall` or `snowchains.yaml`
     #[structopt(long)]
     pub config: Option<PathBuf>,
 
@@ -28,12 +30,21 @@ pub struct OptRetrieveSubmissionSummaries {
     pub color: crate::ColorChoice,
 
     /// Platform
-    #[structopt(short, long, value_name("SERVICE"), possible_value("atcoder"))]
+    #[structopt(
+        short,
+        long,
+        value_name("SERVICE"),
+        possible_values(PlatformKind::KEBAB_CASE_VARIANTS)
+    )]
     pub service: Option<PlatformKind>,
 
     /// Contest ID
     #[structopt(short, long, value_name("STRING"))]
     pub contest: Option<String>,
+
+    /// Problem number for yukicoder, used when `contest` is not given
+    #[structopt(short, long, value_name("STRING"))]
+    pub problem: Option<String>,
 }
 
 pub(crate) fn run(
@@ -45,11 +56,13 @@ pub(crate) fn run(
         color: _,
         service,
         contest,
+        problem,
     } = opt;
 
     let crate::Context { cwd, mut shell } = ctx;
 
-    let (detected_target, _) = crate::config::detect_target(&cwd, config.as_deref())?;
+    let (detected_target, _) =
+        crate::config::detect_target(&mut shell.stderr, &cwd, config.as_deref())?;
 
     let service = service
         .map(Ok)
@@ -59,42 +72,83 @@ pub(crate) fn run(
         })??;
 
     let contest = contest.or(detected_target.contest);
+    let problem = problem.or(detected_target.problem);
 
     let cookie_storage = CookieStorage::with_jsonl(crate::web::credentials::cookie_store_path()?)?;
     let timeout = Some(crate::web::SESSION_TIMEOUT);
 
-    match service {
+    let outcome = match service {
+        PlatformKind::Aoj => bail!("`retrieve submission-summaries` does not support AOJ yet"),
         PlatformKind::Atcoder => {
-            let outcome = {
-                let shell = RefCell::new(&mut shell);
-
-                let target = AtcoderRetrieveSubmissionSummariesTarget {
-                    contest: contest.with_context(|| "`contest` is required for AtCoder")?,
-                };
-
-                let credentials = AtcoderRetrieveSubmissionSummariesCredentials {
-                    username_and_password:
-                        &mut crate::web::credentials::atcoder_username_and_password(&shell),
-                };
-
-                Atcoder::exec(RetrieveSubmissionSummaries {
-                    target,
-                    credentials,
-                    cookie_storage,
-                    timeout,
-                    shell: &shell,
-                })?
+            let shell = RefCell::new(&mut shell);
+
+            let target = AtcoderRetrieveSubmissionSummariesTarget {
+                contest: contest.with_context(|| "`contest` is required for AtCoder")?,
+            };
+
+            let credentials = AtcoderRetrieveSubmissionSummariesCredentials {
+                username_and_password: &mut crate::web::credentials::atcoder_username_and_password(
+                    &shell,
+                ),
             };
 
-            writeln!(shell.stdout, "{}", outcome.to_json())?;
-            shell.stdout.flush()?;
-            Ok(())
+            Atcoder::exec(RetrieveSubmissionSummaries {
+                target,
+                credentials,
+                cookie_storage,
+                timeout,
+                shell: &shell,
+            })?
         }
         PlatformKind::Codeforces => {
-            todo!("`retrieve submission-summaries` for Codeforces is not implemented");
+            let target = CodeforcesRetrieveSubmissionSummariesTarget {
+                contest: contest.with_context(|| "`contest` is required for Codeforces")?,
+            };
+
+            let (api_key, api_secret) =
+                crate::web::credentials::codeforces_api_key_and_secret(&mut shell)?;
+
+            let shell = RefCell::new(&mut shell);
+
+            let credentials = CodeforcesRetrieveSubmissionSummariesCredentials {
+                username_and_password:
+                    &mut crate::web::credentials::codeforces_username_and_password(&shell),
+                api_key,
+                api_secret,
+            };
+
+            Codeforces::exec(RetrieveSubmissionSummaries {
+                target,
+                credentials,
+                cookie_storage,
+                timeout,
+                shell: &shell,
+            })?
         }
         PlatformKind::Yukicoder => {
-            todo!("`retrieve submission-summaries` for yukicoder is not implemented");
+            let target = match (contest, problem) {
+                (Some(contest), _) => YukicoderRetrieveSubmissionSummariesTarget::Contest(contest),
+                (None, Some(problem)) => {
+                    YukicoderRetrieveSubmissionSummariesTarget::ProblemNo(problem)
+                }
+                (None, None) => bail!("`contest` or `problem` is required for yukicoder"),
+            };
+
+            let credentials = YukicoderRetrieveSubmissionSummariesCredentials {
+                username: crate::web::credentials::yukicoder_username(&mut shell)?,
+            };
+
+            Yukicoder::exec(RetrieveSubmissionSummaries {
+                target,
+                credentials,
+                cookie_storage: (),
+                timeout,
+                shell: &mut shell,
+            })?
         }
-    }
+    };
+
+    writeln!(shell.stdout, "{}", outcome.to_json())?;
+    shell.stdout.flush()?;
+    Ok(())
 }
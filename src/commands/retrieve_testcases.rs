@@ -4,9 +4,9 @@ use maplit::btreeset;
 use serde::Serialize;
 use snowchains_core::{
     color_spec,
-    testsuite::{Additional, BatchTestSuite, TestSuite},
+    testsuite::{Additional, BatchTestSuite, InteractiveTestSuite, TestSuite},
     web::{
-        Atcoder, AtcoderRetrieveFullTestCasesCredentials,
+        Aoj, AojRetrieveTestCasesTargets, Atcoder, AtcoderRetrieveFullTestCasesCredentials,
         AtcoderRetrieveSampleTestCasesCredentials, Codeforces,
         CodeforcesRetrieveSampleTestCasesCredentials, CookieStorage, PlatformKind,
         ProblemsInContest, RetrieveFullTestCases, RetrieveTestCases, Yukicoder,
@@ -33,7 +33,7 @@ pub struct OptRetrieveTestcases {
     #[structopt(long)]
     pub json: bool,
 
-    /// Path to `snowchains.dhall`
+    /// Path to `snowchains.dhall` or `snowchains.yaml`
     #[structopt(long)]
     pub config: Option<PathBuf>,
 
@@ -58,6 +58,10 @@ pub struct OptRetrieveTestcases {
     #[structopt(short, long, value_name("STRING"))]
     pub contest: Option<String>,
 
+    /// Language name, whose `template` is instantiated for each problem
+    #[structopt(short, long, value_name("STRING"))]
+    pub language: Option<String>,
+
     /// Problem indexes (e.g. "a", "b", "c")
     #[structopt(short, long, value_name("STRING"))]
     pub problems: Option<Vec<String>>,
@@ -109,12 +113,14 @@ pub(crate) fn run(
         color: _,
         service,
         contest,
+        language,
         problems,
     } = opt;
 
     let crate::Context { cwd, mut shell } = ctx;
 
-    let (detected_target, workspace) = crate::config::detect_target(&cwd, config.as_deref())?;
+    let (detected_target, workspace) =
+        crate::config::detect_target(&mut shell.stderr, &cwd, config.as_deref())?;
 
     let service = service
         .map(Ok)
@@ -125,6 +131,8 @@ pub(crate) fn run(
 
     let contest = contest.or(detected_target.contest);
 
+    let language = language.or(detected_target.language);
+
     let problems = match (problems.as_deref().unwrap_or(&[]), &detected_target.problem) {
         ([], None) => None,
         ([], Some(problem)) => Some(btreeset!(problem.clone())),
@@ -136,6 +144,28 @@ pub(crate) fn run(
     let timeout = Some(crate::web::SESSION_TIMEOUT);
 
     let outcome = match service {
+        PlatformKind::Aoj => {
+            let targets = AojRetrieveTestCasesTargets::ProblemIds(
+                problems.with_context(|| "`problem`s are required for AOJ")?,
+            );
+
+            let full = if full {
+                Some(RetrieveFullTestCases { credentials: () })
+            } else {
+                None
+            };
+
+            let shell = RefCell::new(&mut shell);
+
+            Aoj::exec(RetrieveTestCases {
+                targets,
+                credentials: (),
+                full,
+                cookie_storage,
+                timeout,
+                shell,
+            })
+        }
         PlatformKind::Atcoder => {
             let shell = RefCell::new(&mut shell);
 
@@ -257,7 +287,10 @@ pub(crate) fn run(
         problems: vec![],
     };
 
+    let config_path = crate::config::find_config_file(&cwd, config.as_deref())?;
+
     for snowchains_core::web::RetrieveTestCasesOutcomeProblem {
+        contest: problem_contest,
         index,
         url,
         screen_name,
@@ -349,6 +382,51 @@ pub(crate) fn run(
         writeln!(shell.stderr, ")")?;
         shell.stderr.flush()?;
 
+        if let Some(language) = &language {
+            let target = crate::config::Target {
+                service,
+                contest: contest.clone(),
+                problem: index.original.clone(),
+                mode: crate::config::Mode::Debug,
+            };
+
+            let crate::config::Language { src, template, .. } =
+                crate::config::eval_language(&mut shell.stderr, &config_path, &target, language)?;
+
+            if let Some(template) = template {
+                let src = workspace.join(src);
+
+                if !src.exists() {
+                    let template = crate::fs::read_to_string(workspace.join(template))?;
+
+                    let placeholders = Placeholders {
+                        service,
+                        contest: problem_contest.as_ref(),
+                        index: &index.original,
+                        url: &url,
+                        screen_name: screen_name.as_deref(),
+                        display_name: &display_name,
+                        test_suite: &test_suite,
+                    };
+
+                    crate::fs::write(&src, placeholders.expand(&template), true)?;
+
+                    shell.stderr.set_color(color_spec!(Bold))?;
+                    write!(shell.stderr, "{}:", index.original)?;
+                    shell.stderr.reset()?;
+
+                    write!(shell.stderr, " Created ")?;
+
+                    shell.stderr.set_color(color_spec!(Fg(Color::Cyan)))?;
+                    write!(shell.stderr, "{}", src.display())?;
+                    shell.stderr.reset()?;
+
+                    writeln!(shell.stderr)?;
+                    shell.stderr.flush()?;
+                }
+            }
+        }
+
         acc.problems.push(OutcomeProblem {
             index,
             url,
@@ -369,5 +447,123 @@ pub(crate) fn run(
         shell.stdout.flush()?;
     }
 
+    let hooks = crate::config::hooks(&mut shell.stderr, &cwd, config.as_deref())?;
+
+    let hook = crate::hooks::Hook {
+        name: "afterRetrieveTestcases",
+        commands: &hooks.afterRetrieveTestcases,
+        base_dir: &workspace,
+        target: crate::target_env::TargetEnv {
+            workspace: Some(&workspace),
+            service: Some(service),
+            contest: contest.as_deref(),
+            language: language.as_deref(),
+            ..Default::default()
+        },
+        payload: acc.to_json(),
+    };
+
+    if let Err(err) = hook.run(&mut shell.stderr, shell.stderr_process_redirection) {
+        shell.warn(format_args!("{:#}", err))?;
+    }
+
     Ok(())
 }
+
+/// Values for `{{ name }}` in the templates of `Language.src`.
+///
+/// `{{ ... }}` with an unknown name is left as it is, so that the templates can contain `{{` and `}}` as code.
+struct Placeholders<'a> {
+    service: PlatformKind,
+    contest: Option<&'a snowchains_core::web::RetrieveTestCasesOutcomeProblemContest>,
+    index: &'a str,
+    url: &'a Url,
+    screen_name: Option<&'a str>,
+    display_name: &'a str,
+    test_suite: &'a TestSuite,
+}
+
+impl Placeholders<'_> {
+    fn get(&self, name: &str) -> Option<String> {
+        let timelimit = match self.test_suite {
+            TestSuite::Batch(BatchTestSuite { timelimit, .. })
+            | TestSuite::Interactive(InteractiveTestSuite { timelimit, .. }) => *timelimit,
+            TestSuite::Unsubmittable => None,
+        };
+
+        Some(match name {
+            "service" => self.service.to_kebab_case_str().to_owned(),
+            "contest.id" => self.contest.map(|c| c.id.clone()).unwrap_or_default(),
+            "contest.display_name" => self
+                .contest
+                .map(|c| c.display_name.clone())
+                .unwrap_or_default(),
+            "contest.url" => self.contest.map(|c| c.url.to_string()).unwrap_or_default(),
+            "problem.index" => self.index.to_owned(),
+            "problem.url" => self.url.to_string(),
+            "problem.screen_name" => self.screen_name.unwrap_or_default().to_owned(),
+            "problem.display_name" => self.display_name.to_owned(),
+            "problem.timelimit_ms" => timelimit
+                .map(|t| t.as_millis().to_string())
+                .unwrap_or_default(),
+            _ => return None,
+        })
+    }
+
+    fn expand(&self, template: &str) -> String {
+        let mut expanded = "".to_owned();
+        let mut rest = template;
+
+        while let Some(start) = rest.find("{{") {
+            expanded += &rest[..start];
+            rest = &rest[start..];
+
+            match rest[2..]
+                .find("}}")
+                .and_then(|end| Some((end, self.get(rest[2..end + 2].trim())?)))
+            {
+                Some((end, value)) => {
+                    expanded += &value;
+                    rest = &rest[end + 4..];
+                }
+                None => {
+                    expanded += "{{";
+                    rest = &rest[2..];
+                }
+            }
+        }
+
+        expanded += rest;
+        expanded
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Placeholders;
+    use snowchains_core::{testsuite::TestSuite, web::PlatformKind};
+
+    #[test]
+    fn expand_placeholders() {
+        let placeholders = Placeholders {
+            service: PlatformKind::Yukicoder,
+            contest: None,
+            index: "1",
+            url: &"https://yukicoder.me/problems/no/1".parse().unwrap(),
+            screen_name: None,
+            display_name: "道のショートカット",
+            test_suite: &TestSuite::Unsubmittable,
+        };
+
+        assert_eq!(
+            placeholders.expand(
+                "// {{ problem.url }}\n\
+                 // {{service}} {{ contest.id }}{{ problem.index }}: {{ problem.display_name }}\n\
+                 int a[][1] = {{1}}, b[][1] = {{ n }};\n",
+            ),
+            "// https://yukicoder.me/problems/no/1\n\
+             // yukicoder 1: 道のショートカット\n\
+             int a[][1] = {{1}}, b[][1] = {{ n }};\n",
+        );
+    }
+}
@@ -0,0 +1,391 @@
+use crate::config;
+use anyhow::bail;
+use az::SaturatingAs as _;
+use human_size::{Byte, Size};
+use indicatif::{ProgressBar, ProgressDrawTarget, ProgressStyle};
+use maplit::btreemap;
+use snowchains_core::{
+    color_spec,
+    judge::{CommandExpression, JudgeOptions, Verdict},
+    testsuite::{
+        BatchTestCase, DeterministicExpectedOutput, ExpectedOutput, PartialBatchTestCase, TestSuite,
+    },
+    web::PlatformKind,
+};
+use std::{
+    path::PathBuf,
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use structopt::StructOpt;
+use strum::VariantNames as _;
+use termcolor::{Color, WriteColor};
+
+#[derive(StructOpt, Debug)]
+pub struct OptStress {
+    /// Bash command that prints a random input. `$SEED` is set to the seed
+    #[structopt(long, value_name("COMMAND"))]
+    pub generator: String,
+
+    /// Bash command of a reference solution (e.g. a brute-force one)
+    #[structopt(long, value_name("COMMAND"))]
+    pub reference: String,
+
+    /// Maximum number of iterations
+    #[structopt(long, value_name("N"), default_value("1000"))]
+    pub iterations: u64,
+
+    /// Seed for the first iteration. Defaults to the current UNIX time
+    #[structopt(long, value_name("INTEGER"))]
+    pub seed: Option<u64>,
+
+    /// Timelimit for the generator and the reference solution. Defaults to 10 times the timelimit of the problem, or 60s
+    #[structopt(long, value_name("DURATION"), parse(try_from_str = humantime::parse_duration))]
+    pub generator_timelimit: Option<Duration>,
+
+    /// Build in `Release` mode
+    #[structopt(long)]
+    pub release: bool,
+
+    /// Builds even if the source, the command and the mode are unchanged since the last build
+    #[structopt(long)]
+    pub force_build: bool,
+
+    /// Display limit
+    #[structopt(long, value_name("SIZE"), default_value("4KiB"))]
+    pub display_limit: Size,
+
+    /// Path to `snowchains.dhall` or `snowchains.yaml`
+    #[structopt(long)]
+    pub config: Option<PathBuf>,
+
+    /// Coloring
+    #[structopt(
+        long,
+        possible_values(crate::ColorChoice::VARIANTS),
+        default_value("auto")
+    )]
+    pub color: crate::ColorChoice,
+
+    /// Platform
+    #[structopt(
+        short,
+        long,
+        value_name("SERVICE"),
+        possible_values(PlatformKind::KEBAB_CASE_VARIANTS)
+    )]
+    pub service: Option<PlatformKind>,
+
+    /// Contest ID
+    #[structopt(short, long, value_name("STRING"))]
+    pub contest: Option<String>,
+
+    /// Language name
+    #[structopt(short, long, value_name("STRING"))]
+    pub language: Option<String>,
+
+    /// Problem index (e.g. "a", "b", "c")
+    pub problem: Option<String>,
+}
+
+pub(crate) fn run(
+    opt: OptStress,
+    ctx: crate::Context<impl Sized, impl WriteColor, impl WriteColor>,
+) -> anyhow::Result<()> {
+    let OptStress {
+        generator,
+        reference,
+        iterations,
+        seed,
+        generator_timelimit,
+        release,
+        force_build,
+        display_limit,
+        config,
+        color: _,
+        service,
+        contest,
+        language,
+        problem,
+    } = opt;
+
+    let crate::Context { cwd, shell } = ctx;
+
+    let mode = if release {
+        config::Mode::Release
+    } else {
+        config::Mode::Debug
+    };
+
+    let progress_draw_target = shell.progress_draw_target();
+
+    let crate::shell::Shell {
+        stdout,
+        mut stderr,
+        stdin_process_redirection,
+        stdout_process_redirection,
+        stderr_process_redirection,
+        ..
+    } = shell;
+
+    let (
+        config::Target {
+            service,
+            contest,
+            problem,
+            ..
+        },
+        config::Language {
+            src,
+            transpile,
+            compile,
+            run,
+            languageId: _,
+            env,
+            cwd: language_cwd,
+            template: _,
+        },
+        base_dir,
+    ) = config::target_and_language(
+        &mut stderr,
+        &cwd,
+        config.as_deref(),
+        service,
+        contest.as_deref(),
+        problem.as_deref(),
+        language.as_deref(),
+        mode,
+    )?;
+
+    let (_, test_suite_path) =
+        crate::judge::test_suite_path(&base_dir, service, contest.as_deref(), &problem);
+
+    let mut test_suite = match crate::fs::read_yaml(&test_suite_path)? {
+        TestSuite::Batch(test_suite) => test_suite,
+        TestSuite::Interactive(_) | TestSuite::Unsubmittable => bail!(
+            "`{}` is not a batch problem. `stress` only supports batch problems",
+            test_suite_path.display(),
+        ),
+    };
+
+    let generator_timelimit = generator_timelimit.unwrap_or_else(|| {
+        test_suite
+            .timelimit
+            .map_or(GENERATOR_FALLBACK_TIMELIMIT, |t| {
+                t * GENERATOR_TIMELIMIT_FACTOR
+            })
+    });
+
+    let redirections = (
+        stdin_process_redirection,
+        stdout_process_redirection,
+        stderr_process_redirection,
+    );
+
+    for (action, msg) in &[(transpile, "Transpiling..."), (compile, "Compiling...")] {
+        if let Some(action) = action {
+            crate::judge::build(
+                &mut stderr,
+                &base_dir,
+                &src,
+                action,
+                crate::judge::BuildCache {
+                    mode,
+                    force: force_build,
+                },
+                redirections,
+                msg,
+                tokio::signal::ctrl_c,
+            )?;
+            writeln!(stderr)?;
+        }
+    }
+
+    let (cmd, tempfile) =
+        crate::judge::command_expression(run, &base_dir, language_cwd.as_deref(), &env)?;
+    let reference = CommandExpression::bash(reference, base_dir.clone(), btreemap!())?;
+
+    let first_seed = seed.unwrap_or_else(|| {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0)
+    });
+
+    stderr.set_color(color_spec!(Bold))?;
+    write!(stderr, "Stress testing...")?;
+    stderr.reset()?;
+    writeln!(stderr, " (seed = {}..)", first_seed)?;
+    stderr.flush()?;
+
+    let pb = ProgressBar::with_draw_target(iterations, progress_draw_target);
+    pb.set_style(
+        ProgressStyle::default_bar().template("{bar:50} {pos:>4}/{len} ({elapsed}) {msg}"),
+    );
+
+    let mut failure = None;
+
+    for seed in (0..iterations).map(|i| first_seed.wrapping_add(i)) {
+        pb.set_message(&format!("seed = {}", seed));
+
+        let generator = CommandExpression::bash(
+            &generator,
+            base_dir.clone(),
+            btreemap!("SEED".into() => seed.to_string().into()),
+        )?;
+        let input = run_to_completion("The generator", &generator, generator_timelimit, "".into())?;
+        let output = run_to_completion(
+            "The reference solution",
+            &reference,
+            generator_timelimit,
+            input.clone(),
+        )?;
+
+        let test_case = test_suite.test_case(PartialBatchTestCase {
+            name: Some(format!("stress-{}", seed)),
+            r#in: input.clone(),
+            out: Some(output.clone()),
+            timelimit: None,
+            memorylimit: None,
+            r#match: None,
+        });
+
+        let outcome = snowchains_core::judge::judge(
+            ProgressDrawTarget::hidden(),
+            tokio::signal::ctrl_c,
+            &cmd,
+            &[test_case],
+            JudgeOptions::default(),
+        )?;
+
+        if !matches!(outcome.verdicts[..], [Verdict::Accepted { .. }]) {
+            failure = Some((seed, input, output, outcome));
+            break;
+        }
+        pb.inc(1);
+    }
+
+    pb.finish_and_clear();
+
+    if let Some(tempfile) = tempfile {
+        tempfile.close()?;
+    }
+
+    let (seed, input, output, outcome) = if let Some(failure) = failure {
+        failure
+    } else {
+        writeln!(stderr, "No failures in {} iteration(s).", iterations)?;
+        return stderr.flush().map_err(Into::into);
+    };
+
+    let name = unique_name(&test_suite.cases, seed);
+
+    test_suite.cases.push(PartialBatchTestCase {
+        name: Some(name.clone()),
+        r#in: input,
+        out: Some(output),
+        timelimit: None,
+        memorylimit: None,
+        r#match: None,
+    });
+    crate::fs::write(
+        &test_suite_path,
+        TestSuite::Batch(test_suite).to_yaml_pretty(),
+        false,
+    )?;
+
+    write!(stderr, "Found a failing input (seed = {}). Added ", seed)?;
+    stderr.set_color(color_spec!(Fg(Color::Cyan)))?;
+    write!(stderr, "{:?}", name)?;
+    stderr.reset()?;
+    write!(stderr, " to ")?;
+    stderr.set_color(color_spec!(Fg(Color::Cyan)))?;
+    write!(stderr, "{}", test_suite_path.display())?;
+    stderr.reset()?;
+    writeln!(stderr)?;
+    writeln!(stderr)?;
+    stderr.flush()?;
+
+    outcome.print_pretty(
+        stdout,
+        Some(display_limit.into::<Byte>().value().saturating_as()),
+    )?;
+    outcome.error_on_fail()
+}
+
+/// Multiplied by the timelimit of the problem to get the default `--generator-timelimit`.
+const GENERATOR_TIMELIMIT_FACTOR: u32 = 10;
+
+/// Default `--generator-timelimit` for problems without `timelimit`.
+const GENERATOR_FALLBACK_TIMELIMIT: Duration = Duration::from_secs(60);
+
+/// Returns `stress-<seed>`, suffixed with `-2`, `-3`, ... if it is already taken by `cases`.
+fn unique_name(cases: &[PartialBatchTestCase], seed: u64) -> String {
+    let is_taken = |name: &str| cases.iter().any(|c| c.name.as_deref() == Some(name));
+
+    let name = format!("stress-{}", seed);
+    if !is_taken(&name) {
+        return name;
+    }
+    (2..)
+        .map(|i| format!("{}-{}", name, i))
+        .find(|name| !is_taken(name))
+        .expect("should be found")
+}
+
+fn run_to_completion(
+    name: &str,
+    cmd: &CommandExpression,
+    timelimit: Duration,
+    input: Arc<str>,
+) -> anyhow::Result<Arc<str>> {
+    let outcome = snowchains_core::judge::judge(
+        ProgressDrawTarget::hidden(),
+        tokio::signal::ctrl_c,
+        cmd,
+        &[BatchTestCase {
+            name: None,
+            timelimit: Some(timelimit),
+            memorylimit: None,
+            input,
+            output: ExpectedOutput::Deterministic(DeterministicExpectedOutput::Pass),
+        }],
+        JudgeOptions::default(),
+    )?;
+
+    match &outcome.verdicts[..] {
+        [Verdict::Accepted { stdout, .. }] => Ok(stdout.clone()),
+        [Verdict::RuntimeError { status, stderr, .. }] => {
+            bail!("{} failed ({}):\n{}", name, status, stderr)
+        }
+        [Verdict::TimelimitExceeded { timelimit, .. }] => bail!(
+            "{} did not finish in {}. Try `--generator-timelimit`",
+            name,
+            humantime::format_duration(*timelimit),
+        ),
+        _ => bail!("{} failed", name),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use snowchains_core::testsuite::PartialBatchTestCase;
+
+    #[test]
+    fn unique_name() {
+        let case = |name: &str| PartialBatchTestCase {
+            name: Some(name.to_owned()),
+            r#in: "".into(),
+            out: None,
+            timelimit: None,
+            memorylimit: None,
+            r#match: None,
+        };
+
+        assert_eq!("stress-1", super::unique_name(&[case("stress-2")], 1));
+        assert_eq!(
+            "stress-1-3",
+            super::unique_name(&[case("stress-1"), case("stress-1-2")], 1),
+        );
+    }
+}
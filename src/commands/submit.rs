@@ -2,9 +2,9 @@ use crate::config;
 use anyhow::{bail, Context as _};
 use human_size::Size;
 use snowchains_core::web::{
-    Atcoder, AtcoderSubmitCredentials, Codeforces, CodeforcesSubmitCredentials, CookieStorage,
-    PlatformKind, ProblemInContest, Submit, Yukicoder, YukicoderSubmitCredentials,
-    YukicoderSubmitTarget,
+    Aoj, AojSubmitCredentials, AojSubmitTarget, Atcoder, AtcoderSubmitCredentials, Codeforces,
+    CodeforcesSubmitCredentials, CookieStorage, PlatformKind, ProblemInContest, Submit, Yukicoder,
+    YukicoderSubmitCredentials, YukicoderSubmitTarget,
 };
 use std::{cell::RefCell, env, io::BufRead, iter, path::PathBuf};
 use structopt::StructOpt;
@@ -37,7 +37,7 @@ pub struct OptSubmit {
     #[structopt(long, value_name("SIZE"), default_value("4KiB"))]
     pub display_limit: Size,
 
-    /// Path to `snowchains.dhall`
+    /// Path to `snowchains.dhall` or `snowchains.yaml`
     #[structopt(long)]
     pub config: Option<PathBuf>,
 
@@ -91,6 +91,12 @@ pub(crate) fn run(
 
     let crate::Context { cwd, mut shell } = ctx;
 
+    let mode = if debug {
+        config::Mode::Debug
+    } else {
+        config::Mode::Release
+    };
+
     let (
         config::Target {
             service,
@@ -104,23 +110,22 @@ pub(crate) fn run(
             compile: _,
             run: _,
             languageId: language_id,
+            env: _,
+            cwd: _,
+            template: _,
         },
         base_dir,
     ) = config::target_and_language(
+        &mut shell.stderr,
         &cwd,
         config.as_deref(),
         service,
         contest.as_deref(),
         problem.as_deref(),
         language.as_deref(),
-        if debug {
-            config::Mode::Debug
-        } else {
-            config::Mode::Release
-        },
+        mode,
     )?;
 
-    let code = crate::fs::read_to_string(base_dir.join(&src))?;
     let language_id = language_id.with_context(|| "Missing `languageId`")?;
 
     if no_judge {
@@ -130,9 +135,13 @@ pub(crate) fn run(
                 &base_dir,
                 &src,
                 transpile,
-                shell.stdin_process_redirection,
-                shell.stdout_process_redirection,
-                shell.stderr_process_redirection,
+                crate::judge::BuildCache { mode, force: false },
+                (
+                    shell.stdin_process_redirection,
+                    shell.stdout_process_redirection,
+                    shell.stderr_process_redirection,
+                ),
+                tokio::signal::ctrl_c,
             )?;
         }
     } else {
@@ -145,8 +154,11 @@ pub(crate) fn run(
                 vec![]
             })
             .args(&["--display-limit", &display_limit.to_string()])
-            .arg("--config")
-            .arg(base_dir.join("snowchains.dhall"))
+            .args(if let Some(config) = &config {
+                vec!["--config".as_ref(), config.as_os_str()]
+            } else {
+                vec![]
+            })
             .args(&["--color", &color.to_string()])
             .args(&["-s", service.to_kebab_case_str()])
             .args(if let Some(contest) = &contest {
@@ -167,6 +179,38 @@ pub(crate) fn run(
         }
     }
 
+    let hooks = config::hooks(&mut shell.stderr, &cwd, config.as_deref())?;
+    let (_, test_suite_path) =
+        crate::judge::test_suite_path(&base_dir, service, contest.as_deref(), &problem);
+
+    crate::hooks::Hook {
+        name: "beforeSubmit",
+        commands: &hooks.beforeSubmit,
+        base_dir: &base_dir,
+        target: crate::target_env::TargetEnv {
+            workspace: Some(&base_dir),
+            service: Some(service),
+            contest: contest.as_deref(),
+            problem: Some(&problem),
+            mode: Some(mode),
+            src: Some(&base_dir.join(&src)),
+            test_suite: Some(&test_suite_path),
+            ..Default::default()
+        },
+        payload: serde_json::json!({
+            "service": service.to_kebab_case_str(),
+            "contest": contest,
+            "problem": problem,
+            "language_id": language_id,
+            "src": base_dir.join(&src),
+        })
+        .to_string(),
+    }
+    .run(&mut shell.stderr, shell.stderr_process_redirection)
+    .with_context(|| "Aborted the submission")?;
+
+    let code = crate::fs::read_to_string(base_dir.join(&src))?;
+
     let watch_submission = !no_watch;
 
     let cookie_storage = CookieStorage::with_jsonl(crate::web::credentials::cookie_store_path()?)?;
@@ -174,6 +218,28 @@ pub(crate) fn run(
     let timeout = Some(crate::web::SESSION_TIMEOUT);
 
     let outcome = match service {
+        PlatformKind::Aoj => {
+            let shell = RefCell::new(&mut shell);
+
+            let target = AojSubmitTarget::ProblemId(problem);
+
+            let credentials = AojSubmitCredentials {
+                username_and_password: &mut crate::web::credentials::aoj_username_and_password(
+                    &shell,
+                ),
+            };
+
+            Aoj::exec(Submit {
+                target,
+                credentials,
+                language_id,
+                code,
+                watch_submission,
+                cookie_storage,
+                timeout,
+                shell: &shell,
+            })
+        }
         PlatformKind::Atcoder => {
             let shell = RefCell::new(&mut shell);
 
@@ -1,7 +1,10 @@
-use anyhow::Context as _;
+use anyhow::{bail, Context as _};
 use snowchains_core::web::{
-    Atcoder, AtcoderWatchSubmissionsCredentials, AtcoderWatchSubmissionsTarget, CookieStorage,
-    PlatformKind, WatchSubmissions,
+    Aoj, AojWatchSubmissionsCredentials, AojWatchSubmissionsTarget, Atcoder,
+    AtcoderWatchSubmissionsCredentials, AtcoderWatchSubmissionsTarget, Codeforces,
+    CodeforcesWatchSubmissionsCredentials, CodeforcesWatchSubmissionsTarget, CookieStorage,
+    PlatformKind, WatchSubmissions, Yukicoder, YukicoderWatchSubmissionsCredentials,
+    YukicoderWatchSubmissionsTarget,
 };
 use std::{cell::RefCell, io::BufRead, path::PathBuf};
 use structopt::StructOpt;
@@ -10,7 +13,7 @@ use termcolor::WriteColor;
 
 #[derive(StructOpt, Debug)]
 pub struct OptWatchSubmissions {
-    /// Path to `snowchains.dhall`
+    /// Path to `snowchains.dhall` or `snowchains.yaml`
     #[structopt(long)]
     pub config: Option<PathBuf>,
 
@@ -23,12 +26,21 @@ pub struct OptWatchSubmissions {
     pub color: crate::ColorChoice,
 
     /// Target platform
-    #[structopt(short, long, value_name("SERVICE"), possible_value("atcoder"))]
+    #[structopt(
+        short,
+        long,
+        value_name("SERVICE"),
+        possible_values(PlatformKind::KEBAB_CASE_VARIANTS)
+    )]
     pub service: Option<PlatformKind>,
 
     /// Contest ID
     #[structopt(short, long, value_name("STRING"))]
     pub contest: Option<String>,
+
+    /// Problem ID for AOJ, or problem number for yukicoder used when `contest` is not given
+    #[structopt(short, long, value_name("STRING"))]
+    pub problem: Option<String>,
 }
 
 pub(crate) fn run(
@@ -40,11 +52,13 @@ pub(crate) fn run(
         color: _,
         service,
         contest,
+        problem,
     } = opt;
 
     let crate::Context { cwd, mut shell } = ctx;
 
-    let (detected_target, _) = crate::config::detect_target(&cwd, config.as_deref())?;
+    let (detected_target, _) =
+        crate::config::detect_target(&mut shell.stderr, &cwd, config.as_deref())?;
 
     let service = service
         .map(Ok)
@@ -53,11 +67,33 @@ pub(crate) fn run(
             "`service` was not detected. To specify it, add `--service` to the arguments"
         })??;
     let contest = contest.or(detected_target.contest);
+    let problem = problem.or(detected_target.problem);
 
     let cookie_storage = CookieStorage::with_jsonl(crate::web::credentials::cookie_store_path()?)?;
     let timeout = Some(crate::web::SESSION_TIMEOUT);
 
     match service {
+        PlatformKind::Aoj => {
+            let target = AojWatchSubmissionsTarget {
+                problem: problem.with_context(|| "`problem` is required for AOJ")?,
+            };
+
+            let shell = RefCell::new(&mut shell);
+
+            let credentials = AojWatchSubmissionsCredentials {
+                username_and_password: &mut crate::web::credentials::aoj_username_and_password(
+                    &shell,
+                ),
+            };
+
+            Aoj::exec(WatchSubmissions {
+                target,
+                credentials,
+                cookie_storage,
+                timeout,
+                shell: &shell,
+            })
+        }
         PlatformKind::Atcoder => {
             let target = AtcoderWatchSubmissionsTarget {
                 contest: contest.with_context(|| "`contest` is required for AtCoder")?,
@@ -79,7 +115,49 @@ pub(crate) fn run(
                 shell: &shell,
             })
         }
-        PlatformKind::Codeforces => todo!(),
-        PlatformKind::Yukicoder => todo!(),
+        PlatformKind::Codeforces => {
+            let target = CodeforcesWatchSubmissionsTarget {
+                contest: contest.with_context(|| "`contest` is required for Codeforces")?,
+            };
+
+            let (api_key, api_secret) =
+                crate::web::credentials::codeforces_api_key_and_secret(&mut shell)?;
+
+            let shell = RefCell::new(&mut shell);
+
+            let credentials = CodeforcesWatchSubmissionsCredentials {
+                username_and_password:
+                    &mut crate::web::credentials::codeforces_username_and_password(&shell),
+                api_key,
+                api_secret,
+            };
+
+            Codeforces::exec(WatchSubmissions {
+                target,
+                credentials,
+                cookie_storage,
+                timeout,
+                shell: &shell,
+            })
+        }
+        PlatformKind::Yukicoder => {
+            let target = match (contest, problem) {
+                (Some(contest), _) => YukicoderWatchSubmissionsTarget::Contest(contest),
+                (None, Some(problem)) => YukicoderWatchSubmissionsTarget::ProblemNo(problem),
+                (None, None) => bail!("`contest` or `problem` is required for yukicoder"),
+            };
+
+            let credentials = YukicoderWatchSubmissionsCredentials {
+                username: crate::web::credentials::yukicoder_username(&mut shell)?,
+            };
+
+            Yukicoder::exec(WatchSubmissions {
+                target,
+                credentials,
+                cookie_storage: (),
+                timeout,
+                shell: &mut shell,
+            })
+        }
     }
 }
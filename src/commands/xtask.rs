@@ -1,28 +1,66 @@
-use crate::config;
-use anyhow::bail;
-use std::{ffi::OsString, io::Write as _};
-use structopt::StructOpt;
+use crate::{config, target_env::TargetEnv};
+use anyhow::{bail, Context as _};
+use snowchains_core::web::PlatformKind;
+use std::{ffi::OsString, path::PathBuf};
+use structopt::{clap::AppSettings, StructOpt};
+use termcolor::WriteColor;
 
 #[derive(StructOpt, Debug)]
+#[structopt(setting = AppSettings::TrailingVarArg)]
 pub struct OptXtask {
-    /// Name
-    pub subcommand: String,
+    /// Resolves `src` in `Release` mode
+    #[structopt(long)]
+    pub release: bool,
 
-    /// Arguments for the subcommand
-    #[structopt(parse(from_os_str))]
-    pub args: Vec<OsString>,
+    /// Path to `snowchains.dhall` or `snowchains.yaml`
+    #[structopt(long)]
+    pub config: Option<PathBuf>,
+
+    /// Platform
+    #[structopt(
+        short,
+        long,
+        value_name("SERVICE"),
+        possible_values(PlatformKind::KEBAB_CASE_VARIANTS)
+    )]
+    pub service: Option<PlatformKind>,
+
+    /// Contest ID
+    #[structopt(short, long, value_name("STRING"))]
+    pub contest: Option<String>,
+
+    /// Problem index (e.g. "a", "b", "c")
+    #[structopt(short, long, value_name("STRING"))]
+    pub problem: Option<String>,
+
+    /// Language name
+    #[structopt(short, long, value_name("STRING"))]
+    pub language: Option<String>,
+
+    /// Name and arguments for the subcommand. Everything after the name is passed to the subcommand as-is
+    #[structopt(required(true), value_name("SUBCOMMAND"), parse(from_os_str))]
+    pub subcommand: Vec<OsString>,
 }
 
 pub(crate) fn run(
     opt: OptXtask,
-    ctx: crate::Context<impl Sized, impl Sized, impl Sized>,
+    ctx: crate::Context<impl Sized, impl Sized, impl WriteColor>,
 ) -> anyhow::Result<()> {
-    let OptXtask { subcommand, args } = opt;
+    let OptXtask {
+        release,
+        config,
+        service,
+        contest,
+        problem,
+        language,
+        subcommand,
+    } = opt;
 
     let crate::Context {
         cwd,
         shell:
             crate::shell::Shell {
+                mut stderr,
                 stdin_process_redirection,
                 stdout_process_redirection,
                 stderr_process_redirection,
@@ -30,23 +68,74 @@ pub(crate) fn run(
             },
     } = ctx;
 
-    let config::Script {
-        program,
-        extension,
-        content,
-    } = config::xtask(&cwd, None, &subcommand)?;
+    let mode = if release {
+        config::Mode::Release
+    } else {
+        config::Mode::Debug
+    };
+
+    let (subcommand, args) = subcommand
+        .split_first()
+        .expect("should be required by clap");
+    let subcommand = subcommand
+        .to_str()
+        .with_context(|| format!("Invalid name: {:?}", subcommand))?;
 
-    let mut tempfile = tempfile::Builder::new()
-        .prefix(&format!("snowchains-xtask-{}", subcommand))
-        .suffix(&format!(".{}", extension))
-        .tempfile()?;
+    let command = config::xtask(&mut stderr, &cwd, config.as_deref(), subcommand)?;
 
-    tempfile.write_all(content.as_ref())?;
-    tempfile.flush()?;
+    let (detected, workspace) = config::detect_target(&mut stderr, &cwd, config.as_deref())?;
 
-    let status = std::process::Command::new(program)
-        .arg(tempfile.path())
+    // Unlike the other commands, the target may be partially resolved.
+    let service = match service {
+        Some(service) => Some(service),
+        None => detected.parse_service()?,
+    };
+    let contest = contest.or_else(|| detected.contest.clone());
+    let problem = problem.or_else(|| detected.problem.clone());
+    let language = language.or_else(|| detected.language.clone());
+
+    let src = match (service, &problem, &language) {
+        (Some(service), Some(problem), Some(language)) => {
+            let target = config::Target {
+                service,
+                contest: contest.clone(),
+                problem: problem.clone(),
+                mode,
+            };
+            let config_path = config::find_config_file(&cwd, config.as_deref())?;
+            let config::Language { src, .. } =
+                config::eval_language(&mut stderr, &config_path, &target, language)?;
+            Some(workspace.join(src))
+        }
+        _ => None,
+    };
+
+    let test_suite = match (service, &problem) {
+        (Some(service), Some(problem)) => {
+            Some(crate::judge::test_suite_path(&workspace, service, contest.as_deref(), problem).1)
+        }
+        _ => None,
+    };
+
+    let env = TargetEnv {
+        workspace: Some(&workspace),
+        service,
+        contest: contest.as_deref(),
+        problem: problem.as_deref(),
+        language: language.as_deref(),
+        mode: Some(mode),
+        src: src.as_deref(),
+        test_suite: test_suite.as_deref(),
+    }
+    .to_env();
+
+    let (cmd, tempfile) = crate::judge::command_expression(command, &cwd, None, &env)?;
+
+    let status = std::process::Command::new(&cmd.program)
+        .args(&cmd.args)
         .args(args)
+        .current_dir(&cmd.cwd)
+        .envs(&cmd.env)
         .stdin(stdin_process_redirection())
         .stdout(stdout_process_redirection())
         .stderr(stderr_process_redirection())
@@ -59,6 +148,8 @@ pub(crate) fn run(
         }
     }
 
-    tempfile.close()?;
+    if let Some(tempfile) = tempfile {
+        tempfile.close()?;
+    }
     Ok(())
 }
@@ -0,0 +1,897 @@
+// https://github.com/Nadrieril/dhall-rust/pull/215
+#![allow(redundant_semicolons)]
+
+use anyhow::{anyhow, bail, ensure, Context as _};
+use dhall::syntax::{
+    Expr, ExprKind, FilePath, FilePrefix, Import, ImportMode, ImportTarget, InterpolatedText,
+};
+use heck::{CamelCase as _, KebabCase as _, MixedCase as _, SnakeCase as _};
+use indexmap::IndexMap;
+use itertools::Itertools as _;
+use maplit::hashmap;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_dhall::{SimpleType, StaticType};
+use sha2::{Digest as _, Sha256};
+use snowchains_core::web::PlatformKind;
+use std::{
+    collections::BTreeMap,
+    convert::Infallible,
+    env, fmt,
+    path::{Path, PathBuf},
+    str,
+};
+use termcolor::WriteColor;
+
+mod yaml;
+
+/// File names of the config, in order of precedence.
+const FILE_NAMES: &[&str] = &["snowchains.dhall", "snowchains.yaml", "snowchains.yml"];
+
+pub(crate) fn detect_target(
+    stderr: impl WriteColor,
+    cwd: &Path,
+    rel_path: Option<&Path>,
+) -> anyhow::Result<(Detected, PathBuf)> {
+    let path = find_config_file(cwd, rel_path)?;
+    let detected = Detected::load_and_eval(stderr, cwd, &path)?;
+    let dir = Path::new(&path)
+        .parent()
+        .unwrap_or_else(|| path.as_ref())
+        .to_owned();
+    Ok((detected, dir))
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn target_and_language(
+    mut stderr: impl WriteColor,
+    cwd: &Path,
+    rel_path: Option<&Path>,
+    cli_opt_service: Option<PlatformKind>,
+    cli_opt_contest: Option<&str>,
+    cli_opt_problem: Option<&str>,
+    cli_opt_language: Option<&str>,
+    cli_opt_mode: Mode,
+) -> anyhow::Result<(Target, Language, PathBuf)> {
+    let path = find_config_file(cwd, rel_path)?;
+
+    let (target, language_name) = Detected::load_and_eval(&mut stderr, cwd, &path)?
+        .merge_with_cli_options(
+            cli_opt_service,
+            cli_opt_contest,
+            cli_opt_problem,
+            cli_opt_language,
+            cli_opt_mode,
+        )?;
+
+    let language = eval_language(stderr, &path, &target, &language_name)?;
+
+    let dir = Path::new(&path)
+        .parent()
+        .unwrap_or_else(|| path.as_ref())
+        .to_owned();
+
+    Ok((target, language, dir))
+}
+
+/// Evaluates `languages` in the config at `path` and picks `language_name`.
+pub(crate) fn eval_language(
+    stderr: impl WriteColor,
+    path: &str,
+    target: &Target,
+    language_name: &str,
+) -> anyhow::Result<Language> {
+    if is_yaml(path) {
+        let mut language = yaml::language(path, target, language_name)?;
+        language.inherit_env_and_cwd();
+        return Ok(language);
+    }
+
+    let languages_expr = |service_type| {
+        format!(
+            "let target = {} let config = {} in config.languages target",
+            target.to_dhall_expr(service_type),
+            path,
+        )
+    };
+
+    let mut languages = eval_cached(stderr, path, &languages_expr(SERVICE_TYPE), |expr| {
+        let eval = |expr: &str| {
+            serde_dhall::from_str(expr)
+                .parse::<BTreeMap<String, Language>>()
+                .with_context(|| format!("Could not evaluate `languages` in `{}`", path))
+        };
+
+        eval(expr).or_else(|err| {
+            // Configs that import the old schema expect `Service` without `Aoj`.
+            if target.service == PlatformKind::Aoj {
+                return Err(err);
+            }
+            eval(&languages_expr(LEGACY_SERVICE_TYPE)).map_err(|_| err)
+        })
+    })?;
+
+    let expected_names = languages.keys().join(", ");
+
+    let mut language = languages.remove(language_name).with_context(|| {
+        format!(
+            "The language `{}` not found. Expected one of [{}]",
+            language_name, expected_names,
+        )
+    })?;
+    language.inherit_env_and_cwd();
+
+    Ok(language)
+}
+
+pub(crate) fn xtask(
+    stderr: impl WriteColor,
+    cwd: &Path,
+    rel_path: Option<&Path>,
+    name: &str,
+) -> anyhow::Result<Command> {
+    let path = find_config_file(cwd, rel_path)?;
+
+    let xtask = if is_yaml(&path) {
+        yaml::xtask(&path)?
+    } else {
+        let expr = format!("let config = {} in config.xtask", path);
+
+        eval_cached(stderr, &path, &expr, |expr| {
+            match serde_dhall::from_str(expr)
+                .type_annotation(&map_annot(SimpleType::Text, Command::static_type()))
+                .parse::<IndexMap<String, Command>>()
+            {
+                Ok(xtask) => Ok(xtask),
+                // Older configs have `Script`s instead of `Command`s.
+                Err(err) => serde_dhall::from_str(expr)
+                    .type_annotation(&map_annot(SimpleType::Text, Script::static_type()))
+                    .parse::<IndexMap<String, Script>>()
+                    .map(|xtask| {
+                        xtask
+                            .into_iter()
+                            .map(|(name, script)| (name, Command::Script(script)))
+                            .collect()
+                    })
+                    .map_err(|_| err),
+            }
+            .with_context(|| format!("Could not evaluate `xtask` in `{}`", path))
+        })?
+    };
+
+    xtask.get(name).cloned().with_context(|| {
+        format!(
+            "No such xtask subcommand: `{}` (found [{}])",
+            name,
+            xtask
+                .keys()
+                .format_with(", ", |s, f| f(&format_args!("`{}`", s)))
+        )
+    })
+}
+
+/// Evaluates `hooks` in the config. Missing `hooks` and missing fields of it are regarded as empty.
+pub(crate) fn hooks(
+    stderr: impl WriteColor,
+    cwd: &Path,
+    rel_path: Option<&Path>,
+) -> anyhow::Result<Hooks> {
+    let path = find_config_file(cwd, rel_path)?;
+
+    if is_yaml(&path) {
+        return yaml::hooks(&path);
+    }
+
+    let expr = format!(
+        "let Command = < Args : List Text | Script : {{ program : Text, extension : Text, content : Text }} > \
+         let config = {} \
+         in  {{ afterRetrieveTestcases = [] : List Command \
+              , beforeSubmit = [] : List Command \
+              , afterJudgeAccepted = [] : List Command \
+              , afterJudgeFailure = [] : List Command \
+              }} \
+             ⫽ ({{ hooks = {{=}} }} ⫽ config).hooks",
+        path,
+    );
+
+    eval_cached(stderr, &path, &expr, |expr| {
+        serde_dhall::from_str(expr)
+            .static_type_annotation()
+            .parse::<Hooks>()
+            .with_context(|| format!("Could not evaluate `hooks` in `{}`", path))
+    })
+}
+
+pub(crate) fn find_config_file(cwd: &Path, rel_path: Option<&Path>) -> anyhow::Result<String> {
+    let path = if let Some(rel_path) = rel_path {
+        let rel_path = rel_path.strip_prefix(".").unwrap_or(rel_path);
+        let path = cwd.join(rel_path);
+        ensure!(path.exists(), "`{}` does not exist", path.display());
+        path
+    } else {
+        cwd.ancestors()
+            .flat_map(|p| FILE_NAMES.iter().map(move |name| p.join(name)))
+            .find(|p| p.exists())
+            .with_context(|| {
+                format!(
+                    "Could not find any of [{}] in `{}` or any parent directory",
+                    FILE_NAMES
+                        .iter()
+                        .format_with(", ", |s, f| f(&format_args!("`{}`", s))),
+                    cwd.display(),
+                )
+            })?
+    };
+
+    let path = path
+        .into_os_string()
+        .into_string()
+        .map_err(|path| anyhow!("The config path must be valid UTF-8: {:?}", path))?;
+
+    // The path of a Dhall config is embedded in Dhall expressions as an import.
+    if !is_yaml(&path) && path.chars().any(|c| c.is_whitespace() || c.is_control()) {
+        bail!(
+            "The config path must not contain whitespace and control characters: {:?}",
+            path,
+        );
+    }
+
+    Ok(path)
+}
+
+fn is_yaml(path: &str) -> bool {
+    matches!(
+        Path::new(path).extension().and_then(|e| e.to_str()),
+        Some("yaml") | Some("yml")
+    )
+}
+
+/// Returns the value saved by the last evaluation of `expr`, or evaluates it with `eval` and saves the
+/// result under `.snowchains/config-cache`.
+///
+/// The key is the hash of `expr` and the contents of the config file and its local imports.
+/// Remote imports are assumed not to change.
+///
+/// The cache is best-effort. Failures to write it are printed to `stderr` as warnings, and only the newest
+/// [`CONFIG_CACHE_CAPACITY`] entries are kept.
+fn eval_cached<T: Serialize + DeserializeOwned>(
+    mut stderr: impl WriteColor,
+    config_path: &str,
+    expr: &str,
+    eval: impl FnOnce(&str) -> anyhow::Result<T>,
+) -> anyhow::Result<T> {
+    let config_path = Path::new(config_path);
+
+    let cache_dir = config_path
+        .parent()
+        .unwrap_or(config_path)
+        .join(".snowchains")
+        .join("config-cache");
+
+    let mut imports = BTreeMap::new();
+    collect_imports(&mut stderr, &cache_dir, config_path, true, &mut imports)?;
+
+    let mut hasher = Sha256::new();
+    hasher.update(env!("CARGO_PKG_VERSION"));
+    hasher.update([0]);
+    hasher.update(expr);
+    for (import, content) in imports {
+        hasher.update([0]);
+        hasher.update(import);
+        if let Some(content) = content {
+            hasher.update([0]);
+            hasher.update(Sha256::digest(&content));
+        }
+    }
+    let cache_path = cache_dir
+        .join(hex::encode(hasher.finalize()))
+        .with_extension("json");
+
+    if cache_path.exists() {
+        if let Ok(value) = serde_json::from_str(&crate::fs::read_to_string(&cache_path)?) {
+            return Ok(value);
+        }
+    }
+
+    let value = eval(expr)?;
+    let saved = crate::fs::write_json(&cache_path, &value, true)
+        .and_then(|()| evict(&cache_dir, CONFIG_CACHE_CAPACITY))
+        .and_then(|()| evict(&cache_dir.join("imports"), CONFIG_CACHE_CAPACITY));
+    if let Err(err) = saved {
+        crate::shell::warn(
+            stderr,
+            format_args!("Could not update the config cache: {:#}", err),
+        )?;
+    }
+    Ok(value)
+}
+
+/// Maximum number of files kept in `.snowchains/config-cache` and `.snowchains/config-cache/imports` each.
+const CONFIG_CACHE_CAPACITY: usize = 256;
+
+/// Removes the least recently written `*.json` files in `dir` so that at most `capacity` of them remain.
+fn evict(dir: &Path, capacity: usize) -> anyhow::Result<()> {
+    if !dir.exists() {
+        return Ok(());
+    }
+
+    let mut entries = std::fs::read_dir(dir)
+        .with_context(|| format!("Could not read `{}`", dir.display()))?
+        .map(|entry| {
+            let path = entry?.path();
+            let modified = path.metadata()?.modified()?;
+            Ok((modified, path))
+        })
+        .collect::<std::io::Result<Vec<_>>>()
+        .with_context(|| format!("Could not read `{}`", dir.display()))?;
+
+    entries.retain(|(_, path)| path.extension() == Some("json".as_ref()));
+    entries.sort();
+
+    for (_, path) in entries.iter().rev().skip(capacity) {
+        std::fs::remove_file(path)
+            .with_context(|| format!("Could not remove `{}`", path.display()))?;
+    }
+    Ok(())
+}
+
+/// An import in a local file, except remote ones.
+#[derive(Deserialize, Serialize)]
+enum LocalImport {
+    File { path: PathBuf, code: bool },
+    Env(String),
+}
+
+/// Collects the contents of `path` and what it imports recursively.
+///
+/// Parsing takes about as long as evaluating, so the imports found in each file are also cached by its
+/// content.
+fn collect_imports(
+    stderr: &mut dyn WriteColor,
+    cache_dir: &Path,
+    path: &Path,
+    code: bool,
+    imports: &mut BTreeMap<String, Option<Vec<u8>>>,
+) -> anyhow::Result<()> {
+    let key = path.to_string_lossy().into_owned();
+    if imports.contains_key(&key) {
+        return Ok(());
+    }
+    let content = std::fs::read(path).ok();
+    imports.insert(key.clone(), content.clone());
+
+    let content = match content {
+        Some(content) if code => content,
+        _ => return Ok(()),
+    };
+
+    let found_path = cache_dir
+        .join("imports")
+        .join(hex::encode(
+            Sha256::new()
+                .chain(&key)
+                .chain([0])
+                .chain(&content)
+                .finalize(),
+        ))
+        .with_extension("json");
+
+    let found = match crate::fs::read_json::<Vec<LocalImport>, _>(&found_path) {
+        Ok(found) => found,
+        Err(_) => {
+            let found = find_imports(path, &content);
+            if let Err(err) = crate::fs::write_json(&found_path, &found, true) {
+                crate::shell::warn(
+                    &mut *stderr,
+                    format_args!("Could not update the config cache: {:#}", err),
+                )?;
+            }
+            found
+        }
+    };
+
+    for import in found {
+        match import {
+            LocalImport::File { path, code } => {
+                collect_imports(stderr, cache_dir, &path, code, imports)?;
+            }
+            LocalImport::Env(name) => {
+                imports.insert(
+                    format!("env:{}", name),
+                    env::var(name).ok().map(String::into_bytes),
+                );
+            }
+        }
+    }
+    return Ok(());
+
+    /// Files that cannot be parsed are left to the evaluation to report.
+    fn find_imports(path: &Path, content: &[u8]) -> Vec<LocalImport> {
+        let expr = match str::from_utf8(content)
+            .ok()
+            .and_then(|content| dhall::syntax::parse_expr(content).ok())
+        {
+            Some(expr) => expr,
+            None => return vec![],
+        };
+
+        let mut found = vec![];
+        visit(&expr, &mut found);
+
+        let dir = path.parent().unwrap_or(path);
+
+        return found
+            .into_iter()
+            .flat_map(|import| match &import.location {
+                ImportTarget::Local(prefix, FilePath { file_path }) => {
+                    let base = match prefix {
+                        FilePrefix::Absolute => PathBuf::from("/"),
+                        FilePrefix::Here => dir.to_owned(),
+                        FilePrefix::Parent => dir.join(".."),
+                        FilePrefix::Home => dirs_next::home_dir()?,
+                    };
+                    Some(LocalImport::File {
+                        path: file_path.iter().fold(base, |path, s| path.join(s)),
+                        code: import.mode == ImportMode::Code,
+                    })
+                }
+                ImportTarget::Env(name) => Some(LocalImport::Env(name.clone())),
+                ImportTarget::Remote(_) | ImportTarget::Missing => None,
+            })
+            .collect();
+
+        fn visit<'a>(expr: &'a Expr, found: &mut Vec<&'a Import<Expr>>) {
+            if let ExprKind::Import(import) = expr.kind() {
+                found.push(import);
+            }
+            let _ = expr.kind().traverse_ref(|expr| {
+                visit(expr, found);
+                Ok::<_, Infallible>(())
+            });
+        }
+    }
+}
+
+fn quote(s: impl AsRef<str>) -> impl fmt::Display {
+    InterpolatedText::<Infallible>::from(s.as_ref().to_owned())
+}
+
+fn map_annot(key: SimpleType, value: SimpleType) -> SimpleType {
+    SimpleType::List(Box::new(SimpleType::Record(
+        hashmap!("mapKey".to_owned() => key, "mapValue".to_owned() => value),
+    )))
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, StaticType)]
+pub(crate) struct Detected {
+    pub(crate) service: Option<String>,
+    pub(crate) contest: Option<String>,
+    pub(crate) problem: Option<String>,
+    pub(crate) language: Option<String>,
+}
+
+impl Detected {
+    pub(crate) fn load_and_eval(
+        stderr: impl WriteColor,
+        cwd: &Path,
+        path: &str,
+    ) -> anyhow::Result<Self> {
+        let rel_path_components = cwd
+            .strip_prefix({
+                let path = Path::new(path);
+                path.parent().unwrap_or(path)
+            })
+            .map(|rel_path| {
+                rel_path
+                    .iter()
+                    .map(|comp| {
+                        comp.to_str()
+                            .expect("components of a UTF-8 path should also be UTF-8")
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        if is_yaml(path) {
+            return yaml::detect(path, &rel_path_components);
+        }
+
+        let rel_path_components = rel_path_components.into_iter().map(quote).join(", ");
+
+        let rel_path_components = if rel_path_components.is_empty() {
+            "[] : List Text".to_owned()
+        } else {
+            format!("[{}]", rel_path_components)
+        };
+
+        let expr = format!(
+            r"let relativePathSegments = {}
+
+let config = {}
+
+in  {{ service = config.detectServiceFromRelativePathSegments relativePathSegments
+    , contest = config.detectContestFromRelativePathSegments relativePathSegments
+    , problem = config.detectProblemFromRelativePathSegments relativePathSegments
+    , language = config.detectLanguageFromRelativePathSegments relativePathSegments
+    }}
+",
+            rel_path_components, path,
+        );
+
+        eval_cached(stderr, path, &expr, |expr| {
+            serde_dhall::from_str(expr)
+                .static_type_annotation()
+                .parse()
+                .with_context(|| {
+                    format!(
+                        "Could not evaluate `detect*FromRelativePathSegments` in `{}`",
+                        path,
+                    )
+                })
+        })
+    }
+
+    pub(crate) fn merge_with_cli_options(
+        &self,
+        service: Option<PlatformKind>,
+        contest: Option<&str>,
+        problem: Option<&str>,
+        language: Option<&str>,
+        mode: Mode,
+    ) -> anyhow::Result<(Target, String)> {
+        let service = service.map(Ok).unwrap_or_else(|| {
+            self.service
+                .as_deref()
+                .with_context(|| "`service` was not detected. Specify with `--service`")?
+                .parse()
+                .with_context(|| {
+                    "`detectServiceFromRelativePathSegments` returned unrecognized `service`. \
+                     Specify the correct `service` with `--service`"
+                })
+        })?;
+
+        let contest = contest
+            .or_else(|| self.contest.as_deref())
+            .map(ToOwned::to_owned);
+
+        let problem = problem
+            .map(Ok)
+            .unwrap_or_else(|| {
+                self.problem
+                    .as_deref()
+                    .with_context(|| "`problem` was not detected. Specify with `--problem`")
+            })?
+            .to_owned();
+
+        let language = language
+            .map(Ok)
+            .unwrap_or_else(|| {
+                self.language
+                    .as_deref()
+                    .with_context(|| "`language` was not detected. Specify with `--language`")
+            })?
+            .to_owned();
+
+        let target = Target {
+            service,
+            contest,
+            problem,
+            mode,
+        };
+
+        Ok((target, language))
+    }
+
+    pub(crate) fn parse_service(&self) -> anyhow::Result<Option<PlatformKind>> {
+        self.service
+            .as_deref()
+            .map(str::parse)
+            .transpose()
+            .with_context(|| {
+                "Specified invalid `service` by `detectServiceFromRelativePathSegments`"
+            })
+    }
+}
+
+/// Commands run at the lifecycle points, in order.
+#[allow(non_snake_case)] // same as the field names in Dhall
+#[derive(Debug, Default, Deserialize, Serialize, StaticType)]
+pub(crate) struct Hooks {
+    /// After `retrieve testcases` writes the test files.
+    pub(crate) afterRetrieveTestcases: Vec<Command>,
+    /// Before `submit` reads the source file. A failure aborts the submission.
+    pub(crate) beforeSubmit: Vec<Command>,
+    /// After `judge` passes all of the test cases.
+    pub(crate) afterJudgeAccepted: Vec<Command>,
+    /// After `judge` fails.
+    pub(crate) afterJudgeFailure: Vec<Command>,
+}
+
+#[derive(Debug, Deserialize, Serialize, StaticType, Clone)]
+pub(crate) enum Command {
+    Args(Vec<String>),
+    Script(Script),
+}
+
+#[derive(Debug, Deserialize, Serialize, StaticType, Clone)]
+pub(crate) struct Script {
+    pub(crate) program: String,
+    pub(crate) extension: String,
+    pub(crate) content: String,
+}
+
+#[allow(non_snake_case)] // same as the field names in Dhall
+#[derive(Debug, Deserialize, Serialize)]
+pub(crate) struct Language {
+    pub(crate) src: String,
+    pub(crate) transpile: Option<Compile>,
+    pub(crate) compile: Option<Compile>,
+    pub(crate) run: Command,
+    pub(crate) languageId: Option<String>,
+    /// Environment variables for `transpile`, `compile`, and `run`.
+    #[serde(default)]
+    pub(crate) env: BTreeMap<String, String>,
+    /// Working directory for `transpile`, `compile`, and `run`, relative to the config file.
+    #[serde(default)]
+    pub(crate) cwd: Option<String>,
+    /// Template for `src`, relative to the config file. Instantiated by `retrieve testcases`.
+    #[serde(default)]
+    pub(crate) template: Option<String>,
+}
+
+impl Language {
+    /// Makes `transpile` and `compile` inherit `env` and `cwd` unless they override them.
+    fn inherit_env_and_cwd(&mut self) {
+        for compile in self.transpile.iter_mut().chain(&mut self.compile) {
+            for (key, value) in &self.env {
+                compile
+                    .env
+                    .entry(key.clone())
+                    .or_insert_with(|| value.clone());
+            }
+            if compile.cwd.is_none() {
+                compile.cwd = self.cwd.clone();
+            }
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub(crate) struct Compile {
+    pub(crate) command: Command,
+    pub(crate) output: String,
+    #[serde(default)]
+    pub(crate) env: BTreeMap<String, String>,
+    #[serde(default)]
+    pub(crate) cwd: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct Target {
+    pub(crate) service: PlatformKind,
+    pub(crate) contest: Option<String>,
+    pub(crate) problem: String,
+    pub(crate) mode: Mode,
+}
+
+/// `Service` in the schema.
+const SERVICE_TYPE: &str = "< Aoj | Atcoder | Codeforces | Yukicoder >";
+
+/// `Service` in the schema before AOJ was added, which is still imported by existing configs with `sha256`.
+const LEGACY_SERVICE_TYPE: &str = "< Atcoder | Codeforces | Yukicoder >";
+
+impl Target {
+    fn to_dhall_expr(&self, service_type: &str) -> String {
+        format!(
+            r"let Service = {}
+
+let CaseConvertedText =
+      {{ lowercase : Text
+      , uppercase : Text
+      , snakeCase : Text
+      , kebabCase : Text
+      , mixedCase : Text
+      , pascalCase : Text
+      }}
+
+let Mode = < Debug | Release >
+
+in  {{ service = Service.{}
+    , contest = {}
+    , problem =
+        {{ lowercase = {}
+        , uppercase = {}
+        , snakeCase = {}
+        , kebabCase = {}
+        , mixedCase = {}
+        , pascalCase = {}
+        }}
+    , mode = Mode.{}
+    }}
+",
+            service_type,
+            self.service.to_pascal_case_str(),
+            if let Some(contest) = &self.contest {
+                format!(
+                    r"Some {{ lowercase =  {}, uppercase =  {}, snakeCase =  {}, kebabCase =  {}, mixedCase =  {}, pascalCase = {} }}",
+                    quote(contest.to_lowercase()),
+                    quote(contest.to_uppercase()),
+                    quote(contest.to_snake_case()),
+                    quote(contest.to_kebab_case()),
+                    quote(contest.to_mixed_case()),
+                    quote(contest.to_camel_case()),
+                )
+            } else {
+                "None CaseConvertedText".to_owned()
+            },
+            quote(self.problem.to_lowercase()),
+            quote(self.problem.to_uppercase()),
+            quote(self.problem.to_snake_case()),
+            quote(self.problem.to_kebab_case()),
+            quote(self.problem.to_mixed_case()),
+            quote(self.problem.to_camel_case()),
+            match self.mode {
+                Mode::Debug => "Debug",
+                Mode::Release => "Release",
+            },
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+pub(crate) enum Mode {
+    Debug,
+    Release,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Language, Mode, Target};
+    use filetime::FileTime;
+    use snowchains_core::web::PlatformKind;
+    use std::{cell::Cell, fs};
+    use termcolor::NoColor;
+
+    #[test]
+    fn eval_cached() -> anyhow::Result<()> {
+        let tempdir = tempfile::Builder::new()
+            .prefix("snowchains-test-")
+            .tempdir()?;
+
+        let config_path = tempdir.path().join("snowchains.dhall");
+        let import_path = tempdir.path().join("x.dhall");
+        fs::write(&config_path, "{ x = ./x.dhall }")?;
+        fs::write(&import_path, "1")?;
+        let config_path = config_path.to_str().unwrap();
+
+        let evaluations = Cell::new(0);
+        let eval = || {
+            super::eval_cached(NoColor::new(vec![]), config_path, "expr", |_| {
+                evaluations.set(evaluations.get() + 1);
+                Ok(evaluations.get())
+            })
+        };
+
+        assert_eq!(1, eval()?);
+        assert_eq!(1, eval()?);
+        assert_eq!(1, evaluations.get());
+
+        fs::write(&import_path, "2")?;
+        assert_eq!(2, eval()?);
+        assert_eq!(2, eval()?);
+        assert_eq!(2, evaluations.get());
+        Ok(())
+    }
+
+    #[test]
+    fn eval_cached_warns_on_write_failures() -> anyhow::Result<()> {
+        let tempdir = tempfile::Builder::new()
+            .prefix("snowchains-test-")
+            .tempdir()?;
+
+        let config_path = tempdir.path().join("snowchains.dhall");
+        fs::write(&config_path, "{=}")?;
+        // `.snowchains/config-cache` cannot be created.
+        fs::write(tempdir.path().join(".snowchains"), "")?;
+
+        let mut stderr = NoColor::new(vec![]);
+        let value = super::eval_cached(&mut stderr, config_path.to_str().unwrap(), "expr", |_| {
+            Ok(42)
+        })?;
+
+        assert_eq!(42, value);
+        let stderr = String::from_utf8(stderr.into_inner())?;
+        assert!(
+            stderr.starts_with("warning: Could not update the config cache"),
+            "{}",
+            stderr,
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn eval_language() -> anyhow::Result<()> {
+        let eval = |schema: &str, service| -> anyhow::Result<_> {
+            let tempdir = tempfile::Builder::new()
+                .prefix("snowchains-test-")
+                .tempdir()?;
+
+            fs::copy(schema, tempdir.path().join("schema.dhall"))?;
+
+            let config_path = tempdir.path().join("snowchains.dhall");
+            fs::write(
+                &config_path,
+                r#"let Snowchains = ./schema.dhall
+
+in  { languages =
+        λ(target : Snowchains.Target) →
+          toMap
+            { rust =
+              { src =
+                  "${Snowchains.Service/lowercase
+                       target.service}/${target.problem.kebabCase}.rs"
+              , transpile = None Snowchains.Compile
+              , compile = None Snowchains.Compile
+              , run = Snowchains.Command.Args [ "./a" ]
+              , languageId = None Text
+              }
+            }
+    }
+"#,
+            )?;
+
+            let target = Target {
+                service,
+                contest: None,
+                problem: "a".to_owned(),
+                mode: Mode::Debug,
+            };
+
+            let Language { src, .. } = super::eval_language(
+                NoColor::new(vec![]),
+                config_path.to_str().unwrap(),
+                &target,
+                "rust",
+            )?;
+            Ok(src)
+        };
+
+        let schema = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/resources/config/schema/Snowchains/package.dhall",
+        );
+        assert_eq!("atcoder/a.rs", eval(schema, PlatformKind::Atcoder)?);
+        assert_eq!("aoj/a.rs", eval(schema, PlatformKind::Aoj)?);
+
+        // The schema before AOJ was added, which existing configs import with `sha256`.
+        let old_schema = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/schema-4b0a18a.dhall",
+        );
+        assert_eq!("atcoder/a.rs", eval(old_schema, PlatformKind::Atcoder)?);
+        assert_eq!("yukicoder/a.rs", eval(old_schema, PlatformKind::Yukicoder)?);
+        assert!(eval(old_schema, PlatformKind::Aoj).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn evict() -> anyhow::Result<()> {
+        let tempdir = tempfile::Builder::new()
+            .prefix("snowchains-test-")
+            .tempdir()?;
+
+        for (i, name) in ["a.json", "b.json", "c.json", "d.txt"].iter().enumerate() {
+            let path = tempdir.path().join(name);
+            fs::write(&path, "")?;
+            filetime::set_file_mtime(&path, FileTime::from_unix_time(i as _, 0))?;
+        }
+
+        super::evict(tempdir.path(), 2)?;
+
+        let mut names = fs::read_dir(tempdir.path())?
+            .map(|e| Ok(e?.file_name().into_string().unwrap()))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        names.sort();
+        assert_eq!(["b.json", "c.json", "d.txt"], *names);
+        Ok(())
+    }
+}
@@ -0,0 +1,423 @@
+//! `snowchains.yaml`, a declarative alternative to `snowchains.dhall`.
+//!
+//! ```yaml
+//! detect:
+//!   - "{service}/{contest}/{language}"
+//! languages:
+//!   cpp:
+//!     src: "{service}/{contest.kebabCase|problems}/cpp/{problem.kebabCase}.cpp"
+//!     compile:
+//!       command: [g++, "{src}", -o, "{output}"]
+//!       output: "{service}/{contest.kebabCase|problems}/cpp/target/{problem.kebabCase}"
+//!     run: ["{compile.output}"]
+//!     languageId:
+//!       atcoder: "4003"
+//!       codeforces: "54"
+//! ```
+
+use super::{Command, Compile, Detected, Hooks, Language, Mode, Script, Target};
+use anyhow::{bail, Context as _};
+use heck::{CamelCase as _, KebabCase as _, MixedCase as _, SnakeCase as _};
+use indexmap::IndexMap;
+use itertools::Itertools as _;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Config {
+    /// Patterns of the path segments relative to the config file, tried in order.
+    #[serde(default)]
+    detect: Vec<String>,
+    #[serde(default)]
+    languages: IndexMap<String, LanguageTemplate>,
+    #[serde(default)]
+    xtask: IndexMap<String, CommandTemplate>,
+    #[serde(default)]
+    hooks: HooksTemplate,
+}
+
+#[allow(non_snake_case)] // same as the field names in Dhall
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct LanguageTemplate {
+    src: String,
+    #[serde(default)]
+    transpile: Option<CompileTemplate>,
+    #[serde(default)]
+    compile: Option<CompileTemplate>,
+    run: CommandTemplate,
+    #[serde(default)]
+    languageId: Option<LanguageId>,
+    #[serde(default)]
+    env: BTreeMap<String, String>,
+    #[serde(default)]
+    cwd: Option<String>,
+    #[serde(default)]
+    template: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CompileTemplate {
+    command: CommandTemplate,
+    output: String,
+    #[serde(default)]
+    env: BTreeMap<String, String>,
+    #[serde(default)]
+    cwd: Option<String>,
+}
+
+/// Unlike `languages`, the commands are not expanded. They receive the target as environment variables.
+#[allow(non_snake_case)] // same as the field names in Dhall
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct HooksTemplate {
+    afterRetrieveTestcases: Vec<CommandTemplate>,
+    beforeSubmit: Vec<CommandTemplate>,
+    afterJudgeAccepted: Vec<CommandTemplate>,
+    afterJudgeFailure: Vec<CommandTemplate>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum CommandTemplate {
+    Args(Vec<String>),
+    Script(Script),
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum LanguageId {
+    Common(String),
+    /// Keyed by lowercase service names.
+    PerService(BTreeMap<String, String>),
+}
+
+fn load(path: &str) -> anyhow::Result<Config> {
+    crate::fs::read_yaml(path)
+}
+
+pub(super) fn detect(path: &str, rel_path_components: &[&str]) -> anyhow::Result<Detected> {
+    for rule in load(path)?.detect {
+        if let Some(detected) = match_rule(&rule, rel_path_components)
+            .with_context(|| format!("Invalid detection rule in `{}`", path))?
+        {
+            return Ok(detected);
+        }
+    }
+    Ok(Detected::default())
+}
+
+/// Matches `rule` against the leading segments.
+///
+/// Each segment of `rule` is `{service}`, `{contest}`, `{problem}`, `{language}`, `*`, or a literal.
+fn match_rule(rule: &str, rel_path_components: &[&str]) -> anyhow::Result<Option<Detected>> {
+    let patterns = rule
+        .split('/')
+        .filter(|s| !s.is_empty() && *s != ".")
+        .collect::<Vec<_>>();
+
+    if patterns.len() > rel_path_components.len() {
+        return Ok(None);
+    }
+
+    let mut detected = Detected::default();
+
+    for (&pattern, &component) in patterns.iter().zip(rel_path_components) {
+        let field = match pattern {
+            "*" => continue,
+            "{service}" => &mut detected.service,
+            "{contest}" => &mut detected.contest,
+            "{problem}" => &mut detected.problem,
+            "{language}" => &mut detected.language,
+            pattern if pattern.contains(['{', '}']) => {
+                bail!("Unknown placeholder `{}` in {:?}", pattern, rule);
+            }
+            pattern if pattern == component => continue,
+            _ => return Ok(None),
+        };
+        *field = Some(component.to_owned());
+    }
+
+    Ok(Some(detected))
+}
+
+pub(super) fn language(path: &str, target: &Target, name: &str) -> anyhow::Result<Language> {
+    let mut languages = load(path)?.languages;
+
+    let expected_names = languages.keys().join(", ");
+
+    languages
+        .swap_remove(name)
+        .with_context(|| {
+            format!(
+                "The language `{}` not found. Expected one of [{}]",
+                name, expected_names,
+            )
+        })?
+        .render(target)
+        .with_context(|| format!("Could not expand `languages.{}` in `{}`", name, path))
+}
+
+/// Like `hooks`, the commands are not expanded.
+pub(super) fn xtask(path: &str) -> anyhow::Result<IndexMap<String, Command>> {
+    Ok(load(path)?
+        .xtask
+        .into_iter()
+        .map(|(name, command)| (name, command.into_verbatim()))
+        .collect())
+}
+
+pub(super) fn hooks(path: &str) -> anyhow::Result<Hooks> {
+    let HooksTemplate {
+        afterRetrieveTestcases,
+        beforeSubmit,
+        afterJudgeAccepted,
+        afterJudgeFailure,
+    } = load(path)?.hooks;
+
+    let verbatim = |commands: Vec<CommandTemplate>| -> Vec<_> {
+        commands
+            .into_iter()
+            .map(CommandTemplate::into_verbatim)
+            .collect()
+    };
+
+    Ok(Hooks {
+        afterRetrieveTestcases: verbatim(afterRetrieveTestcases),
+        beforeSubmit: verbatim(beforeSubmit),
+        afterJudgeAccepted: verbatim(afterJudgeAccepted),
+        afterJudgeFailure: verbatim(afterJudgeFailure),
+    })
+}
+
+impl LanguageTemplate {
+    fn render(self, target: &Target) -> anyhow::Result<Language> {
+        let Self {
+            src,
+            transpile,
+            compile,
+            run,
+            languageId,
+            env,
+            cwd,
+            template,
+        } = self;
+
+        let mut vars = Variables::new(target);
+
+        let src = vars.render(&src)?;
+        vars.insert("src", &src);
+
+        let transpile = transpile.map(|t| t.render(&vars)).transpose()?;
+        if let Some(transpile) = &transpile {
+            vars.insert("transpile.output", &transpile.output);
+        }
+
+        let compile = compile.map(|c| c.render(&vars)).transpose()?;
+        if let Some(compile) = &compile {
+            vars.insert("compile.output", &compile.output);
+        }
+
+        Ok(Language {
+            src,
+            transpile,
+            compile,
+            run: run.render(&vars)?,
+            languageId: languageId.and_then(|id| match id {
+                LanguageId::Common(id) => Some(id),
+                LanguageId::PerService(mut ids) => ids.remove(target.service.to_kebab_case_str()),
+            }),
+            env: vars.render_values(env)?,
+            cwd: cwd.map(|cwd| vars.render(&cwd)).transpose()?,
+            template: template.map(|t| vars.render(&t)).transpose()?,
+        })
+    }
+}
+
+impl CompileTemplate {
+    fn render(self, vars: &Variables) -> anyhow::Result<Compile> {
+        let output = vars.render(&self.output)?;
+
+        let mut vars = vars.clone();
+        vars.insert("output", &output);
+
+        Ok(Compile {
+            command: self.command.render(&vars)?,
+            output,
+            env: vars.render_values(self.env)?,
+            cwd: self.cwd.map(|cwd| vars.render(&cwd)).transpose()?,
+        })
+    }
+}
+
+impl CommandTemplate {
+    fn into_verbatim(self) -> Command {
+        match self {
+            Self::Args(args) => Command::Args(args),
+            Self::Script(script) => Command::Script(script),
+        }
+    }
+
+    fn render(self, vars: &Variables) -> anyhow::Result<Command> {
+        Ok(match self {
+            Self::Args(args) => Command::Args(
+                args.iter()
+                    .map(|arg| vars.render(arg))
+                    .collect::<anyhow::Result<_>>()?,
+            ),
+            Self::Script(Script {
+                program,
+                extension,
+                content,
+            }) => Command::Script(Script {
+                program: vars.render(&program)?,
+                extension,
+                content: vars.render(&content)?,
+            }),
+        })
+    }
+}
+
+/// Variables for the templates, which are the fields of `Target` in Dhall joined with `.`.
+///
+/// `service`, `contest`, `problem` and `mode` themselves are also available.
+#[derive(Clone)]
+struct Variables(BTreeMap<String, String>);
+
+impl Variables {
+    fn new(target: &Target) -> Self {
+        let mut vars = Self(BTreeMap::new());
+
+        vars.insert_case_converted("service", target.service.to_kebab_case_str());
+        if let Some(contest) = &target.contest {
+            vars.insert_case_converted("contest", contest);
+        }
+        vars.insert_case_converted("problem", &target.problem);
+        vars.insert_case_converted(
+            "mode",
+            match target.mode {
+                Mode::Debug => "debug",
+                Mode::Release => "release",
+            },
+        );
+
+        vars
+    }
+
+    fn insert(&mut self, name: &str, value: &str) {
+        self.0.insert(name.to_owned(), value.to_owned());
+    }
+
+    /// Inserts the fields of `CaseConvertedText`.
+    fn insert_case_converted(&mut self, name: &str, value: &str) {
+        self.insert(name, value);
+        for (case, value) in &[
+            ("lowercase", value.to_lowercase()),
+            ("uppercase", value.to_uppercase()),
+            ("snakeCase", value.to_snake_case()),
+            ("kebabCase", value.to_kebab_case()),
+            ("mixedCase", value.to_mixed_case()),
+            ("pascalCase", value.to_camel_case()),
+        ] {
+            self.insert(&format!("{}.{}", name, case), value);
+        }
+    }
+
+    /// Expands `{name}` and `{name|default}`. `{{` and `}}` are `{` and `}`.
+    fn render(&self, template: &str) -> anyhow::Result<String> {
+        let mut rendered = "".to_owned();
+        let mut rest = template;
+
+        while let Some(i) = rest.find(['{', '}']) {
+            rendered += &rest[..i];
+            rest = &rest[i..];
+
+            if let Some(s) = rest.strip_prefix("{{") {
+                rendered.push('{');
+                rest = s;
+            } else if let Some(s) = rest.strip_prefix("}}") {
+                rendered.push('}');
+                rest = s;
+            } else if rest.starts_with('}') {
+                bail!("Unmatched `}}` in {:?}", template);
+            } else {
+                let end = rest
+                    .find('}')
+                    .with_context(|| format!("Unmatched `{{` in {:?}", template))?;
+                let (name, default) = match rest[1..end].split_once('|') {
+                    Some((name, default)) => (name.trim(), Some(default)),
+                    None => (rest[1..end].trim(), None),
+                };
+                rendered += self
+                    .0
+                    .get(name)
+                    .map(|s| &**s)
+                    .or(default)
+                    .with_context(|| {
+                        format!(
+                            "`{}` is not available in {:?}. Expected one of [{}]",
+                            name,
+                            template,
+                            self.0.keys().format(", "),
+                        )
+                    })?;
+                rest = &rest[end + 1..];
+            }
+        }
+
+        rendered += rest;
+        Ok(rendered)
+    }
+
+    fn render_values(
+        &self,
+        map: BTreeMap<String, String>,
+    ) -> anyhow::Result<BTreeMap<String, String>> {
+        map.into_iter()
+            .map(|(key, value)| Ok((key, self.render(&value)?)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{match_rule, Variables};
+    use crate::config::{Mode, Target};
+    use snowchains_core::web::PlatformKind;
+
+    #[test]
+    fn detect_from_path_segments() -> anyhow::Result<()> {
+        let detected =
+            match_rule("{service}/*/{contest}", &["atcoder", "x", "abc100", "rs"])?.unwrap();
+        assert_eq!(detected.service.as_deref(), Some("atcoder"));
+        assert_eq!(detected.contest.as_deref(), Some("abc100"));
+        assert_eq!(detected.language, None);
+
+        assert!(match_rule("{service}/cpp", &["atcoder", "rs"])?.is_none());
+        assert!(match_rule("{service}/{contest}", &["atcoder"])?.is_none());
+        assert!(match_rule("{service}/{task}", &["atcoder", "a"]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn render_templates() -> anyhow::Result<()> {
+        let vars = Variables::new(&Target {
+            service: PlatformKind::Yukicoder,
+            contest: None,
+            problem: "no_1".to_owned(),
+            mode: Mode::Release,
+        });
+
+        assert_eq!(
+            vars.render("{service}/{contest.kebabCase|problems}/{problem.pascalCase}-{mode}")?,
+            "yukicoder/problems/No1-release",
+        );
+        assert_eq!(vars.render("{{{problem}}}")?, "{no_1}");
+        assert!(vars.render("{contest}").is_err());
+        assert!(vars.render("{problem").is_err());
+        assert!(vars.render("problem}").is_err());
+        Ok(())
+    }
+}
@@ -1,11 +1,10 @@
 use anyhow::Context as _;
 use serde::{de::DeserializeOwned, Serialize};
-use std::{fs::Metadata, path::Path};
+use std::path::Path;
 
-pub(crate) fn metadata(path: impl AsRef<Path>) -> anyhow::Result<Metadata> {
+pub(crate) fn read(path: impl AsRef<Path>) -> anyhow::Result<Vec<u8>> {
     let path = path.as_ref();
-    std::fs::metadata(path)
-        .with_context(|| format!("Could not get the metadata of `{}`", path.display()))
+    std::fs::read(path).with_context(|| format!("Could not read `{}`", path.display()))
 }
 
 pub(crate) fn read_to_string(path: impl AsRef<Path>) -> anyhow::Result<String> {
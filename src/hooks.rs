@@ -0,0 +1,94 @@
+use crate::config;
+use crate::target_env::TargetEnv;
+use anyhow::{bail, Context as _};
+use snowchains_core::color_spec;
+use std::{
+    collections::BTreeMap,
+    io::Write as _,
+    path::Path,
+    process::{self, Stdio},
+};
+use termcolor::{Color, WriteColor};
+
+/// A lifecycle point and the data passed to its commands.
+pub(crate) struct Hook<'a> {
+    /// Field name in `hooks`, also given as `$SNOWCHAINS_HOOK`.
+    pub(crate) name: &'static str,
+    pub(crate) commands: &'a [config::Command],
+    /// Directory of the config file, which is the working directory of the commands.
+    pub(crate) base_dir: &'a Path,
+    pub(crate) target: TargetEnv<'a>,
+    /// JSON value written to the stdin of the commands.
+    pub(crate) payload: String,
+}
+
+impl Hook<'_> {
+    /// Runs the commands in order. Stops at the first one that fails.
+    ///
+    /// Both the stdout and the stderr of the commands go to `stderr_process_redirection` so that they do not mix with
+    /// the output of `--json` and `--junit`.
+    pub(crate) fn run(
+        &self,
+        mut stderr: impl WriteColor,
+        stderr_process_redirection: fn() -> Stdio,
+    ) -> anyhow::Result<()> {
+        let env = self.env();
+
+        for command in self.commands {
+            let (cmd, tempfile) =
+                crate::judge::command_expression(command.clone(), self.base_dir, None, &env)?;
+
+            let shell_escaped = crate::judge::shell_escape_args(&cmd.program, &cmd.args);
+
+            stderr.set_color(color_spec!(Bold, Fg(Color::Magenta)))?;
+            write!(stderr, "Running `{}` hook:", self.name)?;
+            stderr.reset()?;
+            writeln!(stderr, " {}", shell_escaped)?;
+            stderr.flush()?;
+
+            let mut child = process::Command::new(&cmd.program)
+                .args(&cmd.args)
+                .current_dir(&cmd.cwd)
+                .envs(&cmd.env)
+                .stdin(Stdio::piped())
+                .stdout(stderr_process_redirection())
+                .stderr(stderr_process_redirection())
+                .spawn()
+                .with_context(|| format!("Could not execute {}", shell_escaped))?;
+
+            // The command may exit without reading the whole input.
+            let _ = child
+                .stdin
+                .take()
+                .expect("specified `Stdio::piped()`")
+                .write_all(self.payload.as_ref());
+
+            let status = child.wait()?;
+
+            if let Some(tempfile) = tempfile {
+                tempfile.close()?;
+            }
+
+            if !status.success() {
+                bail!(
+                    "`{}` hook {} {}",
+                    self.name,
+                    shell_escaped,
+                    if let Some(code) = status.code() {
+                        format!("exited with code {}", code)
+                    } else {
+                        "was terminated by signal".to_owned()
+                    },
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    fn env(&self) -> BTreeMap<String, String> {
+        let mut env = self.target.to_env();
+        env.insert("SNOWCHAINS_HOOK".to_owned(), self.name.to_owned());
+        env
+    }
+}
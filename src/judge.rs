@@ -1,23 +1,30 @@
 use crate::config;
-use anyhow::bail;
+use anyhow::{bail, Context as _};
 use az::SaturatingAs as _;
+use heck::KebabCase as _;
 use human_size::{Byte, Size};
 use indicatif::ProgressDrawTarget;
 use itertools::Itertools as _;
-use maplit::btreemap;
+use sha2::{Digest as _, Sha256};
 use snowchains_core::{
-    color_spec, judge::CommandExpression, testsuite::TestSuite, web::PlatformKind,
+    color_spec,
+    judge::{CommandExpression, JudgeOptions, Sandbox},
+    testsuite::{Additional, BatchTestCase, InteractiveTestCase, PartialBatchTestCase, TestSuite},
+    web::PlatformKind,
 };
 use std::{
-    collections::HashSet,
+    collections::{BTreeMap, HashSet},
     ffi::OsStr,
+    future::Future,
     io::Write as _,
     iter, mem,
     ops::Deref,
     path::{Path, PathBuf},
     process::Stdio,
 };
+use tempfile::NamedTempFile;
 use termcolor::{Color, WriteColor};
+use url::Url;
 
 pub(crate) struct Args<W1, W2> {
     pub(crate) stdout: W1,
@@ -34,18 +41,101 @@ pub(crate) struct Args<W1, W2> {
     pub(crate) transpile: Option<config::Compile>,
     pub(crate) compile: Option<config::Compile>,
     pub(crate) run: config::Command,
+    pub(crate) env: BTreeMap<String, String>,
+    pub(crate) cwd: Option<String>,
     pub(crate) test_case_names: Option<HashSet<String>>,
     pub(crate) display_limit: Size,
+    pub(crate) report: Report,
+    pub(crate) sandbox: Option<Sandbox>,
+    pub(crate) judge_options: JudgeOptions,
+    pub(crate) watch: bool,
+    pub(crate) build_cache: BuildCache,
+    pub(crate) hooks: config::Hooks,
 }
 
-pub(crate) fn judge(args: Args<impl WriteColor, impl WriteColor>) -> anyhow::Result<()> {
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Report {
+    Pretty,
+    Json,
+    Junit,
+}
+
+pub(crate) fn judge(mut args: Args<impl WriteColor, impl WriteColor>) -> anyhow::Result<()> {
+    if args.watch {
+        return watch(args);
+    }
+    let progress_draw_target =
+        mem::replace(&mut args.progress_draw_target, ProgressDrawTarget::hidden());
+    judge_once(&mut args, progress_draw_target, tokio::signal::ctrl_c)
+}
+
+/// Judges again every time the source file or the test suite file changes.
+///
+/// A change during a build or a run cancels it.
+fn watch(mut args: Args<impl WriteColor, impl WriteColor>) -> anyhow::Result<()> {
+    let (_, test_suite_path) = test_suite_path(
+        &args.base_dir,
+        args.service,
+        args.contest.as_deref(),
+        &args.problem,
+    );
+    let src = {
+        let src = Path::new(&args.src);
+        args.base_dir.join(src.strip_prefix(".").unwrap_or(src))
+    };
+
+    let is_tty = !args.progress_draw_target.is_hidden();
+
+    crate::watch::start(vec![src, test_suite_path])?;
+
+    loop {
+        if is_tty {
+            // Clears the screen and moves the cursor to the top-left.
+            write!(args.stderr, "\x1b[2J\x1b[H")?;
+        }
+
+        let progress_draw_target = if is_tty {
+            ProgressDrawTarget::stderr()
+        } else {
+            ProgressDrawTarget::hidden()
+        };
+
+        match judge_once(&mut args, progress_draw_target, crate::watch::interruption) {
+            Ok(()) => {}
+            Err(err) if crate::watch::received_ctrl_c() => return Err(err),
+            Err(_) if crate::watch::take_change() => continue,
+            Err(err) => {
+                writeln!(args.stderr)?;
+                args.stderr.set_color(color_spec!(Bold, Fg(Color::Red)))?;
+                write!(args.stderr, "Error:")?;
+                args.stderr.reset()?;
+                writeln!(args.stderr, " {:?}", err)?;
+            }
+        }
+
+        writeln!(args.stderr)?;
+        args.stderr.set_color(color_spec!(Bold))?;
+        write!(args.stderr, "Watching for changes...")?;
+        args.stderr.reset()?;
+        writeln!(args.stderr, " (press Ctrl-c to quit)")?;
+        args.stderr.flush()?;
+
+        crate::watch::wait_for_change()?;
+    }
+}
+
+fn judge_once<C: 'static + Future<Output = tokio::io::Result<()>> + Send>(
+    args: &mut Args<impl WriteColor, impl WriteColor>,
+    progress_draw_target: ProgressDrawTarget,
+    ctrl_c: fn() -> C,
+) -> anyhow::Result<()> {
     let Args {
         stdout,
-        mut stderr,
+        stderr,
         stdin_process_redirection,
         stdout_process_redirection,
         stderr_process_redirection,
-        progress_draw_target,
+        progress_draw_target: _,
         base_dir,
         service,
         contest,
@@ -54,41 +144,63 @@ pub(crate) fn judge(args: Args<impl WriteColor, impl WriteColor>) -> anyhow::Res
         transpile,
         compile,
         run,
+        env,
+        cwd,
         test_case_names,
         display_limit,
+        report,
+        sandbox,
+        judge_options,
+        watch: _,
+        build_cache,
+        hooks,
     } = args;
+    let (service, display_limit) = (*service, *display_limit);
 
-    let test_suite_dir = base_dir
-        .join(".snowchains")
-        .join("tests")
-        .join(service.to_kebab_case_str())
-        .join(contest.as_deref().unwrap_or(""));
-    let test_suite_path = test_suite_dir.join(problem).with_extension("yml");
+    let (test_suite_dir, test_suite_path) =
+        test_suite_path(base_dir, service, contest.as_deref(), problem);
 
     let test_cases = match crate::fs::read_yaml(&test_suite_path)? {
-        TestSuite::Batch(test_sutie) => {
-            test_sutie.load_test_cases(&test_suite_dir, test_case_names, |_| {
-                unimplemented!("`SystemTestCases` is not impelemented");
-            })?
-        }
-        _ => todo!("currently only `Batch` is supported"),
+        TestSuite::Batch(test_sutie) => TestCases::Batch(test_sutie.load_test_cases(
+            &test_suite_dir,
+            test_case_names.clone(),
+            |url| load_system_test_cases(base_dir, &test_suite_path, url, test_case_names.as_ref()),
+        )?),
+        TestSuite::Interactive(test_suite) => TestCases::Interactive(
+            test_suite
+                .load_test_cases(test_case_names.clone())
+                .with_context(|| format!("Could not load `{}`", test_suite_path.display()))?,
+        ),
+        TestSuite::Unsubmittable => bail!(
+            "`{}` is an unsubmittable problem",
+            test_suite_path.display(),
+        ),
     };
 
     let redirections = (
-        stdin_process_redirection,
-        stdout_process_redirection,
-        stderr_process_redirection,
+        *stdin_process_redirection,
+        *stdout_process_redirection,
+        *stderr_process_redirection,
     );
 
     let mut newline = false;
 
-    for (action, msg) in &[(transpile, "Transpiling..."), (compile, "Compiling...")] {
+    for (action, msg) in &[(&*transpile, "Transpiling..."), (&*compile, "Compiling...")] {
         if let Some(action) = action {
             if mem::replace(&mut newline, true) {
                 writeln!(stderr)?;
             }
 
-            build(&mut stderr, &base_dir, &src, action, redirections, msg)?;
+            build(
+                &mut *stderr,
+                base_dir,
+                src,
+                action,
+                *build_cache,
+                redirections,
+                msg,
+                ctrl_c,
+            )?;
         }
     }
 
@@ -101,13 +213,222 @@ pub(crate) fn judge(args: Args<impl WriteColor, impl WriteColor>) -> anyhow::Res
     writeln!(stderr)?;
     stderr.flush()?;
 
-    let (cmd, tempfile) = match run {
+    let (mut cmd, tempfile) = command_expression(run.clone(), base_dir, cwd.as_deref(), env)?;
+    cmd.sandbox = *sandbox;
+
+    stderr.set_color(color_spec!(Bold, Fg(Color::Magenta)))?;
+    write!(stderr, "Test file:")?;
+    stderr.reset()?;
+    writeln!(stderr, " {}", test_suite_path.display())?;
+
+    stderr.set_color(color_spec!(Bold, Fg(Color::Magenta)))?;
+    write!(stderr, "Command:")?;
+    stderr.reset()?;
+    writeln!(stderr, " {}", shell_escape_args(&cmd.program, &cmd.args))?;
+
+    stderr.set_color(color_spec!(Bold, Fg(Color::Magenta)))?;
+    write!(stderr, "Working Directory:")?;
+    stderr.reset()?;
+    writeln!(stderr, " {}", cmd.cwd.display())?;
+
+    stderr.flush()?;
+
+    let outcome = match &test_cases {
+        TestCases::Batch(test_cases) => snowchains_core::judge::judge(
+            progress_draw_target,
+            ctrl_c,
+            &cmd,
+            test_cases,
+            *judge_options,
+        )?,
+        TestCases::Interactive(test_cases) => snowchains_core::judge::judge_interactive(
+            progress_draw_target,
+            ctrl_c,
+            &cmd,
+            test_cases,
+            *judge_options,
+        )?,
+    };
+
+    if let Some(tempfile) = tempfile {
+        tempfile.close()?;
+    }
+
+    writeln!(stderr)?;
+    stderr.flush()?;
+    match report {
+        Report::Pretty => outcome.print_pretty(
+            &mut *stdout,
+            Some(display_limit.into::<Byte>().value().saturating_as()),
+        )?,
+        Report::Json => {
+            writeln!(stdout, "{}", outcome.to_json())?;
+            stdout.flush()?;
+        }
+        Report::Junit => {
+            let name = iter::once(service.to_kebab_case_str())
+                .chain(contest.as_deref())
+                .chain(iter::once(&**problem))
+                .join("/");
+            write!(stdout, "{}", outcome.to_junit_xml(&name))?;
+            stdout.flush()?;
+        }
+    }
+
+    let result = outcome.error_on_fail();
+
+    let hook = crate::hooks::Hook {
+        name: if result.is_ok() {
+            "afterJudgeAccepted"
+        } else {
+            "afterJudgeFailure"
+        },
+        commands: if result.is_ok() {
+            &hooks.afterJudgeAccepted
+        } else {
+            &hooks.afterJudgeFailure
+        },
+        base_dir,
+        target: crate::target_env::TargetEnv {
+            workspace: Some(base_dir),
+            service: Some(service),
+            contest: contest.as_deref(),
+            problem: Some(problem),
+            mode: Some(build_cache.mode),
+            src: Some(&base_dir.join(&*src)),
+            test_suite: Some(&test_suite_path),
+            ..Default::default()
+        },
+        payload: outcome.to_json(),
+    };
+
+    if let Err(err) = hook.run(&mut *stderr, *stderr_process_redirection) {
+        stderr.set_color(color_spec!(Bold, Fg(Color::Yellow)))?;
+        write!(stderr, "warning:")?;
+        stderr.reset()?;
+        writeln!(stderr, " {:#}", err)?;
+        stderr.flush()?;
+    }
+
+    result
+}
+
+enum TestCases {
+    Batch(Vec<BatchTestCase>),
+    Interactive(Vec<InteractiveTestCase>),
+}
+
+/// Returns the directory and the path of the test suite file.
+pub(crate) fn test_suite_path(
+    base_dir: &Path,
+    service: PlatformKind,
+    contest: Option<&str>,
+    problem: &str,
+) -> (PathBuf, PathBuf) {
+    let test_suite_dir = base_dir
+        .join(".snowchains")
+        .join("tests")
+        .join(service.to_kebab_case_str())
+        .join(contest.unwrap_or(""));
+    let test_suite_path = test_suite_dir.join(problem).with_extension("yml");
+    (test_suite_dir, test_suite_path)
+}
+
+/// Loads the system test cases saved by `retrieve testcases --full`.
+///
+/// They are looked up in the directory next to the test suite file, or the one for `url` if
+/// specified. If `names` (from `--testcases`) is given, only those cases are loaded, and only they
+/// are required to have their expected outputs.
+fn load_system_test_cases(
+    base_dir: &Path,
+    test_suite_path: &Path,
+    url: Option<&Url>,
+    names: Option<&HashSet<String>>,
+) -> anyhow::Result<Vec<PartialBatchTestCase>> {
+    let dir = if let Some(url) = url {
+        let (service, contest, problem) = PlatformKind::contest_and_problem_index_from_url(url)?;
+        let (_, test_suite_path) = self::test_suite_path(
+            base_dir,
+            service,
+            contest.as_deref(),
+            &problem.to_kebab_case(),
+        );
+        test_suite_path.with_extension("")
+    } else {
+        test_suite_path.with_extension("")
+    };
+
+    if !dir.exists() {
+        bail!(
+            "System test cases not found at `{}`. Run `snowchains retrieve testcases --full` first",
+            dir.display(),
+        );
+    }
+
+    let cases = Additional::Text {
+        path: dir
+            .to_str()
+            .with_context(|| format!("The path is not valid UTF-8: {}", dir.display()))?
+            .into(),
+        r#in: "/in/*.txt".to_owned(),
+        out: "/out/*.txt".to_owned(),
+        timelimit: None,
+        r#match: None,
+    }
+    .load_test_cases(&dir, |_| unreachable!())?;
+
+    let cases = cases
+        .into_iter()
+        .filter(|case| match (names, &case.name) {
+            (Some(names), Some(name)) => names.contains(name),
+            _ => true,
+        })
+        .collect::<Vec<_>>();
+
+    if cases.is_empty() {
+        bail!("No system test cases found in `{}`", dir.display());
+    }
+
+    let missing = cases
+        .iter()
+        .filter(|case| case.out.is_none())
+        .flat_map(|case| case.name.as_deref())
+        .collect::<Vec<_>>();
+    if !missing.is_empty() {
+        bail!(
+            "Missing the expected outputs of {} system test case(s) in `{}`: {:?}",
+            missing.len(),
+            dir.join("out").display(),
+            missing,
+        );
+    }
+
+    Ok(cases)
+}
+
+/// Converts `run` into a `CommandExpression`.
+///
+/// The returned temporary file (if any) must be kept until the command finishes.
+pub(crate) fn command_expression(
+    run: config::Command,
+    base_dir: &Path,
+    cwd: Option<&str>,
+    env: &BTreeMap<String, String>,
+) -> anyhow::Result<(CommandExpression, Option<NamedTempFile>)> {
+    let cwd = working_directory(base_dir, cwd);
+    let env = env
+        .iter()
+        .map(|(key, value)| (key.into(), value.into()))
+        .collect::<BTreeMap<_, _>>();
+
+    Ok(match run {
         config::Command::Args(args) => {
             let cmd = CommandExpression {
                 program: args.get(0).cloned().unwrap_or_default().into(),
                 args: args.into_iter().skip(1).map(Into::into).collect(),
-                cwd: base_dir,
-                env: btreemap!(),
+                cwd,
+                env,
+                sandbox: None,
             };
 
             (cmd, None)
@@ -127,91 +448,100 @@ pub(crate) fn judge(args: Args<impl WriteColor, impl WriteColor>) -> anyhow::Res
             let cmd = CommandExpression {
                 program: program.into(),
                 args: vec![tempfile.path().into()],
-                cwd: base_dir,
-                env: btreemap!(),
+                cwd,
+                env,
+                sandbox: None,
             };
 
             (cmd, Some(tempfile))
         }
-    };
-
-    stderr.set_color(color_spec!(Bold, Fg(Color::Magenta)))?;
-    write!(stderr, "Test file:")?;
-    stderr.reset()?;
-    writeln!(stderr, " {}", test_suite_path.display())?;
-
-    stderr.set_color(color_spec!(Bold, Fg(Color::Magenta)))?;
-    write!(stderr, "Command:")?;
-    stderr.reset()?;
-    writeln!(stderr, " {}", shell_escape_args(&cmd.program, &cmd.args))?;
-
-    stderr.set_color(color_spec!(Bold, Fg(Color::Magenta)))?;
-    write!(stderr, "Working Directory:")?;
-    stderr.reset()?;
-    writeln!(stderr, " {}", cmd.cwd.display())?;
-
-    stderr.flush()?;
-
-    let outcome = snowchains_core::judge::judge(
-        progress_draw_target,
-        tokio::signal::ctrl_c,
-        &cmd,
-        &test_cases,
-    )?;
+    })
+}
 
-    if let Some(tempfile) = tempfile {
-        tempfile.close()?;
+/// Resolves `cwd` in a language or a build action against the directory of the config file.
+fn working_directory(base_dir: &Path, cwd: Option<&str>) -> PathBuf {
+    match cwd {
+        Some(cwd) => {
+            let cwd = Path::new(cwd);
+            base_dir.join(cwd.strip_prefix(".").unwrap_or(cwd))
+        }
+        None => base_dir.to_owned(),
     }
-
-    writeln!(stderr)?;
-    stderr.flush()?;
-    outcome.print_pretty(
-        stdout,
-        Some(display_limit.into::<Byte>().value().saturating_as()),
-    )?;
-
-    outcome.error_on_fail()
 }
 
-pub(crate) fn transpile(
+#[allow(clippy::type_complexity)]
+pub(crate) fn transpile<C: Future<Output = tokio::io::Result<()>>>(
     stderr: impl WriteColor,
     base_dir: &Path,
     src: &str,
     transpile: &config::Compile,
-    stdin_process_redirection: fn() -> Stdio,
-    stdout_process_redirection: fn() -> Stdio,
-    stderr_process_redirection: fn() -> Stdio,
+    cache: BuildCache,
+    redirections: (fn() -> Stdio, fn() -> Stdio, fn() -> Stdio),
+    ctrl_c: fn() -> C,
 ) -> anyhow::Result<()> {
     build(
         stderr,
         base_dir,
         src,
         transpile,
-        (
-            stdin_process_redirection,
-            stdout_process_redirection,
-            stderr_process_redirection,
-        ),
+        cache,
+        redirections,
         "Transpiling...",
+        ctrl_c,
     )
 }
 
-#[allow(clippy::type_complexity)]
-fn build(
+/// Decides whether `build` can skip building.
+///
+/// The output is regarded as up to date if it exists and the hash of the source, the resolved command and the mode is the same as the one recorded in `.snowchains/build-cache`.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct BuildCache {
+    pub(crate) mode: config::Mode,
+    /// Ignores the recorded hash and always builds.
+    pub(crate) force: bool,
+}
+
+impl BuildCache {
+    fn key(self, src: &Path, build_action: &config::Compile) -> anyhow::Result<String> {
+        let mut hasher = Sha256::new();
+        hasher.update(crate::fs::read(src)?);
+        hasher.update(format!("{:?}", build_action));
+        hasher.update(format!("{:?}", self.mode));
+        Ok(hex::encode(hasher.finalize()))
+    }
+
+    fn path(base_dir: &Path, output: &Path) -> PathBuf {
+        let name = hex::encode(Sha256::digest(output.to_string_lossy().as_bytes()));
+        base_dir.join(".snowchains").join("build-cache").join(name)
+    }
+}
+
+/// Builds `src` with `build_action` unless the output is up to date.
+///
+/// The build is killed when `ctrl_c` resolves or fails, so that `judge --watch` can restart it as soon as a change
+/// is detected.
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub(crate) fn build<C: Future<Output = tokio::io::Result<()>>>(
     mut stderr: impl WriteColor,
     base_dir: &Path,
     src: &str,
     build_action: &config::Compile,
+    cache: BuildCache,
     redirections: (fn() -> Stdio, fn() -> Stdio, fn() -> Stdio),
     msg: &'static str,
+    ctrl_c: fn() -> C,
 ) -> anyhow::Result<()> {
-    let src_modified = {
-        let src = Path::new(&src);
-        let src = base_dir.join(src.strip_prefix(".").unwrap_or(src));
-        crate::fs::metadata(src)?.modified()?
-    };
+    let src = Path::new(&src);
+    let src = base_dir.join(src.strip_prefix(".").unwrap_or(src));
 
-    let config::Compile { command, output } = build_action;
+    let config::Compile {
+        command,
+        output,
+        env,
+        cwd,
+    } = build_action;
+
+    let cwd = working_directory(base_dir, cwd.as_deref());
 
     let output = Path::new(&output);
     let output = base_dir.join(output.strip_prefix(".").unwrap_or(output));
@@ -219,7 +549,14 @@ fn build(
     let (stdin_process_redirection, stdout_process_redirection, stderr_process_redirection) =
         redirections;
 
-    if output.exists() && crate::fs::metadata(&output)?.modified()? > src_modified {
+    let key = cache.key(&src, build_action)?;
+    let cache_path = BuildCache::path(base_dir, &output);
+
+    if !cache.force
+        && output.exists()
+        && cache_path.exists()
+        && crate::fs::read_to_string(&cache_path)? == key
+    {
         writeln!(stderr, "{} is up to date.", output.display())?;
         stderr.flush()?;
     } else {
@@ -246,11 +583,15 @@ fn build(
             config::Command::Args(args) => run_command(
                 args.get(0).map(Deref::deref).unwrap_or(""),
                 args.iter().skip(1),
-                base_dir,
-                stdin_process_redirection(),
-                stdout_process_redirection(),
-                stderr_process_redirection(),
+                &cwd,
+                env,
+                (
+                    stdin_process_redirection(),
+                    stdout_process_redirection(),
+                    stderr_process_redirection(),
+                ),
                 &mut stderr,
+                ctrl_c,
             )?,
             config::Command::Script(config::Script {
                 program,
@@ -267,29 +608,45 @@ fn build(
                 run_command(
                     program,
                     &[tempfile.path()],
-                    base_dir,
-                    stdin_process_redirection(),
-                    stdout_process_redirection(),
-                    stderr_process_redirection(),
+                    &cwd,
+                    env,
+                    (
+                        stdin_process_redirection(),
+                        stdout_process_redirection(),
+                        stderr_process_redirection(),
+                    ),
                     &mut stderr,
+                    ctrl_c,
                 )?;
 
                 tempfile.close()?;
             }
         }
+
+        crate::fs::write(&cache_path, key, true)?;
     }
 
     Ok(())
 }
 
-fn run_command<S1: AsRef<OsStr>, S2: AsRef<OsStr>, I: IntoIterator<Item = S2>, W: WriteColor>(
+fn run_command<
+    S1: AsRef<OsStr>,
+    S2: AsRef<OsStr>,
+    I: IntoIterator<Item = S2>,
+    W: WriteColor,
+    C: Future<Output = tokio::io::Result<()>>,
+>(
     program: S1,
     args: I,
-    base_dir: &Path,
-    stdin_process_redirection: Stdio,
-    stdout_process_redirection: Stdio,
-    stderr_process_redirection: Stdio,
+    cwd: &Path,
+    env: &BTreeMap<String, String>,
+    (stdin_process_redirection, stdout_process_redirection, stderr_process_redirection): (
+        Stdio,
+        Stdio,
+        Stdio,
+    ),
     mut stderr: W,
+    ctrl_c: fn() -> C,
 ) -> anyhow::Result<()> {
     let program = program.as_ref();
 
@@ -308,17 +665,33 @@ fn run_command<S1: AsRef<OsStr>, S2: AsRef<OsStr>, I: IntoIterator<Item = S2>, W
     stderr.set_color(color_spec!(Bold, Fg(Color::Magenta)))?;
     write!(stderr, "Working Directory:")?;
     stderr.reset()?;
-    writeln!(stderr, " {}", base_dir.display())?;
+    writeln!(stderr, " {}", cwd.display())?;
 
     stderr.flush()?;
 
-    let status = std::process::Command::new(program)
-        .args(&args)
-        .current_dir(base_dir)
-        .stdin(stdin_process_redirection)
-        .stdout(stdout_process_redirection)
-        .stderr(stderr_process_redirection)
-        .status()?;
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()?;
+
+    let status = rt.block_on(async {
+        let mut child = tokio::process::Command::new(program)
+            .args(&args)
+            .current_dir(cwd)
+            .envs(env)
+            .stdin(stdin_process_redirection)
+            .stdout(stdout_process_redirection)
+            .stderr(stderr_process_redirection)
+            .kill_on_drop(true)
+            .spawn()?;
+
+        tokio::select! {
+            status = child.wait() => Ok(status?),
+            interruption = ctrl_c() => {
+                interruption?;
+                bail!("Recieved Ctrl-c");
+            }
+        }
+    })?;
 
     if !status.success() {
         bail!(
@@ -335,7 +708,7 @@ fn run_command<S1: AsRef<OsStr>, S2: AsRef<OsStr>, I: IntoIterator<Item = S2>, W
     Ok(())
 }
 
-fn shell_escape_args(program: impl AsRef<OsStr>, args: &[impl AsRef<OsStr>]) -> String {
+pub(crate) fn shell_escape_args(program: impl AsRef<OsStr>, args: &[impl AsRef<OsStr>]) -> String {
     format!(
         "`{}`",
         iter::once(program.as_ref())
@@ -344,3 +717,136 @@ fn shell_escape_args(program: impl AsRef<OsStr>, args: &[impl AsRef<OsStr>]) ->
             .format(" "),
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{config, judge::BuildCache};
+    use maplit::{btreemap, hashset};
+    use std::{
+        fs, io,
+        process::Stdio,
+        time::{Duration, Instant},
+    };
+    use termcolor::NoColor;
+
+    #[test]
+    fn build_cache() -> anyhow::Result<()> {
+        let tempdir = tempfile::Builder::new()
+            .prefix("snowchains-test-")
+            .tempdir()?;
+        fs::write(tempdir.path().join("a.txt"), "1")?;
+
+        let compile = |script: &str| config::Compile {
+            command: config::Command::Args(vec![
+                "bash".to_owned(),
+                "-c".to_owned(),
+                script.to_owned(),
+            ]),
+            output: "./a.out".to_owned(),
+            env: btreemap!(),
+            cwd: None,
+        };
+
+        let build = |compile: &config::Compile, mode, force| -> anyhow::Result<_> {
+            super::build(
+                NoColor::new(vec![]),
+                tempdir.path(),
+                "./a.txt",
+                compile,
+                BuildCache { mode, force },
+                (Stdio::null, Stdio::null, Stdio::null),
+                "Compiling...",
+                tokio::signal::ctrl_c,
+            )?;
+            Ok(fs::read_to_string(tempdir.path().join("builds"))?.len())
+        };
+
+        let cp = compile("echo -n . >> builds && cp a.txt a.out");
+        let (debug, release) = (config::Mode::Debug, config::Mode::Release);
+
+        assert_eq!(1, build(&cp, debug, false)?);
+        assert_eq!(1, build(&cp, debug, false)?);
+
+        fs::write(tempdir.path().join("a.txt"), "2")?;
+        assert_eq!(2, build(&cp, debug, false)?);
+        assert_eq!(2, build(&cp, debug, false)?);
+
+        let cp_verbose = compile("echo -n . >> builds && cp -v a.txt a.out");
+        assert_eq!(3, build(&cp_verbose, debug, false)?);
+        assert_eq!(4, build(&cp, debug, false)?);
+
+        assert_eq!(5, build(&cp, release, false)?);
+        assert_eq!(5, build(&cp, release, false)?);
+
+        assert_eq!(6, build(&cp, release, true)?);
+
+        fs::remove_file(tempdir.path().join("a.out"))?;
+        assert_eq!(7, build(&cp, release, false)?);
+        Ok(())
+    }
+
+    #[test]
+    fn build_is_interrupted() -> anyhow::Result<()> {
+        async fn changed() -> tokio::io::Result<()> {
+            Err(io::Error::new(io::ErrorKind::Interrupted, "changed"))
+        }
+
+        let tempdir = tempfile::Builder::new()
+            .prefix("snowchains-test-")
+            .tempdir()?;
+        fs::write(tempdir.path().join("a.txt"), "")?;
+
+        let compile = config::Compile {
+            command: config::Command::Args(vec!["sleep".to_owned(), "60".to_owned()]),
+            output: "./a.out".to_owned(),
+            env: btreemap!(),
+            cwd: None,
+        };
+
+        let start = Instant::now();
+        let result = super::build(
+            NoColor::new(vec![]),
+            tempdir.path(),
+            "./a.txt",
+            &compile,
+            BuildCache {
+                mode: config::Mode::Debug,
+                force: false,
+            },
+            (Stdio::null, Stdio::null, Stdio::null),
+            "Compiling...",
+            changed,
+        );
+
+        assert!(result.is_err());
+        assert!(start.elapsed() < Duration::from_secs(10));
+        Ok(())
+    }
+
+    #[test]
+    fn load_system_test_cases() -> anyhow::Result<()> {
+        let tempdir = tempfile::Builder::new()
+            .prefix("snowchains-test-")
+            .tempdir()?;
+
+        let dir = tempdir.path().join("a");
+        fs::create_dir_all(dir.join("in"))?;
+        fs::create_dir_all(dir.join("out"))?;
+        fs::write(dir.join("in").join("01.txt"), "1\n")?;
+        fs::write(dir.join("out").join("01.txt"), "2\n")?;
+        fs::write(dir.join("in").join("02.txt"), "3\n")?;
+
+        let load = |names| {
+            super::load_system_test_cases(tempdir.path(), &dir.with_extension("yml"), None, names)
+        };
+
+        let err = load(None).unwrap_err();
+        assert!(err.to_string().contains(r#"["02"]"#), "{}", err);
+
+        let cases = load(Some(&hashset!("01".to_owned())))?;
+        assert_eq!(1, cases.len());
+        assert_eq!(Some("01"), cases[0].name.as_deref());
+        assert_eq!(Some("2\n"), cases[0].out.as_deref());
+        Ok(())
+    }
+}
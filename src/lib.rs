@@ -1,22 +1,22 @@
 mod commands;
 mod config;
 mod fs;
+mod hooks;
 mod judge;
 pub mod shell;
+mod target_env;
+mod watch;
 mod web;
 
 pub use crate::commands::{
-    init::OptInit, judge::OptJudge, login::OptLogin, participate::OptParticipate,
-    retrieve_languages::OptRetrieveLanguages,
+    config_show::OptConfigShow, init::OptInit, judge::OptJudge, login::OptLogin,
+    participate::OptParticipate, retrieve_languages::OptRetrieveLanguages,
     retrieve_submission_summaries::OptRetrieveSubmissionSummaries,
-    retrieve_testcases::OptRetrieveTestcases, submit::OptSubmit,
+    retrieve_testcases::OptRetrieveTestcases, stress::OptStress, submit::OptSubmit,
     watch_submissions::OptWatchSubmissions, xtask::OptXtask,
 };
-use std::{env, io::BufRead, path::PathBuf};
-use structopt::{
-    clap::{self, AppSettings},
-    StructOpt,
-};
+use std::{io::BufRead, path::PathBuf};
+use structopt::{clap::AppSettings, StructOpt};
 use strum::{EnumString, EnumVariantNames};
 use termcolor::WriteColor;
 
@@ -52,6 +52,10 @@ pub enum Opt {
     #[structopt(author, visible_aliases(&["j", "test", "t"]))]
     Judge(OptJudge),
 
+    /// Tests code against a reference solution with generated inputs
+    #[structopt(author)]
+    Stress(OptStress),
+
     /// Submits code
     #[structopt(author, visible_alias("s"))]
     Submit(OptSubmit),
@@ -59,6 +63,10 @@ pub enum Opt {
     /// Runs a custom subcommand written in the config file
     #[structopt(author, visible_alias("x"), setting = AppSettings::TrailingVarArg)]
     Xtask(OptXtask),
+
+    /// Inspects the config file
+    #[structopt(author)]
+    Config(OptConfig),
 }
 
 #[derive(StructOpt, Debug)]
@@ -83,29 +91,14 @@ pub enum OptWatch {
     Submissions(OptWatchSubmissions),
 }
 
-impl Opt {
-    pub fn from_args_with_workaround_for_clap_issue_1538() -> Self {
-        let mut args = env::args_os().collect::<Vec<_>>();
-
-        Self::from_iter_safe(&args).unwrap_or_else(|clap::Error { kind, .. }| {
-            if matches!(
-                args.get(1).and_then(|s| s.to_str()),
-                Some("x") | Some("xtask")
-            ) && matches!(args.get(2).and_then(|s| s.to_str()), Some(s) if !s.starts_with('-'))
-                && matches!(
-                    kind,
-                    clap::ErrorKind::UnknownArgument
-                        | clap::ErrorKind::HelpDisplayed
-                        | clap::ErrorKind::VersionDisplayed
-                )
-            {
-                args.insert(3, "--".into());
-            }
-
-            Self::from_iter(args)
-        })
-    }
+#[derive(StructOpt, Debug)]
+pub enum OptConfig {
+    /// Prints the detected target and the evaluated language
+    #[structopt(author)]
+    Show(OptConfigShow),
+}
 
+impl Opt {
     pub fn color(&self) -> crate::ColorChoice {
         match *self {
             Self::Init(OptInit { color, .. })
@@ -120,7 +113,9 @@ impl Opt {
             | Self::Download(OptRetrieveTestcases { color, .. })
             | Self::Watch(OptWatch::Submissions(OptWatchSubmissions { color, .. }))
             | Self::Judge(OptJudge { color, .. })
-            | Self::Submit(OptSubmit { color, .. }) => color,
+            | Self::Stress(OptStress { color, .. })
+            | Self::Submit(OptSubmit { color, .. })
+            | Self::Config(OptConfig::Show(OptConfigShow { color, .. })) => color,
             Self::Xtask(_) => crate::ColorChoice::Auto,
         }
     }
@@ -155,7 +150,9 @@ pub fn run<R: BufRead, W1: WriteColor, W2: WriteColor>(
         Opt::Download(opt) => commands::retrieve_testcases::run(opt, ctx),
         Opt::Watch(OptWatch::Submissions(opt)) => commands::watch_submissions::run(opt, ctx),
         Opt::Judge(opt) => commands::judge::run(opt, ctx),
+        Opt::Stress(opt) => commands::stress::run(opt, ctx),
         Opt::Submit(opt) => commands::submit::run(opt, ctx),
         Opt::Xtask(opt) => commands::xtask::run(opt, ctx),
+        Opt::Config(OptConfig::Show(opt)) => commands::config_show::run(opt, ctx),
     }
 }
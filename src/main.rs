@@ -5,10 +5,11 @@ use std::{
     io::{self, Write as _},
     process,
 };
+use structopt::StructOpt as _;
 use termcolor::{Color, WriteColor as _};
 
 fn main() {
-    let opt = snowchains::Opt::from_args_with_workaround_for_clap_issue_1538();
+    let opt = snowchains::Opt::from_args();
     let color = opt.color();
 
     run_with_large_stack(|| {
@@ -39,15 +39,19 @@ impl<'a> Shell<StdinLock<'a>, BufferedStandardStream, BufferedStandardStream> {
 
 impl<R, W1, W2: WriteColor> Shell<R, W1, W2> {
     pub(crate) fn warn(&mut self, message: impl fmt::Display) -> io::Result<()> {
-        self.stderr
-            .set_color(color_spec!(Bold, Fg(Color::Yellow)))?;
-        write!(self.stderr, "warning:")?;
-        self.stderr.reset()?;
-        writeln!(self.stderr, " {}", message)?;
-        self.stderr.flush()
+        warn(&mut self.stderr, message)
     }
 }
 
+/// Prints `warning: {message}` to `stderr`, for where the [`Shell`] has already been taken apart.
+pub(crate) fn warn(mut stderr: impl WriteColor, message: impl fmt::Display) -> io::Result<()> {
+    stderr.set_color(color_spec!(Bold, Fg(Color::Yellow)))?;
+    write!(stderr, "warning:")?;
+    stderr.reset()?;
+    writeln!(stderr, " {}", message)?;
+    stderr.flush()
+}
+
 impl<R: BufRead, W1, W2: Write> Shell<R, W1, W2> {
     pub(crate) fn read_reply(&mut self, prompt: &'static str) -> io::Result<String> {
         write!(self.stderr, "{}", prompt)?;
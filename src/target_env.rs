@@ -0,0 +1,60 @@
+use crate::config;
+use snowchains_core::web::PlatformKind;
+use std::{collections::BTreeMap, path::Path};
+
+/// `SNOWCHAINS_*` environment variables given to `xtask` and `hooks`.
+///
+/// Variables for the unresolved fields are not set.
+#[derive(Default)]
+pub(crate) struct TargetEnv<'a> {
+    /// Directory of the config file.
+    pub(crate) workspace: Option<&'a Path>,
+    pub(crate) service: Option<PlatformKind>,
+    pub(crate) contest: Option<&'a str>,
+    pub(crate) problem: Option<&'a str>,
+    pub(crate) language: Option<&'a str>,
+    pub(crate) mode: Option<config::Mode>,
+    pub(crate) src: Option<&'a Path>,
+    pub(crate) test_suite: Option<&'a Path>,
+}
+
+impl TargetEnv<'_> {
+    pub(crate) fn to_env(&self) -> BTreeMap<String, String> {
+        let Self {
+            workspace,
+            service,
+            contest,
+            problem,
+            language,
+            mode,
+            src,
+            test_suite,
+        } = *self;
+
+        let mut env = BTreeMap::new();
+        let mut insert = |name: &str, value: Option<String>| {
+            if let Some(value) = value {
+                env.insert(format!("SNOWCHAINS_{}", name), value);
+            }
+        };
+
+        insert("WORKSPACE", workspace.map(|p| p.display().to_string()));
+        insert("SERVICE", service.map(|s| s.to_kebab_case_str().to_owned()));
+        insert("CONTEST", contest.map(ToOwned::to_owned));
+        insert("PROBLEM", problem.map(ToOwned::to_owned));
+        insert("LANGUAGE", language.map(ToOwned::to_owned));
+        insert(
+            "MODE",
+            mode.map(|mode| {
+                match mode {
+                    config::Mode::Debug => "debug",
+                    config::Mode::Release => "release",
+                }
+                .to_owned()
+            }),
+        );
+        insert("SRC", src.map(|p| p.display().to_string()));
+        insert("TEST_SUITE", test_suite.map(|p| p.display().to_string()));
+        env
+    }
+}
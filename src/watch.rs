@@ -0,0 +1,104 @@
+//! Polling file watcher for `judge --watch`.
+//!
+//! The state is global because `snowchains_core::judge::judge` takes the interruption as a `fn` pointer.
+
+use anyhow::bail;
+use std::{
+    fs, io,
+    path::PathBuf,
+    sync::atomic::{AtomicBool, Ordering},
+    thread,
+    time::{Duration, Instant, SystemTime},
+};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+static CHANGED: AtomicBool = AtomicBool::new(false);
+static RECEIVED_CTRL_C: AtomicBool = AtomicBool::new(false);
+
+/// Starts watching `paths` and Ctrl-c in background threads.
+///
+/// A change is reported once the files have stayed unchanged for `DEBOUNCE`.
+pub(crate) fn start(paths: Vec<PathBuf>) -> io::Result<()> {
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .enable_io()
+        .build()?;
+
+    thread::Builder::new()
+        .name("watch-ctrl-c".to_owned())
+        .spawn(move || {
+            let _ = rt.block_on(tokio::signal::ctrl_c());
+            RECEIVED_CTRL_C.store(true, Ordering::SeqCst);
+        })?;
+
+    thread::Builder::new()
+        .name("watch-files".to_owned())
+        .spawn(move || {
+            let mut last = snapshot(&paths);
+            let mut pending = None::<Instant>;
+
+            loop {
+                thread::sleep(POLL_INTERVAL);
+
+                let current = snapshot(&paths);
+                if current != last {
+                    last = current;
+                    pending = Some(Instant::now());
+                } else if matches!(pending, Some(t) if t.elapsed() >= DEBOUNCE) {
+                    pending = None;
+                    CHANGED.store(true, Ordering::SeqCst);
+                }
+            }
+        })?;
+
+    return Ok(());
+
+    fn snapshot(paths: &[PathBuf]) -> Vec<Option<(SystemTime, u64)>> {
+        paths
+            .iter()
+            .map(|path| {
+                let metadata = fs::metadata(path).ok()?;
+                Some((metadata.modified().ok()?, metadata.len()))
+            })
+            .collect()
+    }
+}
+
+/// Resolves on Ctrl-c, or fails when a change is detected. Passed to `snowchains_core::judge::judge`.
+pub(crate) async fn interruption() -> io::Result<()> {
+    loop {
+        if RECEIVED_CTRL_C.load(Ordering::SeqCst) {
+            return Ok(());
+        }
+        if CHANGED.load(Ordering::SeqCst) {
+            return Err(io::Error::new(
+                io::ErrorKind::Interrupted,
+                "Detected a change. Restarting",
+            ));
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
+
+/// Returns `true` and clears the flag if a change has been detected.
+pub(crate) fn take_change() -> bool {
+    CHANGED.swap(false, Ordering::SeqCst)
+}
+
+pub(crate) fn received_ctrl_c() -> bool {
+    RECEIVED_CTRL_C.load(Ordering::SeqCst)
+}
+
+/// Blocks until a change is detected.
+pub(crate) fn wait_for_change() -> anyhow::Result<()> {
+    loop {
+        if received_ctrl_c() {
+            bail!("Recieved Ctrl-c");
+        }
+        if take_change() {
+            return Ok(());
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
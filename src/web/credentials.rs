@@ -12,6 +12,12 @@ pub(crate) fn cookie_store_path() -> anyhow::Result<PathBuf> {
     Ok(data_local_dir.join("snowchains").join("cookies.jsonl"))
 }
 
+pub(crate) fn aoj_username_and_password<'a, R: BufRead, W1, W2: Write>(
+    shell: &'a RefCell<&'a mut crate::shell::Shell<R, W1, W2>>,
+) -> impl FnMut() -> anyhow::Result<(String, String)> + 'a {
+    username_and_password(shell, "User ID: ")
+}
+
 pub(crate) fn atcoder_username_and_password<'a, R: BufRead, W1, W2: Write>(
     shell: &'a RefCell<&'a mut crate::shell::Shell<R, W1, W2>>,
 ) -> impl FnMut() -> anyhow::Result<(String, String)> + 'a {
@@ -96,6 +102,20 @@ pub(crate) fn yukicoder_api_key(
     }
 }
 
+pub(crate) fn yukicoder_username(
+    shell: &mut crate::shell::Shell<impl BufRead, impl Sized, impl Write>,
+) -> anyhow::Result<String> {
+    let path = token_path("yukicoder-username.json")?;
+
+    if path.exists() {
+        crate::fs::read_json(path)
+    } else {
+        let username = shell.read_reply("yukicoder username: ")?;
+        crate::fs::write_json(path, &username, true)?;
+        Ok(username)
+    }
+}
+
 fn token_path(file_name: &str) -> anyhow::Result<PathBuf> {
     let data_local_dir =
         dirs_next::data_local_dir().with_context(|| "Could not find the local data directory")?;